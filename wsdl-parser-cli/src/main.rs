@@ -8,7 +8,10 @@ use anyhow::Context;
 use clap::Parser;
 use roxmltree::{Document, Node};
 use wsdl_parser::{generator::generate, parser::definitions::Definitions};
-use xsd_parser::{generator::builder::GeneratorBuilder, parser::schema::parse_schema};
+use xsd_parser::{
+    generator::{builder::GeneratorBuilder, customization::Customization, Generator},
+    parser::parse_schemas,
+};
 
 #[derive(Parser)]
 #[clap(name = env!("CARGO_PKG_NAME"))]
@@ -22,53 +25,89 @@ struct Opt {
     /// Output file
     #[clap(long, short)]
     output: Option<PathBuf>,
+
+    /// Customization file (TOML) with renames, type overrides, skipped types and derives
+    #[clap(long, short)]
+    customization: Option<PathBuf>,
 }
 
 fn main() -> anyhow::Result<()> {
     let opt: Opt = Opt::parse();
 
     let input_path = opt.input.unwrap_or_else(|| PathBuf::from("input/wsdl"));
+    let customization = match &opt.customization {
+        Some(path) => Customization::load(path)?,
+        None => Customization::default(),
+    };
+    let gen = customization.apply(GeneratorBuilder::default()).build();
     let md = fs::metadata(&input_path).unwrap();
     if md.is_dir() {
         let output_path = opt.output.unwrap_or_else(|| PathBuf::from("output/wsdl-rs"));
-        process_dir(&input_path, &output_path)?;
+        process_dir(&gen, &customization, &input_path, &output_path)?;
     } else {
-        process_single_file(&input_path, opt.output.as_deref())?;
+        process_single_file(&gen, &customization, &input_path, opt.output.as_deref())?;
+    }
+    // Selectors are matched by any of the generated schemas
+    if let Some(path) = &opt.customization {
+        for selector in customization.unmatched() {
+            eprintln!("{}: {} matches no type of the schemas", path.display(), selector);
+        }
     }
 
     Ok(())
 }
 
 //TODO: Add a common mechanism for working with files
-fn process_dir(input_path: &Path, output_path: &Path) -> anyhow::Result<()> {
+fn process_dir(
+    gen: &Generator,
+    customization: &Customization,
+    input_path: &Path,
+    output_path: &Path,
+) -> anyhow::Result<()> {
     if !output_path.exists() {
         fs::create_dir_all(output_path)?;
     }
     for entry in fs::read_dir(input_path)? {
         let path = entry?.path();
         if path.is_dir() {
-            process_dir(&path, &output_path.join(path.file_name().unwrap()))?;
+            process_dir(gen, customization, &path, &output_path.join(path.file_name().unwrap()))?;
         } else {
             let output_file_path = PathBuf::from(path.file_name().unwrap()).with_extension("rs");
             let output_file_path = output_path.join(output_file_path);
-            process_single_file(&path, Some(&output_file_path))?;
+            process_single_file(gen, customization, &path, Some(&output_file_path))?;
         }
     }
     Ok(())
 }
 
-fn process_single_file(input_path: &Path, output_path: Option<&Path>) -> anyhow::Result<()> {
+fn process_single_file(
+    gen: &Generator,
+    customization: &Customization,
+    input_path: &Path,
+    output_path: Option<&Path>,
+) -> anyhow::Result<()> {
     let text = load_file(input_path)?;
     let doc = Document::parse(text.as_str()).context("Failed to parse input document")?;
     let definitions = Definitions::new(&doc.root_element());
-    let gen = GeneratorBuilder::default().build();
     let schemas =
         definitions.types().iter().flat_map(|t| t.schemas()).collect::<Vec<Node<'_, '_>>>();
-    let mut code =
-        schemas.iter().map(|f| gen.generate_rs_file(&parse_schema(f))).collect::<Vec<String>>();
-
-    code.push(generate(&definitions));
-    let code = code.join("");
+    let files = parse_schemas(&schemas)
+        .with_context(|| format!("Error parsing schema in {}", input_path.display()))?;
+    for placeholder in files.iter().flat_map(|f| &f.placeholders) {
+        eprintln!("{}: {}", input_path.display(), placeholder);
+    }
+    customization.check(&files);
+    let code = gen.generate_rs_files(&files) + &generate(&definitions, gen);
+    for renamed in gen.take_renamed() {
+        eprintln!("{}: {}", input_path.display(), renamed);
+    }
+    let errors = gen.take_errors();
+    for error in errors.iter() {
+        eprintln!("{}: {}", input_path.display(), error);
+    }
+    if !errors.is_empty() {
+        anyhow::bail!("Error generating file {}", input_path.display());
+    }
     if let Some(output_filename) = output_path {
         write_to_file(output_filename, &code).context("Error writing file")?;
     } else {
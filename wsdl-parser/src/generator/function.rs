@@ -1,3 +1,5 @@
+use xsd_parser::parser::types::QName;
+
 use crate::parser::{
     definitions::Definitions,
     message::{Message, Part},
@@ -7,14 +9,16 @@ use crate::parser::{
     },
 };
 
-const GENERIC_TRANSPORT: Param<'static> = Param { name: "T", typename: "transport::Transport" };
-const ARGUMENT_TRANSPORT: Param<'static> = Param { name: "transport", typename: "T" };
+const GENERIC_TRANSPORT: Param<'static> =
+    Param { name: "T", typename: "transport::Transport", qname: None };
+const ARGUMENT_TRANSPORT: Param<'static> = Param { name: "transport", typename: "T", qname: None };
 
 #[derive(Debug)]
 pub struct Function<'a> {
     pub generic_params: Vec<Param<'a>>,
     pub arguments: Vec<Param<'a>>,
     pub return_type: &'a str,
+    pub return_qname: Option<QName>,
     pub documentation: Option<&'a str>,
     pub name: &'a str,
 }
@@ -23,6 +27,8 @@ pub struct Function<'a> {
 pub struct Param<'a> {
     pub name: &'a str,
     pub typename: &'a str,
+    // Element or type of a message part, the other parameters are Rust types
+    pub qname: Option<QName>,
 }
 
 impl<'a> Param<'a> {
@@ -33,6 +39,7 @@ impl<'a> Param<'a> {
                 .element()
                 .or_else(|| part.type_())
                 .expect("Element or type must be presented in wsdl:part"),
+            qname: part.qname(),
         }
     }
 }
@@ -52,10 +59,12 @@ impl<'a> Function<'a> {
             }
         };
 
+        let output_part = output_message.map(|m| &m.parts()[0]);
         Function {
             generic_params: vec![GENERIC_TRANSPORT],
             arguments: get_input_params(input_message),
-            return_type: output_message.and_then(|m| m.parts()[0].element()).unwrap_or(""),
+            return_type: output_part.and_then(|p| p.element()).unwrap_or(""),
+            return_qname: output_part.filter(|p| p.element().is_some()).and_then(|p| p.qname()),
             documentation: operation.documentation(),
             name: operation.name(),
         }
@@ -71,7 +80,12 @@ fn get_input_params<'a>(input: Option<&'a Message<'_>>) -> Vec<Param<'a>> {
 
     if input.parts().len() == 1 {
         // ONVIF case
-        result.push(Param { name: "request", typename: input.parts()[0].element().unwrap() })
+        let part = &input.parts()[0];
+        result.push(Param {
+            name: "request",
+            typename: part.element().unwrap(),
+            qname: part.qname(),
+        })
     } else {
         result.append(&mut input.parts().iter().map(Param::new).collect());
     };
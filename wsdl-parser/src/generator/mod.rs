@@ -1,28 +1,49 @@
-use std::borrow::Cow;
-
-use inflector::cases::{pascalcase::to_pascal_case, snakecase::to_snake_case};
-use roxmltree::Namespace;
+use xsd_parser::{
+    generator::{
+        default::{default_format_comment, default_format_name},
+        Generator,
+    },
+    parser::{
+        constants::namespace,
+        types::{Namespace, QName},
+    },
+};
 
 use crate::{generator::function::Function, parser::definitions::Definitions};
 
 pub mod function;
 
-pub fn generate(definitions: &Definitions) -> String {
+// Functions of the operations. The types of the messages are named by the generator
+// of the schemas, so they follow its passes and the names it allocated.
+pub fn generate(definitions: &Definitions, gen: &Generator) -> String {
+    set_namespaces(definitions, gen);
     let mut res = vec![];
 
     for port_type in definitions.port_types().values() {
         for op in port_type.operations() {
             let func = Function::new(op, definitions);
-            res.push(generate_function(&func, definitions.target_namespace()));
+            res.push(generate_function(&func, gen));
         }
     }
     res.join("")
 }
 
+// Type names of the messages are written with the prefixes of the WSDL file
+fn set_namespaces(definitions: &Definitions, gen: &Generator) {
+    let namespaces =
+        definitions.node().namespaces().map(Namespace::from).collect::<Vec<Namespace>>();
+    *gen.target_ns.borrow_mut() = definitions.target_namespace().map(Namespace::from);
+    *gen.xsd_ns.borrow_mut() = namespaces.iter().find(|ns| ns.uri() == namespace::XSD).cloned();
+    *gen.namespaces.borrow_mut() = namespaces;
+}
+
 const REQUEST_FUNC_BODY: &str = "transport::request(transport, request).await";
 
-fn generate_function(func: &Function<'_>, target_ns: Option<&Namespace>) -> String {
-    let ftype = |t| default_format_type(t, target_ns);
+fn generate_function(func: &Function<'_>, gen: &Generator) -> String {
+    let ftype = |typename: &str, qname: &Option<QName>| match qname {
+        Some(qname) => gen.format_reference(typename, Some(qname.clone())),
+        None => typename.to_string(),
+    };
     format!(
         r#"
 {comment}pub async fn {name}<{generics}>(
@@ -31,160 +52,28 @@ fn generate_function(func: &Function<'_>, target_ns: Option<&Namespace>) -> Stri
     {body}
 }}
 "#,
-        comment = default_format_comment(func.documentation, 80, 0),
+        comment = default_format_doc(func, 80),
         name = default_format_name(func.name),
         generics = func
             .generic_params
             .iter()
-            .map(|p| format!("{}: {}", p.name, ftype(p.typename)))
+            .map(|p| format!("{}: {}", p.name, ftype(p.typename, &p.qname)))
             .collect::<Vec<String>>()
             .join(", "),
         arguments = func
             .arguments
             .iter()
-            .map(|p| format!("{}: &{}", p.name, ftype(p.typename)))
+            .map(|p| format!("{}: &{}", p.name, ftype(p.typename, &p.qname)))
             .collect::<Vec<String>>()
             .join(",\n    "),
-        return_type = ftype(func.return_type),
+        return_type = ftype(func.return_type, &func.return_qname),
         body = REQUEST_FUNC_BODY
     )
 }
 
-fn split_comment_line(s: &str, max_len: usize, indent: usize) -> String {
-    let indent_str = " ".repeat(indent);
-
-    let mut splitted = format!("{}//", indent_str);
-    let mut current_line_length = indent + 2;
-    for word in s.split_whitespace() {
-        let len = word.len();
-        if current_line_length + len < max_len {
-            splitted = format!("{} {}", splitted, word);
-            current_line_length += 1 + len;
-        } else {
-            splitted = format!("{}\n{}// {}", splitted, indent_str, word);
-            current_line_length = indent + 3 + len;
-        }
-    }
-    format!("{}\n", splitted)
-}
-
-fn default_format_comment(doc: Option<&str>, max_len: usize, indent: usize) -> String {
-    doc.unwrap_or("")
-        .lines()
-        .map(|s| s.trim())
-        .filter(|s| s.len() > 1)
-        .map(|s| split_comment_line(s, max_len, indent))
-        .fold(String::new(), |x, y| (x + &y))
-}
-
-fn default_format_type(type_name: &str, target_ns: Option<&Namespace>) -> Cow<'static, str> {
-    let (prefix, name) = split_name(type_name);
-    let option_tns = target_ns.as_ref().and_then(|ns| ns.name());
-
-    let pascalized_name = filter_type_name(to_pascal_case(name).as_str());
-
-    let qname = |prefix| format!("{}::{}", prefix, pascalized_name);
-
-    let res = match (prefix, option_tns) {
-        (Some(ns), Some(tns)) => {
-            if ns == tns {
-                pascalized_name
-            } else {
-                qname(ns)
-            }
-        }
-        (Some(ns), None) => qname(ns),
-        _ => pascalized_name,
-    };
-
-    sanitize(res).into()
-}
-
-pub fn default_format_name(name: &str) -> String {
-    sanitize(to_snake_case(name.split(':').last().unwrap()))
+// Documentation of the operation, followed by its name as the function is renamed
+fn default_format_doc(func: &Function<'_>, max_len: usize) -> String {
+    let comment = default_format_comment(func.documentation, max_len, 0);
+    let separator = if comment.is_empty() { "" } else { "///\n" };
+    format!("{}{}/// WSDL: operation `{}`\n", comment, separator, func.name)
 }
-
-fn split_name(name: &str) -> (Option<&str>, &str) {
-    match name.find(':') {
-        Some(index) => (Some(&name[0..index]), &name[index + 1..]),
-        None => (None, name),
-    }
-}
-
-fn filter_type_name(name: &str) -> String {
-    fn is_valid_symbol(c: char) -> bool {
-        (c.is_alphanumeric() || c == '_') && c.is_ascii() && !c.is_whitespace()
-    }
-
-    name.chars().filter(|c| is_valid_symbol(*c)).collect()
-}
-
-fn sanitize(s: String) -> String {
-    if s.is_empty() {
-        s
-    } else if s.chars().next().unwrap().is_numeric() || RS_KEYWORDS.contains(&s.as_str()) {
-        format!("_{}", s)
-    } else {
-        s
-    }
-}
-
-const RS_KEYWORDS: &[&str] = &[
-    "abstract",
-    "alignof",
-    "as",
-    "async",
-    "await",
-    "become",
-    "box",
-    "break",
-    "const",
-    "continue",
-    "crate",
-    "do",
-    "dyn",
-    "else",
-    "enum",
-    "extern crate",
-    "extern",
-    "false",
-    "final",
-    "fn",
-    "for",
-    "if let",
-    "if",
-    "impl",
-    "in",
-    "let",
-    "loop",
-    "macro",
-    "match",
-    "mod",
-    "move",
-    "mut",
-    "offsetof",
-    "override",
-    "priv",
-    "proc",
-    "pub",
-    "pure",
-    "ref",
-    "return",
-    "Self",
-    "self",
-    "sizeof",
-    "static",
-    "struct",
-    "super",
-    "trait",
-    "true",
-    "type",
-    "typeof",
-    "unsafe",
-    "unsized",
-    "use",
-    "virtual",
-    "where",
-    "while",
-    "yield",
-];
@@ -69,7 +69,7 @@ impl<'a> Definitions<'a> {
         &self.port_types
     }
 
-    pub fn types(&self) -> &[Types] {
+    pub fn types(&self) -> &[Types<'_>] {
         self.types.as_ref()
     }
 
@@ -77,8 +77,19 @@ impl<'a> Definitions<'a> {
         &self.messages
     }
 
-    pub fn get_message_by_param(&self, param: &Param<'_>) -> Option<&Message> {
-        self.messages.get(param.message().split(':').last().unwrap())
+    pub fn get_message_by_param(&self, param: &Param<'_>) -> Option<&Message<'_>> {
+        // Messages of the definitions belong to its target namespace. Unprefixed names
+        // without a default namespace are commonly used for them too.
+        let (prefix, name) = match param.message().split_once(':') {
+            Some((prefix, name)) => (Some(prefix), name),
+            None => (None, param.message()),
+        };
+        let namespace = param.message_namespace();
+        let local = prefix.is_none() && namespace.is_none();
+        if !local && namespace != self.node().attribute(attribute::TARGET_NAMESPACE) {
+            return None;
+        }
+        self.messages.get(name)
     }
 
     pub fn new(definitions: &Node<'a, '_>) -> Self {
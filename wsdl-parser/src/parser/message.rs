@@ -1,4 +1,5 @@
 use roxmltree::Node;
+use xsd_parser::parser::{types::QName, xsd_elements::XsdNode};
 
 use crate::parser::{constants::attribute, ElementType, WsdlElement};
 
@@ -90,6 +91,11 @@ impl<'a> Part<'a> {
         self.node.attribute(attribute::TYPE)
     }
 
+    // Element (or type) of the part resolved against the namespace declarations in scope
+    pub fn qname(&self) -> Option<QName> {
+        self.element().or_else(|| self.type_()).and_then(|name| self.node.resolve_qname(name).ok())
+    }
+
     pub fn new(node: &Node<'a, '_>) -> Self {
         Self { node: *node }
     }
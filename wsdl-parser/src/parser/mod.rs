@@ -148,4 +148,33 @@ mod test {
         assert_eq!(def.imports().len(), 0);
         assert_eq!(def.port_types().len(), 1);
     }
+
+    #[test]
+    fn test_message_by_param() {
+        use crate::parser::port_type::Param;
+
+        let text = r#"
+<definitions xmlns:wsdl="http://schemas.xmlsoap.org/wsdl/"
+    xmlns:tds="http://www.onvif.org/ver10/device/wsdl"
+    xmlns:other="http://example.com"
+    targetNamespace="http://www.onvif.org/ver10/device/wsdl">
+    <message name="GetServicesRequest"/>
+    <portType name="Device">
+        <operation name="Local"><input message="GetServicesRequest"/></operation>
+        <operation name="Target"><input message="tds:GetServicesRequest"/></operation>
+        <operation name="Other"><input message="other:GetServicesRequest"/></operation>
+    </portType>
+</definitions>
+        "#;
+        let doc = Document::parse(text).unwrap();
+        let def = Definitions::new(&doc.root_element());
+        let found = doc
+            .descendants()
+            .filter(|n| n.has_tag_name("input"))
+            .map(|n| def.get_message_by_param(&Param::new(&n)).is_some())
+            .collect::<Vec<_>>();
+
+        // An unprefixed name without a default namespace refers to the target namespace
+        assert_eq!(found, [true, true, false]);
+    }
 }
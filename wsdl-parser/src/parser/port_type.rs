@@ -40,7 +40,7 @@ impl<'a> PortType<'a> {
         self.node.attribute(attribute::NAME).expect("Name required for wsdl:portType")
     }
 
-    pub fn operations(&self) -> &[Operation] {
+    pub fn operations(&self) -> &[Operation<'_>] {
         self.operations.as_ref()
     }
 }
@@ -92,7 +92,7 @@ impl<'a> Operation<'a> {
         self.node.attribute(attribute::PARAMETER_ORDER)
     }
 
-    pub fn operation_type(&self) -> &OperationType {
+    pub fn operation_type(&self) -> &OperationType<'_> {
         &self.ty
     }
 
@@ -191,6 +191,12 @@ impl<'a> Param<'a> {
             .attribute(attribute::MESSAGE)
             .expect("Message required for wsdl:input and wsdl:output")
     }
+
+    // Namespace of the message QName, resolved against the declarations in scope
+    pub fn message_namespace(&self) -> Option<&'a str> {
+        let prefix = self.message().split_once(':').map(|(prefix, _)| prefix);
+        self.node.lookup_namespace_uri(prefix)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
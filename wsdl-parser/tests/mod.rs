@@ -1,19 +1,28 @@
 use roxmltree::{Document, Node};
 use wsdl_parser::{generator::generate, parser::definitions::Definitions};
-use xsd_parser::{generator::builder::GeneratorBuilder, parser::schema::parse_schema};
+use xsd_parser::{
+    generator::{builder::GeneratorBuilder, Generator},
+    parser::parse_schemas,
+};
 
 mod port_type_to_function;
 
 pub fn generate_wsdl(input: &str) -> String {
+    generate_wsdl_with(input, &GeneratorBuilder::default().build())
+}
+
+pub fn generate_wsdl_with(input: &str, gen: &Generator) -> String {
     let doc = Document::parse(input).unwrap();
     let definitions = Definitions::new(&doc.root_element());
-    let gen = GeneratorBuilder::default().build();
     let schemas =
         definitions.types().iter().flat_map(|t| t.schemas()).collect::<Vec<Node<'_, '_>>>();
-    let mut code =
-        schemas.iter().map(|f| gen.generate_rs_file(&parse_schema(f))).collect::<Vec<String>>();
+    let mut code = parse_schemas(&schemas)
+        .unwrap()
+        .iter()
+        .map(|f| gen.generate_rs_file(f))
+        .collect::<Vec<String>>();
 
-    code.push(generate(&definitions));
+    code.push(generate(&definitions, gen));
     code.join("")
 }
 
@@ -1,9 +1,12 @@
 
+/// XSD: `tds:GetServices`
 #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
 #[yaserde(prefix = "tds", namespace = "tds: http://www.onvif.org/ver10/device/wsdl")]
 pub struct GetServices {
-    // Indicates if the service capabilities (untyped) should be included in the
-    // response.
+    /// Indicates if the service capabilities (untyped) should be included in
+    /// the response.
+    ///
+    /// XSD: element `IncludeCapability` of type `xs:boolean`
     #[yaserde(prefix = "tds", rename = "IncludeCapability")]
     pub include_capability: bool,
 }
@@ -11,17 +14,33 @@ pub struct GetServices {
 impl Validate for GetServices {}
 
 
+/// XSD: `tds:GetServicesResponse`
 #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
 #[yaserde(prefix = "tds", namespace = "tds: http://www.onvif.org/ver10/device/wsdl")]
 pub struct GetServicesResponse {
-    // Each Service element contains information about one service.
+    /// Each Service element contains information about one service.
+    ///
+    /// XSD: element `Service` of type `tds:Service`
     #[yaserde(prefix = "tds", rename = "Service")]
     pub service: Vec<Service>,
 }
 
-impl Validate for GetServicesResponse {}
+impl Validate for GetServicesResponse {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        errors.check(&format!("{}/Service", path), || {
+            #[allow(clippy::len_zero)]
+            if self.service.len() < 1 {
+                return Err(xs::ValidationError::new(xs::FacetKind::MinOccurs, "service", ">= 1", self.service.len()));
+            }
+            Ok(())
+        });
+        self.service.validate_at(&format!("{}/Service", path), errors);
+    }
+}
 
-// Returns information about services on the device.
+/// Returns information about services on the device.
+///
+/// WSDL: operation `GetServices`
 pub async fn get_services<T: transport::Transport>(
     transport: &T,
     request: &GetServices
@@ -1,4 +1,14 @@
-use super::{ast_test, generate_wsdl};
+use xsd_parser::{
+    generator::{
+        builder::GeneratorBuilder,
+        pass::{Rename, TypeOverride},
+    },
+    parser::types::QName,
+};
+
+use super::{ast_test, generate_wsdl, generate_wsdl_with};
+
+const TDS: &str = "http://www.onvif.org/ver10/device/wsdl";
 
 #[test]
 fn generator_does_not_panic() {
@@ -9,3 +19,19 @@ fn generator_does_not_panic() {
 fn generator_output_has_correct_ast() {
     ast_test(include_str!("input.xsd"), include_str!("expected.rs"));
 }
+
+#[test]
+fn message_types_follow_the_passes() {
+    let gen = GeneratorBuilder::default()
+        .with_pass(Box::new(
+            Rename::default().with_type(QName::new(Some(TDS), "GetServicesResponse"), "Services"),
+        ))
+        .with_pass(Box::new(
+            TypeOverride::default()
+                .with_type(QName::new(Some(TDS), "GetServices"), "crate::Request"),
+        ))
+        .build();
+    let code = generate_wsdl_with(include_str!("input.xsd"), &gen);
+    assert!(code.contains("pub struct Services {"));
+    assert!(code.contains("request: &crate::Request\n) -> Result<Services, transport::Error>"));
+}
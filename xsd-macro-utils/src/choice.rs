@@ -0,0 +1,509 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+
+// Content of an alternative, set by the #[choice(...)] attribute of the variant
+enum Branch {
+    Element(String),
+    Sequence(String),
+    Choice,
+    // Namespace constraint of xs:any and the target namespace it refers to
+    Any(String, Option<String>),
+    // Text of mixed content, see xs::Mixed
+    Text,
+}
+
+// Attributes of the enum: the namespaces of the prefixes used by the element names,
+// as in #[yaserde(namespace = "...")], and the elements of the other fields of the parent
+#[derive(Default)]
+struct Context {
+    namespaces: Vec<(Option<String>, String)>,
+    siblings: Option<String>,
+}
+
+fn context(ast: &syn::DeriveInput) -> syn::Result<Context> {
+    let mut context = Context::default();
+    for attr in ast.attrs.iter().filter(|a| a.path().is_ident("choice")) {
+        attr.parse_nested_meta(|meta| {
+            let value = meta.value()?.parse::<syn::LitStr>()?.value();
+            if meta.path.is_ident("namespace") {
+                context.namespaces.push(match value.split_once(':') {
+                    Some((prefix, uri)) if !prefix.contains('/') => {
+                        (Some(prefix.trim().to_string()), uri.trim().to_string())
+                    }
+                    _ => (None, value.trim().to_string()),
+                });
+            } else if meta.path.is_ident("siblings") {
+                context.siblings = Some(value);
+            } else {
+                return Err(meta.error("Expected namespace or siblings"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(context)
+}
+
+impl Context {
+    // ElementName of a name like "tns:Item", unprefixed names are in the default namespace
+    fn element_name(&self, name: &str, span: &impl quote::ToTokens) -> syn::Result<TokenStream> {
+        let (prefix, local_name) = match name.split_once(':') {
+            Some((prefix, local_name)) => (Some(prefix), local_name),
+            None => (None, name),
+        };
+        let namespace = self
+            .namespaces
+            .iter()
+            .find(|(p, _)| p.as_deref() == prefix)
+            .map(|(_, uri)| uri.as_str());
+        if prefix.is_some() && namespace.is_none() {
+            return Err(syn::Error::new_spanned(
+                span,
+                format!("Undeclared prefix of {}, expected #[choice(namespace = \"...\")]", name),
+            ));
+        }
+        let prefix = option(prefix);
+        let namespace = option(namespace);
+        Ok(quote! {
+            ::xsd_types::types::ElementName {
+                prefix: #prefix,
+                namespace: #namespace,
+                local_name: #local_name,
+            }
+        })
+    }
+
+    // Steps of a sequence described by a pattern like "a b? c|d* e{2,5}". A step matches
+    // any of the names separated by '|', the suffix is its occurrence as in a regex.
+    fn sequence_steps(
+        &self,
+        pattern: &str,
+        span: &impl quote::ToTokens,
+    ) -> syn::Result<TokenStream> {
+        let steps = pattern
+            .split_whitespace()
+            .map(|step| {
+                let (names, (min, max)) = occurs(step).ok_or_else(|| {
+                    syn::Error::new_spanned(span, format!("Invalid step {}", step))
+                })?;
+                let names = names
+                    .split('|')
+                    .map(|name| self.element_name(name, span))
+                    .collect::<syn::Result<Vec<_>>>()?;
+                let max = option(max);
+                Ok(quote! {
+                    ::xsd_types::types::SequenceStep { names: &[#(#names),*], min: #min, max: #max }
+                })
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+        Ok(quote! { &[#(#steps),*] })
+    }
+}
+
+// Names of a step of a sequence pattern with its min and max occurrence
+fn occurs(step: &str) -> Option<(&str, (usize, Option<usize>))> {
+    if let Some(names) = step.strip_suffix('?') {
+        return Some((names, (0, Some(1))));
+    }
+    if let Some(names) = step.strip_suffix('*') {
+        return Some((names, (0, None)));
+    }
+    if let Some(names) = step.strip_suffix('+') {
+        return Some((names, (1, None)));
+    }
+    let (names, range) = match step.strip_suffix('}').and_then(|s| s.split_once('{')) {
+        Some(step) => step,
+        None => return Some((step, (1, Some(1)))),
+    };
+    let occurs = match range.split_once(',') {
+        Some((min, "")) => (min.parse().ok()?, None),
+        Some((min, max)) => (min.parse().ok()?, Some(max.parse().ok()?)),
+        None => (range.parse().ok()?, Some(range.parse().ok()?)),
+    };
+    Some((names, occurs))
+}
+
+fn option<T: quote::ToTokens>(value: Option<T>) -> TokenStream {
+    match value {
+        Some(value) => quote! { ::std::option::Option::Some(#value) },
+        None => quote! { ::std::option::Option::None },
+    }
+}
+
+// Type of the variant value, or of its items if the alternative repeats
+enum Value<'a> {
+    Unit,
+    Single(&'a syn::Type),
+    Optional(&'a syn::Type),
+    Repeated(&'a syn::Type),
+}
+
+fn branch(variant: &syn::Variant) -> syn::Result<Branch> {
+    let attr =
+        variant.attrs.iter().find(|a| a.path().is_ident("choice")).ok_or_else(|| {
+            syn::Error::new_spanned(variant, "#[choice(...)] attribute is expected")
+        })?;
+
+    let mut branch = None;
+    let mut target_namespace = None;
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("choice") {
+            branch = Some(Branch::Choice);
+            return Ok(());
+        }
+        if meta.path.is_ident("text") {
+            branch = Some(Branch::Text);
+            return Ok(());
+        }
+        let value = meta.value()?.parse::<syn::LitStr>()?.value();
+        if meta.path.is_ident("element") {
+            branch = Some(Branch::Element(value));
+        } else if meta.path.is_ident("sequence") {
+            branch = Some(Branch::Sequence(value));
+        } else if meta.path.is_ident("any") {
+            branch = Some(Branch::Any(value, None));
+        } else if meta.path.is_ident("target_namespace") {
+            target_namespace = Some(value);
+        } else {
+            return Err(meta.error("Expected element, sequence, choice, any or text"));
+        }
+        Ok(())
+    })?;
+    match branch {
+        Some(Branch::Any(constraint, _)) => Ok(Branch::Any(constraint, target_namespace)),
+        Some(branch) => Ok(branch),
+        None => Err(syn::Error::new_spanned(attr, "Empty #[choice()] attribute")),
+    }
+}
+
+fn value(variant: &syn::Variant) -> syn::Result<Value<'_>> {
+    let ty = match &variant.fields {
+        syn::Fields::Unit => return Ok(Value::Unit),
+        syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+        fields => {
+            return Err(syn::Error::new_spanned(
+                fields,
+                "One unnamed field per variant is expected",
+            ));
+        }
+    };
+
+    Ok(match generic_argument(ty) {
+        Some(("Vec", item)) => Value::Repeated(item),
+        Some(("Option", item)) => Value::Optional(item),
+        _ => Value::Single(ty),
+    })
+}
+
+// The wrapper and the argument of types like Vec<T>
+fn generic_argument(ty: &syn::Type) -> Option<(&'static str, &syn::Type)> {
+    let segment = match ty {
+        syn::Type::Path(path) => path.path.segments.last()?,
+        _ => return None,
+    };
+    let wrapper = *["Vec", "Option"].iter().find(|w| segment.ident == w)?;
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first()? {
+            syn::GenericArgument::Type(item) => Some((wrapper, item)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// yaserde reads and writes built-in types only as fields of structs
+fn is_built_in(ty: &syn::Type) -> bool {
+    const BUILT_IN: [&str; 14] = [
+        "String", "bool", "char", "i8", "i16", "i32", "i64", "isize", "u8", "u16", "u32", "u64",
+        "f32", "f64",
+    ];
+    match ty {
+        syn::Type::Path(path) => path
+            .path
+            .get_ident()
+            .is_some_and(|ident| BUILT_IN.iter().any(|built_in| ident == built_in)),
+        _ => false,
+    }
+}
+
+// Expression reading one item of the alternative from `elements`
+fn read_item(
+    branch: &Branch,
+    ty: &syn::Type,
+    context: &Context,
+    ident: &syn::Ident,
+) -> syn::Result<TokenStream> {
+    Ok(match branch {
+        Branch::Element(name) if is_built_in(ty) => {
+            let name = context.element_name(name, ident)?;
+            quote! { ::xsd_types::types::read_text_element::<#ty>(elements, &#name) }
+        }
+        Branch::Element(name) => {
+            let name = context.element_name(name, ident)?;
+            quote! { ::xsd_types::types::read_element::<#ty>(elements, &#name) }
+        }
+        Branch::Sequence(pattern) => {
+            let steps = context.sequence_steps(pattern, ident)?;
+            quote! { ::xsd_types::types::read_sequence::<#ty>(elements, #steps) }
+        }
+        Branch::Choice => quote! {
+            <#ty as ::xsd_types::types::Choice>::read(elements)
+        },
+        Branch::Any(constraint, target_namespace) => {
+            let target_namespace = option(target_namespace.as_deref());
+            quote! {
+                Ok::<_, ::std::string::String>(
+                    ::xsd_types::types::read_any_element(elements, #constraint, #target_namespace),
+                )
+            }
+        }
+        Branch::Text => unreachable!(),
+    })
+}
+
+// Statement writing one item of the alternative to `elements`
+fn write_item(
+    branch: &Branch,
+    ty: &syn::Type,
+    value: TokenStream,
+    context: &Context,
+    ident: &syn::Ident,
+) -> syn::Result<TokenStream> {
+    Ok(match branch {
+        Branch::Element(name) if is_built_in(ty) => {
+            let name = context.element_name(name, ident)?;
+            quote! { ::xsd_types::types::write_text_element(#value, &#name, elements)?; }
+        }
+        Branch::Element(name) => {
+            let name = context.element_name(name, ident)?;
+            quote! { ::xsd_types::types::write_element(#value, &#name, elements)?; }
+        }
+        Branch::Sequence(_) => quote! {
+            ::xsd_types::types::write_sequence(#value, elements)?;
+        },
+        Branch::Choice => quote! {
+            ::xsd_types::types::Choice::write(#value, elements)?;
+        },
+        Branch::Any(..) => quote! {
+            ::xsd_types::types::write_any_element(#value, elements)?;
+        },
+        Branch::Text => unreachable!(),
+    })
+}
+
+pub fn serde(ast: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let enum_name = &ast.ident;
+    let context = context(ast)?;
+
+    let variants = match &ast.data {
+        syn::Data::Enum(data_enum) => data_enum
+            .variants
+            .iter()
+            .filter(|variant| &variant.ident.to_string() != "__Unknown__")
+            .map(|variant| Ok((&variant.ident, branch(variant)?, value(variant)?)))
+            .collect::<syn::Result<Vec<_>>>()?,
+        _ => {
+            return Err(syn::Error::new(Span::call_site(), "This macro can only be used on enums"));
+        }
+    };
+    let (text_variants, variants): (Vec<_>, Vec<_>) =
+        variants.into_iter().partition(|(_, branch, _)| matches!(branch, Branch::Text));
+    let text = match text_variants.as_slice() {
+        [] => quote! {},
+        [(ident, _, Value::Single(_))] => quote! {
+            fn from_text(text: &str) -> ::std::option::Option<Self> {
+                ::std::option::Option::Some(#enum_name::#ident(text.to_string()))
+            }
+
+            fn text(&self) -> ::std::option::Option<&str> {
+                match self {
+                    #enum_name::#ident(text) => ::std::option::Option::Some(text.as_str()),
+                    _ => ::std::option::Option::None,
+                }
+            }
+        },
+        [(ident, ..), ..] => {
+            return Err(syn::Error::new_spanned(ident, "One text variant of String is expected"));
+        }
+    };
+    let write_text = text_variants.iter().map(|(ident, ..)| quote! { #enum_name::#ident(_) => {} });
+
+    let read_variants = variants
+        .iter()
+        .map(|(ident, branch, value)| {
+            let read = match value {
+                Value::Unit => match branch {
+                    Branch::Element(name) => {
+                        let name = context.element_name(name, ident)?;
+                        return Ok(quote! {
+                            if let Some(count) = ::xsd_types::types::read_empty_element(elements, &#name) {
+                                return Ok(Some((#enum_name::#ident, count)));
+                            }
+                        })
+                    }
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            ident,
+                            "Only element alternatives can be unit variants",
+                        ))
+                    }
+                },
+                Value::Single(ty) => {
+                    let read = read_item(branch, ty, &context, ident)?;
+                    quote! { #read?.map(|(value, count)| (#enum_name::#ident(value), count)) }
+                }
+                Value::Optional(ty) => {
+                    let read = read_item(branch, ty, &context, ident)?;
+                    quote! { #read?.map(|(value, count)| (#enum_name::#ident(Some(value)), count)) }
+                }
+                Value::Repeated(ty) => {
+                    let read = read_item(branch, ty, &context, ident)?;
+                    quote! {
+                        ::xsd_types::types::read_repeated(elements, |elements| #read)?
+                            .map(|(values, count)| (#enum_name::#ident(values), count))
+                    }
+                }
+            };
+            Ok(quote! {
+                if let Some(choice) = #read {
+                    return Ok(Some(choice));
+                }
+            })
+        })
+        .collect::<syn::Result<TokenStream>>()?;
+
+    let write_variants = variants
+        .iter()
+        .map(|(ident, branch, value)| {
+            Ok(match value {
+                Value::Unit => {
+                    let name = match branch {
+                        Branch::Element(name) => context.element_name(name, ident)?,
+                        _ => unreachable!(),
+                    };
+                    quote! {
+                        #enum_name::#ident => ::xsd_types::types::write_empty_element(&#name, elements)?,
+                    }
+                }
+                Value::Single(ty) => {
+                    let write = write_item(branch, ty, quote!(value), &context, ident)?;
+                    quote! { #enum_name::#ident(value) => { #write } }
+                }
+                Value::Optional(ty) | Value::Repeated(ty) => {
+                    let write = write_item(branch, ty, quote!(value), &context, ident)?;
+                    quote! {
+                        #enum_name::#ident(values) => {
+                            for value in values.iter() {
+                                #write
+                            }
+                        }
+                    }
+                }
+            })
+        })
+        .collect::<syn::Result<TokenStream>>()?;
+
+    let element_variants = variants
+        .iter()
+        .filter_map(|(ident, branch, value)| {
+            let name = match branch {
+                Branch::Element(name) => name.rsplit(':').next().unwrap(),
+                _ => return None,
+            };
+            Some(match value {
+                Value::Unit => quote! { #enum_name::#ident => Some((#name, 1)), },
+                Value::Single(_) => quote! { #enum_name::#ident(_) => Some((#name, 1)), },
+                Value::Optional(_) => quote! {
+                    #enum_name::#ident(value) => Some((#name, value.is_some() as usize)),
+                },
+                Value::Repeated(_) => quote! {
+                    #enum_name::#ident(values) => Some((#name, values.len())),
+                },
+            })
+        })
+        .collect::<Vec<_>>();
+    let elements = match element_variants.as_slice() {
+        [] => quote! {},
+        _ => quote! {
+            fn elements(&self) -> ::std::option::Option<(&'static str, usize)> {
+                match self {
+                    #(#element_variants)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+        },
+    };
+
+    let siblings = match &context.siblings {
+        Some(siblings) => {
+            let names = siblings
+                .split_whitespace()
+                .map(|name| context.element_name(name, enum_name))
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote! {
+                const SIBLINGS: ::std::option::Option<&'static [::xsd_types::types::ElementName]> =
+                    ::std::option::Option::Some(&[#(#names),*]);
+            }
+        }
+        None => quote! {},
+    };
+
+    Ok(quote! {
+        impl ::xsd_types::types::Choice for #enum_name {
+            #siblings
+
+            fn read(
+                elements: &[::xsd_types::types::AnyElement],
+            ) -> ::std::result::Result<::std::option::Option<(Self, usize)>, ::std::string::String> {
+                #read_variants
+                Ok(None)
+            }
+
+            fn write(
+                &self,
+                elements: &mut ::std::vec::Vec<::xsd_types::types::AnyElement>,
+            ) -> ::std::result::Result<(), ::std::string::String> {
+                match self {
+                    #write_variants
+                    #(#write_text)*
+                    #enum_name::__Unknown__(_) => {}
+                }
+                Ok(())
+            }
+
+            #text
+
+            #elements
+        }
+
+        impl ::yaserde::YaSerialize for #enum_name {
+            fn serialize<W: ::std::io::Write>(
+                &self,
+                writer: &mut ::yaserde::ser::Serializer<W>,
+            ) -> ::std::result::Result<(), ::std::string::String> {
+                ::xsd_types::types::serialize_choice(self, writer)
+            }
+
+            fn serialize_attributes(
+                &self,
+                attributes: ::std::vec::Vec<::xml::attribute::OwnedAttribute>,
+                namespace: ::xml::namespace::Namespace,
+            ) -> ::std::result::Result<
+                (
+                    Vec<::xml::attribute::OwnedAttribute>,
+                    ::xml::namespace::Namespace,
+                ),
+                ::std::string::String,
+            > {
+                Ok((attributes, namespace))
+            }
+        }
+
+        impl ::yaserde::YaDeserialize for #enum_name {
+            fn deserialize<R: ::std::io::Read>(
+                reader: &mut ::yaserde::de::Deserializer<R>,
+            ) -> ::std::result::Result<Self, ::std::string::String> {
+                ::xsd_types::types::deserialize_choice(reader)
+            }
+        }
+    })
+}
@@ -2,6 +2,7 @@ use proc_macro::TokenStream;
 use quote::quote;
 use syn::{parse_macro_input, DeriveInput};
 
+mod choice;
 mod tuple;
 mod union;
 
@@ -67,3 +68,14 @@ pub fn union_serde(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
     union::serde(&ast).unwrap_or_else(|err| err.to_compile_error()).into()
 }
+
+// Reads and writes the alternatives of xs:choice, which are described by the
+// #[choice(element = "..." | sequence = "..." | choice | any = "..." | text)] attributes
+// of the variants, text being the text of mixed content. The enum declares the namespaces
+// of the prefixes with #[choice(namespace = "prefix: uri")] and the elements of the other
+// fields of the parent with #[choice(siblings = "...")].
+#[proc_macro_derive(UtilsChoiceSerDe, attributes(choice))]
+pub fn choice_serde(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    choice::serde(&ast).unwrap_or_else(|err| err.to_compile_error()).into()
+}
@@ -1,13 +1,16 @@
 use std::{
     fs,
     fs::OpenOptions,
-    io::{prelude::*, Read},
+    io::prelude::*,
     path::{Path, PathBuf},
 };
 
 use anyhow::Context;
 use clap::Parser;
-use xsd_parser::{generator::builder::GeneratorBuilder, parser::parse};
+use xsd_parser::{
+    generator::{builder::GeneratorBuilder, customization::Customization, Generator},
+    parser::{json::to_json, schema_set::SchemaLoader},
+};
 
 #[derive(Parser)]
 #[clap(name = env!("CARGO_PKG_NAME"))]
@@ -21,45 +24,112 @@ struct Opt {
     /// Output file
     #[clap(long, short)]
     output: Option<PathBuf>,
+
+    /// Directory to look up imported and included schemas in, can be repeated
+    #[clap(long = "search-path", short = 'I')]
+    search_paths: Vec<PathBuf>,
+
+    /// Write the parsed schema model as JSON instead of Rust code
+    #[clap(long)]
+    json: bool,
+
+    /// Customization file (TOML) with renames, type overrides, skipped types and derives
+    #[clap(long, short)]
+    customization: Option<PathBuf>,
 }
 
 fn main() -> anyhow::Result<()> {
     let opt: Opt = Opt::parse();
 
     let input_path = opt.input.unwrap_or_else(|| PathBuf::from("input/xsd"));
+    let loader = SchemaLoader::default().with_search_paths(opt.search_paths);
+    let customization = match &opt.customization {
+        Some(path) => Customization::load(path)?,
+        None => Customization::default(),
+    };
+    let gen = customization.apply(GeneratorBuilder::default()).build();
     let md = fs::metadata(&input_path).unwrap();
     if md.is_dir() {
         let output_path = opt.output.unwrap_or_else(|| PathBuf::from("output/rs"));
-        process_dir(&input_path, &output_path)?;
+        process_dir(&loader, &gen, &customization, &input_path, &output_path, opt.json)?;
     } else {
-        process_single_file(&input_path, opt.output.as_deref())?;
+        let output_path = opt.output.as_deref();
+        process_single_file(&loader, &gen, &customization, &input_path, output_path, opt.json)?;
+    }
+    // Selectors are matched by any of the generated schemas
+    if let Some(path) = &opt.customization {
+        for selector in customization.unmatched() {
+            eprintln!("{}: {} matches no type of the schemas", path.display(), selector);
+        }
     }
 
     Ok(())
 }
 
-fn process_dir(input_path: &Path, output_path: &Path) -> anyhow::Result<()> {
+fn process_dir(
+    loader: &SchemaLoader,
+    gen: &Generator,
+    customization: &Customization,
+    input_path: &Path,
+    output_path: &Path,
+    json: bool,
+) -> anyhow::Result<()> {
     if !output_path.exists() {
         fs::create_dir_all(output_path)?;
     }
     for entry in fs::read_dir(input_path)? {
         let path = entry?.path();
         if path.is_dir() {
-            process_dir(&path, &output_path.join(path.file_name().unwrap()))?;
+            let output_path = output_path.join(path.file_name().unwrap());
+            process_dir(loader, gen, customization, &path, &output_path, json)?;
         } else {
-            let output_file_path = PathBuf::from(path.file_name().unwrap()).with_extension("rs");
+            let extension = if json { "json" } else { "rs" };
+            let output_file_path =
+                PathBuf::from(path.file_name().unwrap()).with_extension(extension);
             let output_file_path = output_path.join(output_file_path);
-            process_single_file(&path, Some(&output_file_path))?;
+            process_single_file(loader, gen, customization, &path, Some(&output_file_path), json)?;
         }
     }
     Ok(())
 }
 
-fn process_single_file(input_path: &Path, output_path: Option<&Path>) -> anyhow::Result<()> {
-    let text = load_file(input_path)?;
-    let rs_file = parse(text.as_str()).map_err(|_| anyhow::anyhow!("Error parsing file"))?;
-    let gen = GeneratorBuilder::default().build();
-    let code = gen.generate_rs_file(&rs_file);
+fn process_single_file(
+    loader: &SchemaLoader,
+    gen: &Generator,
+    customization: &Customization,
+    input_path: &Path,
+    output_path: Option<&Path>,
+    json: bool,
+) -> anyhow::Result<()> {
+    let schema_set = loader
+        .load(input_path)
+        .with_context(|| format!("Error loading file {}", input_path.display()))?;
+    let rs_files = schema_set
+        .parse()
+        .with_context(|| format!("Error parsing file {}", input_path.display()))?;
+    for placeholder in rs_files.iter().flat_map(|f| &f.placeholders) {
+        eprintln!("{}: {}", input_path.display(), placeholder);
+    }
+    customization.check(&rs_files[..1]);
+    // The model includes the imported schemas, the code is generated for the input only
+    let code = if json {
+        to_json(&rs_files).context("Error serializing the schema model")?
+    } else {
+        // Elements of the imported schemas are allowed by strict wildcards of the input
+        gen.elements.take();
+        gen.declare_elements(&rs_files);
+        gen.generate_rs_file(&rs_files[0])
+    };
+    for renamed in gen.take_renamed() {
+        eprintln!("{}: {}", input_path.display(), renamed);
+    }
+    let errors = gen.take_errors();
+    for error in errors.iter() {
+        eprintln!("{}: {}", input_path.display(), error);
+    }
+    if !errors.is_empty() {
+        anyhow::bail!("Error generating file {}", input_path.display());
+    }
     if let Some(output_filename) = output_path {
         write_to_file(output_filename, &code).context("Error writing file")?;
     } else {
@@ -68,13 +138,6 @@ fn process_single_file(input_path: &Path, output_path: Option<&Path>) -> anyhow:
     Ok(())
 }
 
-fn load_file(path: &Path) -> std::io::Result<String> {
-    let mut file = fs::File::open(path)?;
-    let mut text = String::new();
-    file.read_to_string(&mut text)?;
-    Ok(text)
-}
-
 fn write_to_file(path: &Path, text: &str) -> std::io::Result<()> {
     let mut file = OpenOptions::new().write(true).truncate(true).create(true).open(path)?;
     file.write_all(text.as_bytes())
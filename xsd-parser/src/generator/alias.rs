@@ -6,7 +6,10 @@ pub trait AliasGenerator {
             "//{comment} pub type {name} = {original};\n",
             comment = self.format_comment(entity.comment.as_deref(), gen),
             name = self.format_name(entity.name.as_str(), gen),
-            original = self.format_original_type(entity.original.as_str(), gen)
+            original = match &entity.rust_type {
+                Some(rust_type) => rust_type.clone(),
+                None => self.format_original_type(entity.original.as_str(), gen),
+            }
         )
     }
 
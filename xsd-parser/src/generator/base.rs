@@ -3,12 +3,13 @@ use std::borrow::Cow;
 use crate::{
     generator::{
         default::{
-            default_format_comment, default_format_name, default_format_type, default_modify_type,
+            default_format_comment, default_format_doc, default_format_name, default_format_type,
+            default_modify_type,
         },
-        utils::match_built_in_type,
+        utils::{is_primitive_type, match_built_in_type, namespace_uri, split_name},
         Generator,
     },
-    parser::types::{RsEntity, TypeModifier},
+    parser::types::{QName, RsEntity, TypeModifier},
 };
 
 pub trait BaseGenerator {
@@ -21,10 +22,42 @@ pub trait BaseGenerator {
     }
 
     fn format_type_name(&self, type_name: &str, gen: &Generator) -> Cow<'_, str> {
-        if let Some(t) = match_built_in_type(type_name, &gen.xsd_ns.borrow()) {
+        let namespaces = gen.namespaces.borrow();
+        if let Some(t) = match_built_in_type(type_name, &gen.xsd_ns.borrow(), &namespaces) {
             return t.into();
         }
-        default_format_type(type_name, &gen.target_ns.borrow())
+
+        let target_ns = gen.target_ns.borrow();
+        // Any prefix bound to the target namespace refers to a type of the generated module
+        let target_uri = target_ns.as_ref().map(|ns| ns.uri());
+        if target_uri.is_some() && namespace_uri(type_name, &namespaces) == target_uri {
+            return default_format_type(split_name(type_name).1, &None);
+        }
+        default_format_type(type_name, &target_ns)
+    }
+
+    // Rust type set by passes (see TypeOverride) in place of the declared type
+    fn format_rust_type(
+        &self,
+        type_name: &str,
+        rust_type: Option<&str>,
+        gen: &Generator,
+    ) -> Cow<'_, str> {
+        match rust_type {
+            Some(rust_type) => rust_type.to_string().into(),
+            None => self.format_type_name(type_name, gen),
+        }
+    }
+
+    // Whether the type is generated from a schema, so it implements Validate.
+    // Built-in types, types of xsd-types and the String of declarations without
+    // a type aren't. Neither are the Rust types set by passes, see format_rust_type.
+    fn is_generated_type(&self, type_name: &str, gen: &Generator) -> bool {
+        !type_name.is_empty()
+            && !is_primitive_type(type_name)
+            && match_built_in_type(type_name, &gen.xsd_ns.borrow(), &gen.namespaces.borrow())
+                .is_none()
+            && !self.format_type_name(type_name, gen).starts_with("xs::")
     }
 
     fn format_name(&self, name: &str) -> Cow<'_, str> {
@@ -35,6 +68,52 @@ pub trait BaseGenerator {
         default_format_comment(comment, 80, indent)
     }
 
+    // Rustdoc of a generated item, the documentation and the details of its declaration
+    fn format_doc(&self, comment: Option<&str>, details: &[String], indent: usize) -> String {
+        default_format_doc(comment, details, 80, indent)
+    }
+
+    // Name of a declaration as written in the schema, e.g. tt:VideoSource
+    fn format_qname(&self, qname: &QName, gen: &Generator) -> String {
+        let namespaces = gen.namespaces.borrow();
+        let prefix = namespaces
+            .iter()
+            .filter(|ns| Some(ns.uri()) == qname.namespace.as_deref())
+            .find_map(|ns| ns.name());
+        match prefix {
+            Some(prefix) => format!("{}:{}", prefix, qname.name),
+            None => qname.name.clone(),
+        }
+    }
+
+    // Detail of the documentation of a type naming its global declaration
+    fn format_declaration(&self, qname: Option<&QName>, gen: &Generator) -> Option<String> {
+        qname.map(|qname| format!("XSD: `{}`", self.format_qname(qname, gen)))
+    }
+
+    // Detail of the documentation naming an element (or attribute) and its type.
+    // References to global declarations are named after them, the type is theirs.
+    fn format_element_declaration(
+        &self,
+        kind: &str,
+        name: &str,
+        type_qname: Option<&QName>,
+        gen: &Generator,
+    ) -> String {
+        match type_qname.map(|qname| self.format_qname(qname, gen)).filter(|t| t != name) {
+            Some(type_name) => format!("XSD: {} `{}` of type `{}`", kind, name, type_name),
+            None => format!("XSD: {} `{}`", kind, name),
+        }
+    }
+
+    // Derives added to a type by passes, next to the derives of the generator
+    fn format_derives(&self, derives: &[String]) -> String {
+        if derives.is_empty() {
+            return "".into();
+        }
+        format!("#[derive({})]\n", derives.join(", "))
+    }
+
     fn modify_type(&self, type_name: &str, modifiers: &[TypeModifier]) -> Cow<'_, str> {
         default_modify_type(type_name, modifiers)
     }
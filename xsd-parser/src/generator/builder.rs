@@ -3,6 +3,7 @@ use crate::generator::{
     base::{BaseGenerator, DefaultBaseGenerator},
     enum_case::{DefaultEnumCaseGen, EnumCaseGenerator},
     import::{DefaultImportGen, ImportGenerator},
+    pass::Pass,
     r#enum::{DefaultEnumGen, EnumGenerator},
     r#struct::{DefaultStructGen, StructGenerator},
     struct_field::{DefaultStructFieldGen, StructFieldGenerator},
@@ -11,12 +12,12 @@ use crate::generator::{
 };
 
 #[derive(Default)]
-pub struct GeneratorBuilder<'input> {
-    gen: Generator<'input>,
+pub struct GeneratorBuilder {
+    gen: Generator,
 }
 
 #[allow(dead_code)]
-impl<'input> GeneratorBuilder<'input> {
+impl GeneratorBuilder {
     pub fn with_base_gen(mut self, base: Box<dyn BaseGenerator>) -> Self {
         self.gen.base = Some(base);
         self
@@ -57,7 +58,13 @@ impl<'input> GeneratorBuilder<'input> {
         self
     }
 
-    pub fn build(self) -> Generator<'input> {
+    // Passes rewrite the parsed files before generation, in the order they are added
+    pub fn with_pass(mut self, pass: Box<dyn Pass>) -> Self {
+        self.gen.passes.push(pass);
+        self
+    }
+
+    pub fn build(self) -> Generator {
         let mut gen = self.gen;
         gen.base.get_or_insert_with(|| Box::new(DefaultBaseGenerator {})); //.set_target_ns(&gen.target_ns);
 
@@ -0,0 +1,408 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashSet},
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::{
+    generator::{
+        builder::GeneratorBuilder,
+        pass::{walk_entity, Derive, Pass, Prune, Rename, TypeOverride},
+        utils::split_name,
+    },
+    parser::{
+        constants::namespace,
+        types::{Alias, EnumCase, QName, RsFile, StructField, TupleStruct},
+    },
+};
+
+// Customization of the generated code for schemas which can't be edited, read from a
+// TOML file. Types are selected by "prefix:Name" with the prefixes declared in the
+// file (xs and xsd refer to XML Schema unless declared), "{uri}Name", or "Name" for
+// types without a namespace. Fields are selected by "prefix:Type/Field".
+//
+//     [namespaces]
+//     tt = "http://www.onvif.org/ver10/schema"
+//
+//     [rename]
+//     "tt:Transport" = "StreamTransport"
+//
+//     [types]
+//     "tt:ReferenceToken" = "crate::Token"
+//
+//     skip = ["tt:Unused", "tt:Transport/Tunnel"]
+//
+//     [derives]
+//     "tt:ReferenceToken" = ["Eq", "Hash"]
+//
+// Selectors refer to the names in the schema, so a renamed type keeps its original
+// name in the other sections. Selectors matching nothing are reported by unmatched.
+#[derive(Default)]
+pub struct Customization {
+    rename: Rename,
+    types: TypeOverride,
+    skip: Prune,
+    derives: Derive,
+    selectors: Vec<Selector>,
+    // Indices of the selectors matched by the schemas passed to check
+    matched: RefCell<HashSet<usize>>,
+}
+
+struct Selector {
+    section: &'static str,
+    text: String,
+    name: QName,
+    field: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct CustomizationFile {
+    namespaces: BTreeMap<String, String>,
+    rename: BTreeMap<String, String>,
+    types: BTreeMap<String, String>,
+    skip: Vec<String>,
+    derives: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug)]
+pub enum CustomizationError {
+    /// The customization file could not be read.
+    Io { path: PathBuf, error: io::Error },
+    /// The file is not valid TOML or has unknown sections.
+    Toml(toml::de::Error),
+    /// A selector has an undeclared prefix or is not a type (or field) name.
+    InvalidSelector { selector: String, reason: String },
+}
+
+impl fmt::Display for CustomizationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CustomizationError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            CustomizationError::Toml(error) => write!(f, "Invalid customization: {}", error),
+            CustomizationError::InvalidSelector { selector, reason } => {
+                write!(f, "Invalid selector \"{}\": {}", selector, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CustomizationError {}
+
+impl Customization {
+    pub fn load(path: &Path) -> Result<Self, CustomizationError> {
+        let text = fs::read_to_string(path)
+            .map_err(|error| CustomizationError::Io { path: path.to_path_buf(), error })?;
+        Self::from_toml(&text)
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self, CustomizationError> {
+        let file: CustomizationFile = toml::from_str(text).map_err(CustomizationError::Toml)?;
+
+        let mut customization = Customization::default();
+        for (selector, name) in &file.rename {
+            let qname = customization.select("rename", selector, file.type_name(selector)?, None);
+            customization.rename = customization.rename.with_type(qname, name);
+        }
+        for (selector, rust_type) in &file.types {
+            let qname = customization.select("types", selector, file.type_name(selector)?, None);
+            customization.types = customization.types.with_type(qname, rust_type);
+        }
+        for selector in &file.skip {
+            let (type_name, field) = split_field(selector);
+            let qname = customization.select("skip", selector, file.type_name(type_name)?, field);
+            customization.skip = match field {
+                Some(field) => customization.skip.with_field(qname, field),
+                None => customization.skip.with_type(qname),
+            };
+        }
+        for (selector, derives) in &file.derives {
+            let derives = derives.iter().map(String::as_str).collect::<Vec<_>>();
+            let qname = customization.select("derives", selector, file.type_name(selector)?, None);
+            customization.derives = customization.derives.with_type(qname, &derives);
+        }
+        Ok(customization)
+    }
+
+    fn select(
+        &mut self,
+        section: &'static str,
+        text: &str,
+        name: QName,
+        field: Option<&str>,
+    ) -> QName {
+        self.selectors.push(Selector {
+            section,
+            text: text.to_string(),
+            name: name.clone(),
+            field: field.map(|field| field.to_string()),
+        });
+        name
+    }
+
+    // Adds the passes applying the customization. Types are skipped and overridden
+    // before they are renamed, as all of them are selected by their original names.
+    pub fn apply(&self, builder: GeneratorBuilder) -> GeneratorBuilder {
+        builder
+            .with_pass(Box::new(self.skip.clone()))
+            .with_pass(Box::new(self.types.clone()))
+            .with_pass(Box::new(self.derives.clone()))
+            .with_pass(Box::new(self.rename.clone()))
+    }
+
+    // Marks the selectors matching the schemas: the declaration of a type, or a field
+    // of a struct. Overridden types may also be referred to only, e.g. xs:dateTime.
+    pub fn check(&self, files: &[RsFile]) {
+        let declarations = Declarations::default();
+        for file in files {
+            declarations.run(&mut file.clone());
+        }
+        let mut matched = self.matched.borrow_mut();
+        for (index, selector) in self.selectors.iter().enumerate() {
+            let found = match (&selector.field, selector.section) {
+                (Some(field), _) => {
+                    declarations.fields.borrow().contains(&(selector.name.clone(), field.clone()))
+                }
+                (None, "types") => {
+                    declarations.types.borrow().contains(&selector.name)
+                        || declarations.references.borrow().contains(&selector.name)
+                }
+                (None, _) => declarations.types.borrow().contains(&selector.name),
+            };
+            if found {
+                matched.insert(index);
+            }
+        }
+    }
+
+    // Selectors which matched none of the schemas passed to check, e.g. misspelled
+    // names, as "[section] selector"
+    pub fn unmatched(&self) -> Vec<String> {
+        let matched = self.matched.borrow();
+        self.selectors
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !matched.contains(index))
+            .map(|(_, selector)| format!("[{}] \"{}\"", selector.section, selector.text))
+            .collect()
+    }
+}
+
+// Global types, fields of structs and type references of the schemas, to tell which
+// selectors match. It collects them like a pass, but doesn't change the files.
+#[derive(Default)]
+struct Declarations {
+    types: RefCell<HashSet<QName>>,
+    fields: RefCell<HashSet<(QName, String)>>,
+    references: RefCell<HashSet<QName>>,
+}
+
+impl Declarations {
+    fn reference(&self, qname: &Option<QName>) {
+        if let Some(qname) = qname {
+            self.references.borrow_mut().insert(qname.clone());
+        }
+    }
+}
+
+impl Pass for Declarations {
+    fn run(&self, file: &mut RsFile) {
+        let namespace = file.namespace.as_deref();
+        for entity in file.types.iter_mut() {
+            self.types.borrow_mut().insert(QName::new(namespace, entity.name()));
+            entity.visit_structs(&mut |st| {
+                let name = QName::new(namespace, &st.name);
+                self.fields.borrow_mut().extend(
+                    st.fields.iter().map(|f| (name.clone(), split_name(&f.name).1.to_string())),
+                );
+            });
+            walk_entity(self, entity);
+        }
+    }
+
+    fn visit_field(&self, entity: &mut StructField) {
+        self.reference(&entity.type_qname);
+    }
+
+    fn visit_tuple_struct(&self, entity: &mut TupleStruct) {
+        self.reference(&entity.type_qname);
+    }
+
+    fn visit_enum_case(&self, entity: &mut EnumCase) {
+        self.reference(&entity.type_qname);
+    }
+
+    fn visit_alias(&self, entity: &mut Alias) {
+        self.reference(&entity.original_qname);
+    }
+}
+
+// Splits "Type/Field" selectors, the namespace uri of a type can contain slashes
+fn split_field(selector: &str) -> (&str, Option<&str>) {
+    let start = selector.find('}').map_or(0, |index| index + 1);
+    match selector[start..].find('/') {
+        Some(index) => (&selector[..start + index], Some(&selector[start + index + 1..])),
+        None => (selector, None),
+    }
+}
+
+impl CustomizationFile {
+    fn type_name(&self, selector: &str) -> Result<QName, CustomizationError> {
+        let invalid = |reason: &str| CustomizationError::InvalidSelector {
+            selector: selector.to_string(),
+            reason: reason.to_string(),
+        };
+
+        let (namespace, name) = if let Some(qualified) = selector.strip_prefix('{') {
+            let (uri, name) = qualified.split_once('}').ok_or_else(|| invalid("missing '}'"))?;
+            (Some(uri), name)
+        } else if let Some((prefix, name)) = selector.split_once(':') {
+            let uri = match (self.namespaces.get(prefix), prefix) {
+                (Some(uri), _) => uri.as_str(),
+                (None, "xs") | (None, "xsd") => namespace::XSD,
+                (None, _) => return Err(invalid("the prefix is not declared in [namespaces]")),
+            };
+            (Some(uri), name)
+        } else {
+            (None, selector)
+        };
+
+        if name.is_empty() || name.contains([':', '/', '{', '}']) {
+            return Err(invalid("expected a type name"));
+        }
+        Ok(QName::new(namespace, name))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        generator::{
+            builder::GeneratorBuilder,
+            customization::{Customization, CustomizationError},
+        },
+        parser::parse,
+    };
+
+    #[test]
+    fn test_customization() {
+        let customization = Customization::from_toml(
+            r#"
+            skip = ["{http://www.onvif.org/ver10/schema}Unused", "tt:Transport/Internal"]
+
+            [namespaces]
+            tt = "http://www.onvif.org/ver10/schema"
+
+            [rename]
+            "tt:Transport" = "StreamTransport"
+
+            [types]
+            "tt:ReferenceToken" = "crate::Token"
+            "xs:dateTime" = "chrono::NaiveDateTime"
+
+            [derives]
+            "tt:Transport" = ["Eq"]
+            "#,
+        )
+        .unwrap();
+
+        let schema = parse(
+            r#"
+        <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"
+                   xmlns:tt="http://www.onvif.org/ver10/schema"
+                   targetNamespace="http://www.onvif.org/ver10/schema">
+            <xs:complexType name="Transport">
+                <xs:sequence>
+                    <xs:element name="Token" type="tt:ReferenceToken"/>
+                    <xs:element name="Started" type="xs:dateTime"/>
+                    <xs:element name="Internal" type="xs:string"/>
+                </xs:sequence>
+            </xs:complexType>
+            <xs:simpleType name="ReferenceToken">
+                <xs:restriction base="xs:string"/>
+            </xs:simpleType>
+            <xs:simpleType name="Unused">
+                <xs:restriction base="xs:string"/>
+            </xs:simpleType>
+        </xs:schema>
+        "#,
+        )
+        .unwrap();
+
+        customization.check(std::slice::from_ref(&schema));
+        assert!(customization.unmatched().is_empty());
+
+        let gen = customization.apply(GeneratorBuilder::default()).build();
+        let code = gen.generate_rs_file(&schema);
+
+        assert!(code.contains("#[derive(Eq)]\n"));
+        assert!(code.contains("pub struct StreamTransport {"));
+        assert!(code.contains("pub token: crate::Token,"));
+        assert!(code.contains("pub started: chrono::NaiveDateTime,"));
+        assert!(!code.contains("internal"));
+        assert!(!code.contains("Unused"));
+    }
+
+    #[test]
+    fn test_unmatched_selectors() {
+        let customization = Customization::from_toml(
+            r#"
+            skip = ["tns:Item/Missing", "tns:Item/Code"]
+
+            [namespaces]
+            tns = "http://example.com"
+
+            [rename]
+            "tns:Itme" = "Entry"
+
+            [types]
+            "xs:dateTime" = "chrono::NaiveDateTime"
+            "xs:duration" = "String"
+            "#,
+        )
+        .unwrap();
+        let schema = parse(
+            r#"
+        <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"
+                   xmlns:tns="http://example.com"
+                   targetNamespace="http://example.com">
+            <xs:complexType name="Item">
+                <xs:sequence>
+                    <xs:element name="Code" type="xs:string"/>
+                    <xs:element name="Created" type="xs:dateTime"/>
+                </xs:sequence>
+            </xs:complexType>
+        </xs:schema>
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(customization.unmatched().len(), 5);
+        customization.check(std::slice::from_ref(&schema));
+        assert_eq!(
+            customization.unmatched(),
+            vec!["[rename] \"tns:Itme\"", "[types] \"xs:duration\"", "[skip] \"tns:Item/Missing\""]
+        );
+    }
+
+    #[test]
+    fn test_invalid_customization() {
+        let error = |text| match Customization::from_toml(text) {
+            Err(error) => error,
+            Ok(_) => unreachable!("Expected an error"),
+        };
+
+        assert!(matches!(error("[unknown]"), CustomizationError::Toml(_)));
+        assert!(matches!(
+            error("[rename]\n\"tt:Transport\" = \"StreamTransport\""),
+            CustomizationError::InvalidSelector { .. }
+        ));
+        assert_eq!(
+            error("skip = [\"{http://example.com}/Field\"]").to_string(),
+            "Invalid selector \"{http://example.com}\": expected a type name"
+        );
+    }
+}
@@ -1,11 +1,13 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, fmt, str::FromStr};
 
 use inflector::cases::{pascalcase::to_pascal_case, snakecase::to_snake_case};
-use roxmltree::Namespace;
+use xsd_types::types as xs;
 
 use crate::{
-    generator::utils::{filter_type_name, sanitize, split_comment_line, split_name},
-    parser::types::TypeModifier,
+    generator::utils::{
+        filter_type_name, is_primitive_type, sanitize, split_comment_line, split_name,
+    },
+    parser::types::{Facet, Namespace, TypeModifier},
 };
 
 pub fn default_format_comment(doc: Option<&str>, max_len: usize, indent: usize) -> String {
@@ -14,11 +16,72 @@ pub fn default_format_comment(doc: Option<&str>, max_len: usize, indent: usize)
         .map(|s| s.trim())
         .filter(|s| s.len() > 1)
         .map(|s| split_comment_line(s, max_len, indent))
-        .fold(String::new(), |x, y| (x + &y))
+        .fold(String::new(), |x, y| x + &y)
+}
+
+// Documentation is markdown in rustdoc. Code-like words (URLs, paths, identifiers
+// with underscores, markup) are put in code spans, so they are shown as they are,
+// and the other characters with a meaning in markdown are escaped.
+pub fn escape_doc(word: &str) -> String {
+    const MARKDOWN: &[char] = &['\\', '`', '*', '_', '[', ']', '<', '>', '#', '|', '~'];
+    const CODE: &[&str] = &["://", "::", "()", "_", "<", ">", "[", "]", "{", "}", "=", "*", "|"];
+
+    // Punctuation around the word stays out of the code span
+    let start = word.len() - word.trim_start_matches(['(', '"', '\'']).len();
+    let core = word[start..].trim_end_matches(['.', ',', ';', ':', ')', '!', '?', '"', '\'']);
+    if !core.is_empty() && !core.contains('`') && CODE.iter().any(|c| core.contains(c)) {
+        let end = start + core.len();
+        return format!("{}`{}`{}", &word[..start], core, &word[end..]);
+    }
+    word.chars().fold(String::new(), |mut escaped, c| {
+        if MARKDOWN.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+        escaped
+    })
+}
+
+// Code span of a text as it is. The fence is longer than the runs of backticks in the
+// text, which is padded with spaces if it starts or ends with one.
+pub fn code_span(text: &str) -> String {
+    let longest = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest + 1);
+    let padding = if text.starts_with('`') || text.ends_with('`') { " " } else { "" };
+    format!("{fence}{padding}{text}{padding}{fence}", fence = fence, padding = padding, text = text)
+}
+
+// Facets of a value as declared in the schema, for the documentation
+pub fn default_format_facets(facets: &[Facet]) -> Option<String> {
+    if facets.is_empty() {
+        return None;
+    }
+    let facets = facets.iter().map(|f| code_span(&f.facet_type.to_string())).collect::<Vec<_>>();
+    Some(format!("Facets: {}", facets.join(", ")))
+}
+
+// The documentation followed by details of the declaration in the schema, one per line.
+// Details are markdown already, e.g. the names and facets in code spans.
+pub fn default_format_doc(
+    doc: Option<&str>,
+    details: &[String],
+    max_len: usize,
+    indent: usize,
+) -> String {
+    let comment = default_format_comment(doc, max_len, indent);
+    let indent = " ".repeat(indent);
+    let separator = if comment.is_empty() || details.is_empty() {
+        "".into()
+    } else {
+        format!("{}///\n", indent)
+    };
+    details
+        .iter()
+        .fold(comment + &separator, |doc, detail| format!("{}{}/// {}\n", doc, indent, detail))
 }
 
 pub fn default_format_name(name: &str) -> String {
-    sanitize(to_snake_case(name.split(':').last().unwrap()))
+    sanitize(to_snake_case(name.split(':').next_back().unwrap()))
 }
 
 pub fn default_format_type(type_name: &str, target_ns: &Option<Namespace>) -> Cow<'static, str> {
@@ -49,11 +112,20 @@ pub fn default_modify_type(type_name: &str, modifiers: &[TypeModifier]) -> Cow<'
         return "()".into();
     }
 
-    if modifiers.contains(&TypeModifier::Recursive) {
-        return format!("Vec<{}>", type_name).into();
+    let mut result = if modifiers.contains(&TypeModifier::Nillable) {
+        // yaserde deserializes primitive types only as fields, so they need a text wrapper
+        if is_primitive_type(type_name) {
+            format!("xs::NillableText<{}>", type_name)
+        } else {
+            format!("xs::Nillable<{}>", type_name)
+        }
+    } else {
+        type_name.to_string()
+    };
+    // A repeated field is stored in a Vec, so it doesn't need another indirection
+    if modifiers.contains(&TypeModifier::Recursive) && !modifiers.contains(&TypeModifier::Array) {
+        result = format!("xs::Boxed<{}>", result);
     }
-
-    let mut result = type_name.to_string();
     for modifier in modifiers {
         match modifier {
             TypeModifier::Array => result = format!("Vec<{}>", result),
@@ -64,16 +136,132 @@ pub fn default_modify_type(type_name: &str, modifiers: &[TypeModifier]) -> Cow<'
     result.into()
 }
 
-pub fn yaserde_for_attribute(name: &str, indent: &str) -> String {
-    if let Some(index) = name.find(':') {
-        format!(
-            "{}#[yaserde(attribute, prefix = \"{}\", rename = \"{}\")]\n",
-            indent,
-            &name[0..index],
-            &name[index + 1..]
-        )
+// Expression which builds a default or fixed value for a field of type
+// `default_modify_type(type_name, modifiers)`, or the reason why the value is not valid.
+// Values of primitive and built-in types are checked here, those of the generated types
+// against their declarations, see StructFieldGenerator::default_value. Lists of values
+// are not supported.
+pub fn default_format_value(
+    value: &str,
+    type_name: &str,
+    modifiers: &[TypeModifier],
+) -> Result<Option<String>, String> {
+    use TypeModifier::{Array, Empty, Recursive};
+    if modifiers.iter().any(|m| matches!(m, Empty | Array | Recursive)) {
+        return Ok(None);
+    }
+
+    let mut result = match type_name {
+        "String" => format!("{:?}.to_string()", value),
+        ty => match default_format_literal(value, ty)? {
+            Some(literal) => literal,
+            None => format!("xs::from_text::<{}>({:?}).unwrap()", ty, value),
+        },
+    };
+    if modifiers.contains(&TypeModifier::Nillable) {
+        let wrapper = if is_primitive_type(type_name) { "NillableText" } else { "Nillable" };
+        result = format!("xs::{}::Value({})", wrapper, result);
+    }
+    if modifiers.contains(&TypeModifier::Option) {
+        result = format!("Some({})", result);
+    }
+    Ok(Some(result))
+}
+
+// Rust literal of a value of a primitive type. Values of the types from xsd-types are
+// only checked, as they are built at run time.
+pub fn default_format_literal(value: &str, type_name: &str) -> Result<Option<String>, String> {
+    fn parse<T: FromStr>(value: &str, type_name: &str) -> Result<T, String> {
+        value.trim().parse::<T>().map_err(|_| format!("{:?} is not a valid {}", value, type_name))
+    }
+    fn literal<T: FromStr + ToString>(
+        value: &str,
+        type_name: &str,
+    ) -> Result<Option<String>, String> {
+        parse::<T>(value, type_name).map(|v| Some(v.to_string()))
+    }
+    fn checked<T: FromStr>(value: &str, type_name: &str) -> Result<Option<String>, String> {
+        parse::<T>(value, type_name).map(|_| None)
+    }
+    // Special values of xs:float and xs:double, other spellings accepted by Rust are not
+    fn float<T: FromStr + fmt::Debug>(
+        value: &str,
+        type_name: &str,
+        is_finite: impl Fn(&T) -> bool,
+    ) -> Result<Option<String>, String> {
+        match value.trim() {
+            "INF" | "+INF" => Ok(Some(format!("{}::INFINITY", type_name))),
+            "-INF" => Ok(Some(format!("{}::NEG_INFINITY", type_name))),
+            "NaN" => Ok(Some(format!("{}::NAN", type_name))),
+            _ => match parse::<T>(value, type_name)? {
+                v if is_finite(&v) => Ok(Some(format!("{:?}", v))),
+                _ => Err(format!("{:?} is not a valid {}", value, type_name)),
+            },
+        }
+    }
+
+    match type_name {
+        "bool" => match value.trim() {
+            "true" | "1" => Ok(Some("true".to_string())),
+            "false" | "0" => Ok(Some("false".to_string())),
+            _ => Err(format!("{:?} is not a valid bool, expected true, false, 1 or 0", value)),
+        },
+        "i8" => literal::<i8>(value, type_name),
+        "i16" => literal::<i16>(value, type_name),
+        "i32" => literal::<i32>(value, type_name),
+        "i64" => literal::<i64>(value, type_name),
+        "u8" => literal::<u8>(value, type_name),
+        "u16" => literal::<u16>(value, type_name),
+        "u32" => literal::<u32>(value, type_name),
+        "u64" => literal::<u64>(value, type_name),
+        "f32" => float::<f32>(value, type_name, |v| v.is_finite()),
+        "f64" => float::<f64>(value, type_name, |v| v.is_finite()),
+        "xs::Integer" => checked::<xs::Integer>(value, type_name),
+        "xs::NonNegativeInteger" => checked::<xs::NonNegativeInteger>(value, type_name),
+        "xs::PositiveInteger" => checked::<xs::PositiveInteger>(value, type_name),
+        "xs::NonPositiveInteger" => checked::<xs::NonPositiveInteger>(value, type_name),
+        "xs::NegativeInteger" => checked::<xs::NegativeInteger>(value, type_name),
+        "xs::Decimal" => checked::<xs::Decimal>(value, type_name),
+        "xs::Date" => checked::<xs::Date>(value, type_name),
+        "xs::Time" => checked::<xs::Time>(value, type_name),
+        "xs::DateTime" => checked::<xs::DateTime>(value, type_name),
+        "xs::DateTimeStamp" => checked::<xs::DateTimeStamp>(value, type_name),
+        "xs::Duration" => checked::<xs::Duration>(value, type_name),
+        "xs::GDay" => checked::<xs::GDay>(value, type_name),
+        "xs::GMonth" => checked::<xs::GMonth>(value, type_name),
+        "xs::GMonthDay" => checked::<xs::GMonthDay>(value, type_name),
+        "xs::GYear" => checked::<xs::GYear>(value, type_name),
+        "xs::GYearMonth" => checked::<xs::GYearMonth>(value, type_name),
+        _ => Ok(None),
+    }
+}
+
+// Name of the function which returns the default value of a struct field
+pub fn default_fn_name(struct_name: &str, field_name: &str) -> String {
+    format!("default_{}_{}", default_format_name(struct_name), field_name.trim_start_matches('_'))
+}
+
+pub fn yaserde_for_attribute(
+    name: &str,
+    target_namespace: Option<&Namespace>,
+    default_fn: Option<&str>,
+    indent: &str,
+) -> String {
+    let (prefix, attribute_name) = if let Some(index) = name.find(':') {
+        (Some(&name[0..index]), &name[index + 1..])
     } else {
-        format!("{}#[yaserde(attribute, rename = \"{}\")]\n", indent, name)
+        (target_namespace.and_then(|ns| ns.name()), name)
+    };
+
+    let default = default_fn.map(|f| format!(", default = \"{}\"", f)).unwrap_or_default();
+    match prefix {
+        Some(p) => format!(
+            "{}#[yaserde(attribute{}, prefix = \"{}\", rename = \"{}\")]\n",
+            indent, default, p, attribute_name
+        ),
+        None => {
+            format!("{}#[yaserde(attribute{}, rename = \"{}\")]\n", indent, default, attribute_name)
+        }
     }
 }
 
@@ -100,9 +288,45 @@ pub fn yaserde_for_flatten_element(indent: &str) -> String {
     format!("{}#[yaserde(flatten)]\n", indent)
 }
 
+pub fn yaserde_for_text(indent: &str) -> String {
+    format!("{}#[yaserde(text)]\n", indent)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::parser::xsd_elements::FacetType;
+
+    #[test]
+    fn test_escape_doc() {
+        assert_eq!(escape_doc("Resolution"), "Resolution");
+        assert_eq!(escape_doc("(see"), "(see");
+        assert_eq!(
+            escape_doc("http://www.onvif.org/ver10/schema."),
+            "`http://www.onvif.org/ver10/schema`."
+        );
+        assert_eq!(escape_doc("(tt:Profile::token)"), "(`tt:Profile::token`)");
+        assert_eq!(escape_doc("max_len,"), "`max_len`,");
+        assert_eq!(escape_doc("<Capabilities>"), "`<Capabilities>`");
+        assert_eq!(escape_doc("[1..2]"), "`[1..2]`");
+        assert_eq!(escape_doc("#1"), "\\#1");
+        assert_eq!(escape_doc("`a_b`"), "\\`a\\_b\\`");
+        assert_eq!(escape_doc("*"), "`*`");
+    }
+
+    #[test]
+    fn test_code_span() {
+        assert_eq!(code_span("[A-Z]{3}"), "`[A-Z]{3}`");
+        assert_eq!(code_span("a`b"), "``a`b``");
+        assert_eq!(code_span("``a"), "``` ``a ```");
+        assert_eq!(
+            default_format_facets(&[Facet {
+                facet_type: FacetType::Pattern("[^`]+".into()),
+                comment: None
+            }]),
+            Some("Facets: ``pattern = [^`]+``".to_string())
+        );
+    }
 
     #[test]
     fn test_default_format_comment() {
@@ -115,15 +339,60 @@ mod test {
         And new line after empty lines"#,
         );
 
-        let expected = r#"  // Line of documentation!
-  // New line of documentation
-  // with len>30 symbols!
-  // And new line after empty
-  // lines
+        let expected = r#"  /// Line of documentation!
+  /// New line of
+  /// documentation with
+  /// `len>30` symbols!
+  /// And new line after empty
+  /// lines
 "#;
         assert_eq!(default_format_comment(doc, 30, 2), expected);
     }
 
+    #[test]
+    fn test_default_format_doc() {
+        let details = ["XSD: `tt:Width`".to_string()];
+        assert_eq!(
+            default_format_doc(Some("Width of the image."), &details, 80, 0),
+            "/// Width of the image.\n///\n/// XSD: `tt:Width`\n"
+        );
+        assert_eq!(default_format_doc(None, &details, 80, 4), "    /// XSD: `tt:Width`\n");
+        assert_eq!(default_format_doc(None, &[], 80, 4), "");
+    }
+
+    #[test]
+    fn test_default_format_value() {
+        use TypeModifier::*;
+        let value = |value, type_name, modifiers: &[TypeModifier]| {
+            default_format_value(value, type_name, modifiers).unwrap().unwrap()
+        };
+        assert_eq!(value("a", "String", &[]), r#""a".to_string()"#);
+        assert_eq!(value("0", "bool", &[Option]), "Some(false)");
+        assert_eq!(value(" 5 ", "u32", &[None]), "5");
+        assert_eq!(value("-05", "i8", &[Nillable]), "xs::NillableText::Value(-5)");
+        assert_eq!(value("1.5E3", "f64", &[]), "1500.0");
+        assert_eq!(value("-INF", "f32", &[]), "f32::NEG_INFINITY");
+        assert_eq!(
+            value("PT1S", "xs::Duration", &[Nillable]),
+            r#"xs::Nillable::Value(xs::from_text::<xs::Duration>("PT1S").unwrap())"#
+        );
+        assert_eq!(value("Mode", "Mode", &[]), r#"xs::from_text::<Mode>("Mode").unwrap()"#);
+        assert_eq!(default_format_value("a", "String", &[Array]), Ok(std::option::Option::None));
+
+        let invalid = |value, type_name| default_format_value(value, type_name, &[]).unwrap_err();
+        assert_eq!(
+            invalid("yes", "bool"),
+            r#""yes" is not a valid bool, expected true, false, 1 or 0"#
+        );
+        assert_eq!(
+            invalid("TRUE", "bool"),
+            r#""TRUE" is not a valid bool, expected true, false, 1 or 0"#
+        );
+        assert_eq!(invalid("300", "u8"), r#""300" is not a valid u8"#);
+        assert_eq!(invalid("inf", "f64"), r#""inf" is not a valid f64"#);
+        assert_eq!(invalid("1S", "xs::Duration"), r#""1S" is not a valid xs::Duration"#);
+    }
+
     #[test]
     fn test_default_format_name() {
         assert_eq!(default_format_name("Struct").as_str(), "_struct");
@@ -158,7 +427,7 @@ mod test {
             .root_element()
             .namespaces()
             .next()
-            .cloned()
+            .map(Namespace::from)
             .unwrap(),
         );
         assert_eq!(default_format_type("tt:Type", &ns), "Type");
@@ -181,14 +450,28 @@ mod test {
     #[test]
     fn test_default_modify_type() {
         use TypeModifier::*;
-        assert_eq!(default_modify_type("Type", &[Recursive]), "Vec<Type>");
+        assert_eq!(default_modify_type("Type", &[Recursive]), "xs::Boxed<Type>");
         assert_eq!(default_modify_type("Type", &[None]), "Type");
         assert_eq!(default_modify_type("Type", &[Option]), "Option<Type>");
         assert_eq!(default_modify_type("Type", &[Array]), "Vec<Type>");
         assert_eq!(default_modify_type("Type", &[Empty]), "()");
 
-        assert_eq!(default_modify_type("Type", &[Recursive, Option]), "Vec<Type>");
-        assert_eq!(default_modify_type("Type", &[Recursive, Array, Option]), "Vec<Type>");
+        assert_eq!(default_modify_type("Type", &[Option, Recursive]), "Option<xs::Boxed<Type>>");
+        assert_eq!(default_modify_type("Type", &[Array, Recursive]), "Vec<Type>");
+        assert_eq!(
+            default_modify_type("Type", &[None, Nillable, Recursive]),
+            "xs::Boxed<xs::Nillable<Type>>"
+        );
         assert_eq!(default_modify_type("Type", &[Recursive, Array, Empty]), "()");
+
+        assert_eq!(default_modify_type("Type", &[None, Nillable]), "xs::Nillable<Type>");
+        assert_eq!(
+            default_modify_type("i32", &[Option, Nillable]),
+            "Option<xs::NillableText<i32>>"
+        );
+        assert_eq!(
+            default_modify_type("String", &[Array, Nillable]),
+            "Vec<xs::NillableText<String>>"
+        );
     }
 }
@@ -1,12 +1,25 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, cell::RefCell, collections::HashMap};
 
 use crate::{
-    generator::{validator::gen_validate_impl, Generator},
-    parser::types::{Enum, EnumSource},
+    generator::{
+        names::allocate_variant_names,
+        pass::{walk_entity, Pass},
+        utils::{qualified_element_name, shift, split_name},
+        validator::{gen_child_path, gen_nested_validation, gen_validate_impl},
+        Generator,
+    },
+    parser::{
+        types::{
+            ChoiceBranch, Enum, EnumCase, EnumSource, RsEntity, RsFile, Struct, StructField,
+            StructFieldSource,
+        },
+        xsd_elements::FormType,
+    },
 };
 
 pub trait EnumGenerator {
     fn generate(&self, entity: &Enum, gen: &Generator) -> String {
+        let entity = &self.prepare(entity, gen);
         let name = self.get_name(entity, gen);
         let default_case = format!(
             "impl Default for {name} {{\n\
@@ -19,7 +32,7 @@ pub trait EnumGenerator {
         );
 
         format!(
-            "{comment}{macros}\n\
+            "{comment}{derives}{macros}\n\
             pub enum {name} {{\n\
                 {cases}\n\
                 {indent}__Unknown__({typename}),\n\
@@ -29,6 +42,7 @@ pub trait EnumGenerator {
             {subtypes}\n\n",
             indent = gen.base().indent(),
             comment = self.format_comment(entity, gen),
+            derives = gen.base().format_derives(&entity.derives),
             macros = self.macros(entity, gen),
             name = name,
             cases = self.cases(entity, gen),
@@ -39,17 +53,76 @@ pub trait EnumGenerator {
         )
     }
 
+    // Copy of the enum with the variants as they are generated
+    fn prepare(&self, entity: &Enum, gen: &Generator) -> Enum {
+        let mut entity = entity.clone();
+        let name = self.get_name(&entity, gen);
+        allocate_variant_names(&mut entity.cases, &name, gen);
+        entity
+    }
+
     fn cases(&self, entity: &Enum, gen: &Generator) -> String {
         entity
             .cases
             .iter()
-            .map(|case| gen.enum_case_gen().generate(case, gen))
+            .map(|case| match case.branch {
+                ChoiceBranch::Element | ChoiceBranch::Any | ChoiceBranch::Text => {
+                    gen.enum_case_gen().generate(case, gen)
+                }
+                // Nested particles are types of the enum module, sequences are read
+                // by the names of their elements
+                _ => gen.enum_case_gen().generate(
+                    &EnumCase {
+                        type_name: case.type_name.as_ref().map(|ty| {
+                            match entity.subtypes.iter().any(|subtype| subtype.name() == ty) {
+                                true => format!("{}::{}", self.mod_name(entity, gen), ty),
+                                false => ty.clone(),
+                            }
+                        }),
+                        value: self
+                            .branch_struct(entity, case)
+                            .map(|st| sequence_pattern(st, gen))
+                            .unwrap_or_default(),
+                        ..case.clone()
+                    },
+                    gen,
+                ),
+            })
             .collect::<Vec<String>>()
             .join("\n")
     }
 
+    fn branch_struct<'a>(&self, entity: &'a Enum, case: &EnumCase) -> Option<&'a Struct> {
+        entity.subtypes.iter().find_map(|subtype| match subtype {
+            RsEntity::Struct(st) if case.type_name.as_ref() == Some(&st.name) => Some(st),
+            _ => None,
+        })
+    }
+
     fn subtypes(&self, entity: &Enum, gen: &Generator) -> String {
-        gen.base().join_subtypes(entity.subtypes.as_ref(), gen)
+        let (branches, subtypes): (Vec<RsEntity>, Vec<RsEntity>) =
+            entity.subtypes.iter().cloned().partition(|subtype| {
+                entity.cases.iter().any(|case| {
+                    case.branch != ChoiceBranch::Element
+                        && case.type_name.as_deref() == Some(subtype.name())
+                })
+            });
+
+        let subtypes = gen.base().join_subtypes(&subtypes, gen);
+        if branches.is_empty() {
+            return subtypes;
+        }
+        format!(
+            "{subtypes}\npub mod {name} {{\n{indent}use super::*;\n{branches}\n}}\n",
+            subtypes = subtypes,
+            name = self.mod_name(entity, gen),
+            indent = gen.base().indent(),
+            branches = shift(&gen.base().join_subtypes(&branches, gen), &gen.base().indent()),
+        )
+    }
+
+    fn mod_name(&self, entity: &Enum, gen: &Generator) -> String {
+        gen.base().mod_name(entity.name.as_str())
     }
 
     fn get_type_name(&self, entity: &Enum, gen: &Generator) -> String {
@@ -64,6 +137,13 @@ pub trait EnumGenerator {
         if entity.source == EnumSource::Union {
             return "#[derive(PartialEq, Debug, UtilsUnionSerDe)]".into();
         }
+        if entity.source == EnumSource::Choice {
+            return format!(
+                "#[derive(PartialEq, Debug, Clone, UtilsChoiceSerDe)]{}",
+                self.choice_attributes(entity, gen)
+            )
+            .into();
+        }
 
         let derives = "#[derive(PartialEq, Debug, Clone, YaSerialize, YaDeserialize)]";
         let tns = gen.target_ns.borrow();
@@ -86,13 +166,283 @@ pub trait EnumGenerator {
         .into()
     }
 
+    // Namespaces of the elements of the alternatives and the elements of the other
+    // fields of the parent, see UtilsChoiceSerDe
+    fn choice_attributes(&self, entity: &Enum, gen: &Generator) -> String {
+        let qualify = |(name, form): &(String, FormType)| {
+            qualified_element_name(name, *form, &gen.target_ns.borrow(), &gen.namespaces.borrow())
+        };
+        let siblings = entity
+            .siblings
+            .as_ref()
+            .map(|siblings| siblings.iter().map(qualify).collect::<Vec<_>>());
+        let elements = entity
+            .cases
+            .iter()
+            .flat_map(|case| match case.branch {
+                ChoiceBranch::Element => vec![(case.name.clone(), case.form)],
+                ChoiceBranch::Sequence => self
+                    .branch_struct(entity, case)
+                    .map(|st| sequence_steps(st).into_iter().flat_map(|step| step.names).collect())
+                    .unwrap_or_default(),
+                _ => vec![],
+            })
+            .map(|element| qualify(&element))
+            .chain(siblings.iter().flatten().cloned());
+
+        let mut namespaces: Vec<String> = vec![];
+        for (name, uri) in elements {
+            let namespace = match (split_name(&name), uri) {
+                ((Some(prefix), _), Some(uri)) => format!("{}: {}", prefix, uri),
+                ((None, _), Some(uri)) => uri,
+                _ => continue,
+            };
+            if !namespaces.contains(&namespace) {
+                namespaces.push(namespace);
+            }
+        }
+
+        let attributes = namespaces
+            .iter()
+            .map(|namespace| format!("namespace = \"{}\"", namespace))
+            .chain(siblings.map(|siblings| {
+                let names = siblings.into_iter().map(|(name, _)| name).collect::<Vec<_>>();
+                format!("siblings = \"{}\"", names.join(" "))
+            }))
+            .collect::<Vec<_>>();
+        if attributes.is_empty() {
+            return "".into();
+        }
+        format!("\n#[choice({})]", attributes.join(", "))
+    }
+
     fn format_comment(&self, entity: &Enum, gen: &Generator) -> String {
-        gen.base().format_comment(entity.comment.as_deref(), 0)
+        let details = gen.base().format_declaration(entity.qname.as_ref(), gen);
+        gen.base().format_doc(entity.comment.as_deref(), details.as_slice(), 0)
     }
 
+    // Values of generated types are checked with their own Validate impls, at the path
+    // of their element. Other variants have nothing to check.
     fn validation(&self, entity: &Enum, gen: &Generator) -> Cow<'static, str> {
-        // Empty validation
-        Cow::Owned(gen_validate_impl(self.get_name(entity, gen).as_str(), ""))
+        let arms = entity
+            .cases
+            .iter()
+            .filter(|case| {
+                case.rust_type.is_none()
+                    && case
+                        .type_name
+                        .as_ref()
+                        .is_some_and(|ty| gen.base().is_generated_type(ty, gen))
+            })
+            .map(|case| {
+                let path = match (&entity.source, &case.branch) {
+                    (EnumSource::Choice, ChoiceBranch::Element) => {
+                        gen_child_path(&case.name, false)
+                    }
+                    _ => "path".into(),
+                };
+                (gen.enum_case_gen().get_name(case, gen), path)
+            })
+            .collect::<Vec<_>>();
+
+        let name = self.get_name(entity, gen);
+        let body = match arms.as_slice() {
+            [] => "".into(),
+            [(variant, path)] => format!(
+                "\n        if let Self::{}(value) = self {{{}\n        }}",
+                variant,
+                shift_body(&gen_nested_validation("value", path))
+            ),
+            _ => format!(
+                "\n        match self {{{}\n            _ => (),\n        }}",
+                arms.iter()
+                    .map(|(variant, path)| format!(
+                        "\n            Self::{}(value) => value.validate_at({}, errors),",
+                        variant, path
+                    ))
+                    .collect::<String>()
+            ),
+        };
+        Cow::Owned(gen_validate_impl(&name, &body))
+    }
+}
+
+fn shift_body(body: &str) -> String {
+    body.replace('\n', "\n    ")
+}
+
+// Steps of a sequence alternative with their occurrence, see xs::read_sequence
+fn sequence_pattern(st: &Struct, gen: &Generator) -> String {
+    sequence_steps(st)
+        .iter()
+        .map(|step| {
+            let names = step
+                .names
+                .iter()
+                .map(|(name, form)| {
+                    let target_ns = gen.target_ns.borrow();
+                    qualified_element_name(name, *form, &target_ns, &gen.namespaces.borrow()).0
+                })
+                .collect::<Vec<_>>()
+                .join("|");
+            let occurs = match (step.min, step.max) {
+                (1, Some(1)) => "".to_string(),
+                (0, Some(1)) => "?".to_string(),
+                (0, None) => "*".to_string(),
+                (1, None) => "+".to_string(),
+                (min, Some(max)) if min == max => format!("{{{}}}", min),
+                (min, Some(max)) => format!("{{{},{}}}", min, max),
+                (min, None) => format!("{{{},}}", min),
+            };
+            format!("{}{}", names, occurs)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Element names matched by a field of a sequence, repeated from min to max times
+struct SequenceStep {
+    names: Vec<(String, FormType)>,
+    min: usize,
+    max: Option<usize>,
+}
+
+// A nested choice can consist of several elements, so its step is repeated
+fn sequence_steps(st: &Struct) -> Vec<SequenceStep> {
+    st.fields
+        .iter()
+        .filter_map(|field| match (&field.source, field.subtypes.first()) {
+            (StructFieldSource::Element, _) => Some(SequenceStep {
+                names: vec![(field.name.clone(), field.form)],
+                min: field.occurs.min,
+                max: field.occurs.max,
+            }),
+            (StructFieldSource::Choice, Some(RsEntity::Enum(en))) => Some(SequenceStep {
+                names: choice_names(en),
+                min: field.occurs.min.min(1),
+                max: None,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+// Names of the elements which can occur in a choice, including nested particles
+fn choice_names(en: &Enum) -> Vec<(String, FormType)> {
+    en.cases
+        .iter()
+        .flat_map(|case| match case.branch {
+            ChoiceBranch::Element => vec![(case.name.clone(), case.form)],
+            _ => en
+                .subtypes
+                .iter()
+                .filter(|subtype| case.type_name.as_deref() == Some(subtype.name()))
+                .flat_map(|subtype| match subtype {
+                    RsEntity::Struct(st) => {
+                        sequence_steps(st).into_iter().flat_map(|step| step.names).collect()
+                    }
+                    RsEntity::Enum(nested) => choice_names(nested),
+                    _ => vec![],
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+// Whether an alternative of the choice or of its nested particles is xs:any
+fn has_wildcard(en: &Enum) -> bool {
+    en.cases.iter().any(|case| case.branch == ChoiceBranch::Any)
+        || en.subtypes.iter().any(|subtype| match subtype {
+            RsEntity::Struct(st) => st.fields.iter().any(|field| {
+                field.source == StructFieldSource::Any
+                    || matches!(field.subtypes.first(), Some(RsEntity::Enum(en)) if has_wildcard(en))
+            }),
+            RsEntity::Enum(nested) => has_wildcard(nested),
+            _ => false,
+        })
+}
+
+// Elements read by the fields of the struct other than the choice, None if they are
+// not known, e.g. the struct has xs:any or mixed content
+fn struct_siblings(st: &Struct, choice: usize) -> Option<Vec<(String, FormType)>> {
+    if st.mixed {
+        return None;
+    }
+    let mut siblings = vec![];
+    for (_, field) in st.fields.iter().enumerate().filter(|(index, _)| *index != choice) {
+        match (&field.source, field.subtypes.first()) {
+            (StructFieldSource::Element, _) => siblings.push((field.name.clone(), field.form)),
+            (StructFieldSource::Choice, Some(RsEntity::Enum(en))) if !has_wildcard(en) => {
+                siblings.extend(choice_names(en))
+            }
+            (
+                StructFieldSource::Attribute
+                | StructFieldSource::AnyAttribute
+                | StructFieldSource::Text,
+                _,
+            ) => (),
+            _ => return None,
+        }
+    }
+    Some(siblings)
+}
+
+// Sets the siblings of the choices of structs, so that elements which belong to no
+// field are rejected. A choice generated at the top level, e.g. the content of a
+// complex type, is known only if no other struct (e.g. a derived type) refers to it.
+pub fn set_choice_siblings(schemas: &mut [RsFile]) {
+    let references = ChoiceReferences::default();
+    for entity in schemas.iter_mut().flat_map(|schema| schema.types.iter_mut()) {
+        walk_entity(&references, entity);
+    }
+    let siblings = ChoiceSiblings { references: references.0.into_inner() };
+    for entity in schemas.iter_mut().flat_map(|schema| schema.types.iter_mut()) {
+        walk_entity(&siblings, entity);
+    }
+}
+
+// Number of fields referring to each choice generated at the top level
+#[derive(Default)]
+struct ChoiceReferences(RefCell<HashMap<String, usize>>);
+
+impl Pass for ChoiceReferences {
+    fn visit_field(&self, entity: &mut StructField) {
+        if entity.source == StructFieldSource::Choice && entity.subtypes.is_empty() {
+            *self.0.borrow_mut().entry(entity.type_name.clone()).or_default() += 1;
+        }
+    }
+}
+
+struct ChoiceSiblings {
+    references: HashMap<String, usize>,
+}
+
+impl Pass for ChoiceSiblings {
+    fn visit_struct(&self, entity: &mut Struct) {
+        for index in 0..entity.fields.len() {
+            let siblings = struct_siblings(entity, index);
+            let field = &mut entity.fields[index];
+            if let (StructFieldSource::Choice, Some(RsEntity::Enum(en))) =
+                (&field.source, field.subtypes.first_mut())
+            {
+                en.siblings = siblings;
+            }
+        }
+    }
+
+    fn visit_enum(&self, entity: &mut Enum) {
+        if entity.source != EnumSource::Choice || self.references.get(&entity.name) != Some(&1) {
+            return;
+        }
+        let name = entity.name.clone();
+        entity.siblings = entity.subtypes.iter().find_map(|subtype| match subtype {
+            RsEntity::Struct(st) => st
+                .fields
+                .iter()
+                .position(|f| f.source == StructFieldSource::Choice && f.type_name == name)
+                .and_then(|index| struct_siblings(st, index)),
+            _ => None,
+        });
     }
 }
 
@@ -1,6 +1,13 @@
 use crate::{
-    generator::{default::default_format_type, utils::split_name, Generator},
-    parser::types::{EnumCase, EnumSource},
+    generator::{
+        default::{code_span, default_format_type},
+        utils::{qualified_element_name, split_name},
+        Generator,
+    },
+    parser::{
+        types::{ChoiceBranch, EnumCase, EnumSource, TypeModifier},
+        xsd_elements::ProcessContents,
+    },
 };
 
 pub trait EnumCaseGenerator {
@@ -21,6 +28,9 @@ pub trait EnumCaseGenerator {
     }
 
     fn get_name(&self, entity: &EnumCase, gen: &Generator) -> String {
+        if let Some(name) = &entity.rust_name {
+            return name.clone();
+        }
         default_format_type(entity.name.as_str(), &gen.target_ns.borrow())
             .split("::")
             .last()
@@ -29,18 +39,84 @@ pub trait EnumCaseGenerator {
     }
 
     fn get_type_name(&self, entity: &EnumCase, gen: &Generator) -> String {
-        let formatted_type = gen.base().format_type_name(entity.type_name.as_ref().unwrap(), gen);
+        let formatted_type = gen.base().format_rust_type(
+            entity.type_name.as_ref().unwrap(),
+            entity.rust_type.as_deref(),
+            gen,
+        );
+        if entity.branch == ChoiceBranch::Choice
+            && entity.type_modifiers.contains(&TypeModifier::Array)
+        {
+            return format!("xs::Choices<{}>", formatted_type);
+        }
         gen.base().modify_type(formatted_type.as_ref(), &entity.type_modifiers).into()
     }
 
     fn format_comment(&self, entity: &EnumCase, gen: &Generator) -> String {
-        gen.base().format_comment(entity.comment.as_deref(), gen.base().indent_size())
+        let details = self
+            .format_declaration(entity, gen)
+            .into_iter()
+            .chain(self.format_wildcard(entity))
+            .collect::<Vec<_>>();
+        gen.base().format_doc(
+            entity.comment.as_deref(),
+            details.as_slice(),
+            gen.base().indent_size(),
+        )
+    }
+
+    // Detail of the documentation naming the element of an alternative of xs:choice
+    fn format_declaration(&self, entity: &EnumCase, gen: &Generator) -> Option<String> {
+        if entity.source != EnumSource::Choice || entity.branch != ChoiceBranch::Element {
+            return None;
+        }
+        Some(gen.base().format_element_declaration(
+            "element",
+            &entity.name,
+            entity.type_qname.as_ref(),
+            gen,
+        ))
+    }
+
+    // Details of an alternative matched by xs:any
+    fn format_wildcard(&self, entity: &EnumCase) -> Vec<String> {
+        let wildcard = match &entity.wildcard {
+            Some(wildcard) => wildcard,
+            None => return vec![],
+        };
+        let kind = match wildcard.process_contents {
+            ProcessContents::Strict => "strict",
+            ProcessContents::Lax => "lax",
+            ProcessContents::Skip => "skip",
+        };
+        vec![
+            format!(
+                "XSD: any element of namespace {}, processContents `{}`",
+                code_span(wildcard.namespace.trim()),
+                kind
+            ),
+            "Elements are kept as raw XML.".to_string(),
+        ]
+    }
+
+    // Name of the element of an alternative as it is written, e.g. tns:Item
+    fn element_name(&self, entity: &EnumCase, gen: &Generator) -> String {
+        qualified_element_name(
+            &entity.name,
+            entity.form,
+            &gen.target_ns.borrow(),
+            &gen.namespaces.borrow(),
+        )
+        .0
     }
 
     fn macros(&self, entity: &EnumCase, gen: &Generator) -> String {
         if entity.source == EnumSource::Union {
             return "".into();
         }
+        if entity.source == EnumSource::Choice {
+            return self.choice_macros(entity, gen);
+        }
 
         let (prefix, field_name) = split_name(entity.name.as_str());
         match prefix {
@@ -63,6 +139,30 @@ pub trait EnumCaseGenerator {
             }
         }
     }
+
+    // Describes the alternative for UtilsChoiceSerDe
+    fn choice_macros(&self, entity: &EnumCase, gen: &Generator) -> String {
+        let attribute = match entity.branch {
+            ChoiceBranch::Element => format!("element = \"{}\"", self.element_name(entity, gen)),
+            ChoiceBranch::Sequence => format!("sequence = \"{}\"", entity.value),
+            ChoiceBranch::Choice => "choice".into(),
+            ChoiceBranch::Text => "text".into(),
+            ChoiceBranch::Any => {
+                let namespace = entity.wildcard.as_ref().map_or("##any", |w| w.namespace.trim());
+                match gen.target_ns.borrow().as_ref() {
+                    Some(tns) => {
+                        format!("any = \"{}\", target_namespace = \"{}\"", namespace, tns.uri())
+                    }
+                    None => format!("any = \"{}\"", namespace),
+                }
+            }
+        };
+        format!(
+            "{indent}#[choice({attribute})]\n",
+            indent = gen.base().indent(),
+            attribute = attribute
+        )
+    }
 }
 
 pub struct DefaultEnumCaseGen;
@@ -0,0 +1,19 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GenerateError {
+    /// The default or fixed value of an element or attribute is not valid for its type.
+    InvalidValue { owner: String, field: String, reason: String },
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenerateError::InvalidValue { owner, field, reason } => {
+                write!(f, "Invalid default value of '{}::{}': {}", owner, field, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GenerateError {}
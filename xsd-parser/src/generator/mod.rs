@@ -1,33 +1,48 @@
 pub mod alias;
 pub mod base;
 pub mod builder;
+pub mod customization;
 pub mod default;
 pub mod r#enum;
 pub mod enum_case;
+pub mod error;
 pub mod import;
+pub mod names;
+pub mod pass;
 pub mod r#struct;
 pub mod struct_field;
 pub mod tuple_struct;
 mod utils;
 pub mod validator;
 
-use std::{borrow::Borrow, cell::RefCell};
-
-use roxmltree::Namespace;
+use std::{borrow::Borrow, cell::RefCell, collections::HashMap};
 
 use crate::{
     generator::{
-        alias::AliasGenerator, base::BaseGenerator, enum_case::EnumCaseGenerator,
-        import::ImportGenerator, r#enum::EnumGenerator, r#struct::StructGenerator,
-        struct_field::StructFieldGenerator, tuple_struct::TupleStructGenerator,
+        alias::AliasGenerator,
+        base::BaseGenerator,
+        enum_case::EnumCaseGenerator,
+        error::GenerateError,
+        import::ImportGenerator,
+        names::Renamed,
+        pass::{walk_entity, Pass, Rename},
+        r#enum::EnumGenerator,
+        r#struct::StructGenerator,
+        struct_field::StructFieldGenerator,
+        tuple_struct::TupleStructGenerator,
     },
-    parser::types::{RsEntity, RsFile},
+    parser::types::{Alias, Enum, Namespace, QName, RsEntity, RsFile, TupleStruct},
 };
 
 #[derive(Default)]
-pub struct Generator<'input> {
-    pub target_ns: RefCell<Option<Namespace<'input>>>,
-    pub xsd_ns: RefCell<Option<Namespace<'input>>>,
+pub struct Generator {
+    pub target_ns: RefCell<Option<Namespace>>,
+    pub xsd_ns: RefCell<Option<Namespace>>,
+    pub namespaces: RefCell<Vec<Namespace>>,
+    // Global elements of the generated schemas, the content allowed by strict wildcards
+    pub elements: RefCell<Vec<QName>>,
+    // Type name of the struct whose fields are being generated
+    pub struct_name: RefCell<Option<String>>,
 
     pub tuple_struct_gen: Option<Box<dyn TupleStructGenerator>>,
     pub struct_gen: Option<Box<dyn StructGenerator>>,
@@ -37,13 +52,108 @@ pub struct Generator<'input> {
     pub enum_gen: Option<Box<dyn EnumGenerator>>,
     pub alias_gen: Option<Box<dyn AliasGenerator>>,
     pub import_gen: Option<Box<dyn ImportGenerator>>,
+
+    pub passes: Vec<Box<dyn Pass>>,
+    // Identifiers changed by the naming registry since the last call to take_renamed
+    pub renamed: RefCell<Vec<Renamed>>,
+    // Global types renamed by the naming registry in the last call to generate_rs_files
+    pub type_names: RefCell<Rename>,
+    // Enumerations and other simple types of the schemas of the last call to
+    // generate_rs_files, which default and fixed values are checked against
+    pub simple_types: RefCell<HashMap<QName, RsEntity>>,
+    // Errors since the last call to take_errors, the code is generated without the
+    // invalid parts
+    pub errors: RefCell<Vec<GenerateError>>,
 }
 
-impl<'input> Generator<'input> {
-    pub fn generate_rs_file(&self, schema: &RsFile<'input>) -> String {
+impl Generator {
+    pub fn generate_rs_file(&self, schema: &RsFile) -> String {
+        self.generate_rs_files(std::slice::from_ref(schema))
+    }
+
+    // Code of several schemas in a single module, e.g. the schemas of a WSDL file.
+    // Their type names are allocated together, see names::allocate_type_names.
+    pub fn generate_rs_files(&self, schemas: &[RsFile]) -> String {
+        self.declare_elements(schemas);
+        let mut schemas = schemas.iter().map(|schema| self.transform(schema)).collect::<Vec<_>>();
+        r#struct::merge_mixed_contents(&mut schemas);
+        r#enum::set_choice_siblings(&mut schemas);
+        *self.simple_types.borrow_mut() = schemas
+            .iter()
+            .flat_map(|schema| &schema.types)
+            .filter_map(|entity| match entity {
+                RsEntity::Enum(Enum { qname: Some(qname), .. })
+                | RsEntity::TupleStruct(TupleStruct { qname: Some(qname), .. }) => {
+                    Some((qname.clone(), entity.clone()))
+                }
+                _ => None,
+            })
+            .collect();
+        names::allocate_type_names(&mut schemas, self);
+        schemas
+            .iter()
+            .map(|schema| {
+                self.set_namespaces(schema);
+                schema.types.iter().map(|entity| self.generate(entity)).collect::<String>()
+            })
+            .collect()
+    }
+
+    // Global elements of schemas generated elsewhere, e.g. the imports of a schema,
+    // so that strict wildcards accept them
+    pub fn declare_elements(&self, schemas: &[RsFile]) {
+        let mut elements = self.elements.borrow_mut();
+        for element in schemas.iter().flat_map(|schema| &schema.elements) {
+            if !elements.contains(&element.name) {
+                elements.push(element.name.clone());
+            }
+        }
+    }
+
+    fn set_namespaces(&self, schema: &RsFile) {
         *self.target_ns.borrow_mut() = schema.target_ns.clone();
         *self.xsd_ns.borrow_mut() = schema.xsd_ns.clone();
-        schema.types.iter().map(|entity| self.generate(entity)).collect()
+        *self.namespaces.borrow_mut() = schema.namespaces.clone();
+    }
+
+    // Identifiers renamed to resolve clashes, the names of types are allocated first
+    pub fn take_renamed(&self) -> Vec<Renamed> {
+        self.renamed.take()
+    }
+
+    // Errors of the code generated since the last call, e.g. invalid default values
+    pub fn take_errors(&self) -> Vec<GenerateError> {
+        self.errors.take()
+    }
+
+    // Rust type of a reference to a global type of the generated schemas, e.g. a part of
+    // a WSDL message. Like the references in the schemas, it is rewritten by the passes
+    // and follows the names allocated by the last call to generate_rs_files. The type
+    // name is formatted in the current namespaces.
+    pub fn format_reference(&self, type_name: &str, qname: Option<QName>) -> String {
+        let reference =
+            Alias { original: type_name.to_string(), original_qname: qname, ..Default::default() };
+        let mut file = self
+            .transform(&RsFile { types: vec![RsEntity::Alias(reference)], ..Default::default() });
+        if let Some(entity) = file.types.first_mut() {
+            walk_entity(&*self.type_names.borrow(), entity);
+        }
+        match file.types.first() {
+            Some(RsEntity::Alias(Alias { rust_type: Some(rust_type), .. })) => rust_type.clone(),
+            Some(RsEntity::Alias(alias)) => {
+                self.base().format_type_name(&alias.original, self).into()
+            }
+            _ => self.base().format_type_name(type_name, self).into(),
+        }
+    }
+
+    // Copy of the file rewritten by the registered passes
+    pub fn transform(&self, schema: &RsFile) -> RsFile {
+        let mut schema = schema.clone();
+        for pass in &self.passes {
+            pass.run(&mut schema);
+        }
+        schema
     }
 
     pub fn generate(&self, entity: &RsEntity) -> String {
@@ -91,10 +201,11 @@ mod test {
             type_name: "type".to_string(),
             ..Default::default()
         }));
-        let comment = "// comment\n";
+        let comment = "/// comment\n";
         let macros =
             "#[derive(Default, Clone, PartialEq, Debug, UtilsTupleIo, UtilsDefaultSerde)]\n";
-        let validation = "impl Validate for Name {}\n";
+        // The wrapped type is generated, so it is validated too
+        let validation = "impl Validate for Name {\n    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {\n        self.0.validate_at(path, errors);\n    }\n}\n\n";
         let expected =
             format!("{}{}pub struct Name (pub Type);\n\n{}", comment, macros, validation);
         assert_eq!(gen.generate_rs_file(&rs_file), expected);
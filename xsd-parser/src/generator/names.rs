@@ -0,0 +1,410 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use crate::{
+    generator::{
+        pass::{walk_entity, Rename},
+        Generator,
+    },
+    parser::types::{ChoiceBranch, EnumCase, QName, RsEntity, RsFile, StructField, TypeModifier},
+};
+
+// Naming registry of the generated code. Identifiers must be unique in their scope:
+// types and modules in a module, fields in a struct and variants in an enum. They
+// are allocated in the order they are generated, the first one keeps its name and
+// the following ones get the smallest free suffix, starting at 2: `Type2` for types
+// and variants, `field_2` for fields. Modules of nested types are named after their
+// types, so they follow the renames. Every rename is reported, see Generator::take_renamed.
+#[derive(Default)]
+pub struct Scope {
+    names: HashSet<String>,
+}
+
+impl Scope {
+    // Marks a name as used, e.g. a module or a variant added by the generator
+    pub fn reserve(&mut self, name: &str) {
+        self.names.insert(name.to_string());
+    }
+
+    // Allocates the name, returns the suffix of the identifier used instead if the
+    // name is taken
+    pub fn allocate(&mut self, name: &str, suffixed: impl Fn(usize) -> String) -> Option<usize> {
+        if self.names.insert(name.to_string()) {
+            return None;
+        }
+        (2..).find(|suffix| self.names.insert(suffixed(*suffix)))
+    }
+}
+
+// Identifier changed by the naming registry
+#[derive(Debug, Clone, PartialEq)]
+pub struct Renamed {
+    // Path of the module, or the name of the struct or enum
+    pub scope: String,
+    pub name: String,
+    pub new_name: String,
+}
+
+impl fmt::Display for Renamed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.scope.is_empty() {
+            write!(f, "{} renamed to {}", self.name, self.new_name)
+        } else {
+            write!(f, "{}::{} renamed to {}", self.scope, self.name, self.new_name)
+        }
+    }
+}
+
+// Allocates the names of the types of the files, which are generated into a single
+// module. A renamed global type takes the references to its name along, unless
+// another type with the same name keeps it (e.g. an element and a type named alike).
+pub fn allocate_type_names(files: &mut [RsFile], gen: &Generator) {
+    let mut scope = Scope::default();
+    let mut kept = HashSet::new();
+    let mut rename = HashMap::new();
+    for file in files.iter_mut() {
+        gen.set_namespaces(file);
+        for entity in file.types.iter_mut() {
+            let qname = QName::new(file.namespace.as_deref(), entity.name());
+            match declare(entity, &mut scope, "", gen) {
+                Some(new_name) => {
+                    rename.entry(qname).or_insert(new_name);
+                }
+                None => {
+                    kept.insert(qname);
+                }
+            }
+        }
+    }
+
+    let rename = rename
+        .into_iter()
+        .filter(|(qname, _)| !kept.contains(qname))
+        .fold(Rename::default(), |rename, (qname, new_name)| rename.with_type(qname, &new_name));
+    for entity in files.iter_mut().flat_map(|file| file.types.iter_mut()) {
+        walk_entity(&rename, entity);
+    }
+    *gen.type_names.borrow_mut() = rename;
+}
+
+// Declares the type and the types generated next to it in the scope of a module.
+// Returns the new name of the type if it is renamed.
+fn declare(
+    entity: &mut RsEntity,
+    scope: &mut Scope,
+    path: &str,
+    gen: &Generator,
+) -> Option<String> {
+    let new_name = match entity {
+        // Aliases are generated as comments
+        RsEntity::Struct(_) | RsEntity::TupleStruct(_) | RsEntity::Enum(_) => {
+            let name = entity.name().to_string();
+            let ident = |name: &str| gen.base().format_type_name(name, gen).to_string();
+            scope.allocate(&ident(&name), |suffix| ident(&format!("{}{}", name, suffix))).map(
+                |suffix| {
+                    let new_name = format!("{}{}", name, suffix);
+                    gen.renamed.borrow_mut().push(Renamed {
+                        scope: path.to_string(),
+                        name: ident(&name),
+                        new_name: ident(&new_name),
+                    });
+                    entity.set_name(&new_name);
+                    new_name
+                },
+            )
+        }
+        _ => None,
+    };
+
+    match entity {
+        RsEntity::Struct(st) => {
+            if st.subtypes.is_empty() && st.fields.iter().all(|f| f.subtypes.is_empty()) {
+                return new_name;
+            }
+            let mod_name = gen.base().mod_name(&st.name);
+            scope.reserve(&mod_name);
+            let path = join_path(path, &mod_name);
+            let mut nested = Scope::default();
+            for (name, new_name) in declare_all(&mut st.subtypes, &mut nested, &path, gen) {
+                st.fields
+                    .iter_mut()
+                    .filter(|f| f.subtypes.is_empty() && f.type_name == name)
+                    .for_each(|f| f.type_name = new_name.clone());
+            }
+            for field in st.fields.iter_mut() {
+                for (name, new_name) in declare_all(&mut field.subtypes, &mut nested, &path, gen) {
+                    if field.type_name == name {
+                        field.type_name = new_name;
+                    }
+                }
+            }
+        }
+        RsEntity::Enum(en) => {
+            // Nested particles of a choice are generated in the module of the enum
+            let is_branch = en
+                .subtypes
+                .iter()
+                .map(|subtype| {
+                    en.cases.iter().any(|case| {
+                        case.branch != ChoiceBranch::Element
+                            && case.type_name.as_deref() == Some(subtype.name())
+                    })
+                })
+                .collect::<Vec<bool>>();
+            let mut renames = vec![];
+            let mut branches = Scope::default();
+            let branch_path = join_path(path, &gen.base().mod_name(&en.name));
+            // Branches can start with the same element, so each one renames its own case
+            let mut branch_cases = en
+                .cases
+                .iter_mut()
+                .filter(|case| case.branch != ChoiceBranch::Element)
+                .collect::<Vec<_>>();
+            for (subtype, is_branch) in en.subtypes.iter_mut().zip(&is_branch) {
+                let name = subtype.name().to_string();
+                if !is_branch {
+                    let new_name = declare(subtype, scope, path, gen);
+                    renames.extend(new_name.map(|new_name| (name, new_name)));
+                    continue;
+                }
+                let new_name = declare(subtype, &mut branches, &branch_path, gen);
+                let case = branch_cases
+                    .iter()
+                    .position(|case| case.type_name.as_deref() == Some(name.as_str()))
+                    .map(|index| branch_cases.remove(index));
+                if let (Some(case), Some(new_name)) = (case, new_name) {
+                    case.type_name = Some(new_name);
+                }
+            }
+            if is_branch.contains(&true) {
+                scope.reserve(&gen.base().mod_name(&en.name));
+            }
+            for (name, new_name) in renames {
+                en.cases
+                    .iter_mut()
+                    .filter(|case| case.type_name.as_deref() == Some(name.as_str()))
+                    .for_each(|case| case.type_name = Some(new_name.clone()));
+            }
+        }
+        RsEntity::TupleStruct(ts) => {
+            for (name, new_name) in declare_all(&mut ts.subtypes, scope, path, gen) {
+                if ts.type_name == name {
+                    ts.type_name = new_name;
+                }
+            }
+        }
+        RsEntity::Alias(al) => {
+            for (name, new_name) in declare_all(&mut al.subtypes, scope, path, gen) {
+                if al.original == name {
+                    al.original = new_name;
+                }
+            }
+        }
+        _ => (),
+    }
+    new_name
+}
+
+// Declares nested types, returns the renamed ones with their old names
+fn declare_all(
+    entities: &mut [RsEntity],
+    scope: &mut Scope,
+    path: &str,
+    gen: &Generator,
+) -> Vec<(String, String)> {
+    entities
+        .iter_mut()
+        .filter_map(|entity| {
+            let name = entity.name().to_string();
+            declare(entity, scope, path, gen).map(|new_name| (name, new_name))
+        })
+        .collect()
+}
+
+fn join_path(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}::{}", path, name)
+    }
+}
+
+// Allocates the identifiers of the generated fields of a struct
+pub fn allocate_field_names(fields: &mut [StructField], owner: &str, gen: &Generator) {
+    let mut scope = Scope::default();
+    for field in fields.iter_mut() {
+        if field.type_modifiers.contains(&TypeModifier::Empty) {
+            continue;
+        }
+        let name = gen.struct_field_gen().get_name(field, gen);
+        field.rust_name =
+            allocate(&mut scope, owner, &name, |suffix| format!("{}_{}", name, suffix), gen)
+                .or(field.rust_name.take());
+    }
+}
+
+// Allocates the identifiers of the variants of an enum, next to the variant
+// holding unknown values
+pub fn allocate_variant_names(cases: &mut [EnumCase], owner: &str, gen: &Generator) {
+    let mut scope = Scope::default();
+    scope.reserve("__Unknown__");
+    for case in cases.iter_mut() {
+        let name = gen.enum_case_gen().get_name(case, gen);
+        case.rust_name =
+            allocate(&mut scope, owner, &name, |suffix| format!("{}{}", name, suffix), gen)
+                .or(case.rust_name.take());
+    }
+}
+
+// Allocates the identifier, returns the new one if it is renamed
+fn allocate(
+    scope: &mut Scope,
+    owner: &str,
+    name: &str,
+    suffixed: impl Fn(usize) -> String,
+    gen: &Generator,
+) -> Option<String> {
+    let new_name = suffixed(scope.allocate(name, &suffixed)?);
+    gen.renamed.borrow_mut().push(Renamed {
+        scope: owner.to_string(),
+        name: name.to_string(),
+        new_name: new_name.clone(),
+    });
+    Some(new_name)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        generator::{
+            builder::GeneratorBuilder,
+            names::{Renamed, Scope},
+        },
+        parser::parse,
+    };
+
+    #[test]
+    fn test_scope() {
+        let mut scope = Scope::default();
+        scope.reserve("a_2");
+        let suffixed = |suffix| format!("a_{}", suffix);
+        assert_eq!(scope.allocate("a", suffixed), None);
+        assert_eq!(scope.allocate("a", suffixed), Some(3));
+        assert_eq!(scope.allocate("a", suffixed), Some(4));
+    }
+
+    #[test]
+    fn test_allocate_names() {
+        let schema = parse(
+            r#"
+        <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"
+                   xmlns:tns="http://example.com"
+                   targetNamespace="http://example.com">
+            <xs:complexType name="Item">
+                <xs:sequence>
+                    <xs:element name="value">
+                        <xs:complexType>
+                            <xs:sequence>
+                                <xs:element name="A" type="tns:item"/>
+                            </xs:sequence>
+                        </xs:complexType>
+                    </xs:element>
+                    <xs:element name="Value">
+                        <xs:complexType>
+                            <xs:sequence>
+                                <xs:element name="B" type="tns:Item"/>
+                            </xs:sequence>
+                        </xs:complexType>
+                    </xs:element>
+                </xs:sequence>
+            </xs:complexType>
+            <xs:simpleType name="item">
+                <xs:restriction base="xs:string"/>
+            </xs:simpleType>
+        </xs:schema>
+        "#,
+        )
+        .unwrap();
+
+        let gen = GeneratorBuilder::default().build();
+        let code = gen.generate_rs_file(&schema);
+        assert!(code.contains("pub value: item::ValueType,"));
+        assert!(code.contains("pub value_2: item::ValueType2,"));
+        assert!(code.contains("pub a: Item2,"));
+        assert!(code.contains("pub b: xs::Boxed<Item>,"));
+        assert!(code.contains("pub struct Item2 (pub String);"));
+
+        let renamed = |scope: &str, name: &str, new_name: &str| Renamed {
+            scope: scope.to_string(),
+            name: name.to_string(),
+            new_name: new_name.to_string(),
+        };
+        assert_eq!(
+            gen.take_renamed(),
+            vec![
+                renamed("item", "ValueType", "ValueType2"),
+                renamed("", "Item", "Item2"),
+                renamed("Item", "value", "value_2"),
+            ]
+        );
+        assert!(gen.take_renamed().is_empty());
+    }
+
+    #[test]
+    fn test_extension_redeclares_base_field() {
+        let schema = parse(
+            r#"
+        <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"
+                   xmlns:tns="http://example.com"
+                   targetNamespace="http://example.com">
+            <xs:complexType name="Base">
+                <xs:sequence>
+                    <xs:element name="a" type="xs:int"/>
+                </xs:sequence>
+            </xs:complexType>
+            <xs:complexType name="Derived">
+                <xs:complexContent>
+                    <xs:extension base="tns:Base">
+                        <xs:sequence>
+                            <xs:element name="a" type="xs:string"/>
+                        </xs:sequence>
+                    </xs:extension>
+                </xs:complexContent>
+            </xs:complexType>
+            <xs:complexType name="Restricted">
+                <xs:complexContent>
+                    <xs:restriction base="tns:Base">
+                        <xs:sequence>
+                            <xs:element name="a" type="xs:short"/>
+                        </xs:sequence>
+                    </xs:restriction>
+                </xs:complexContent>
+            </xs:complexType>
+        </xs:schema>
+        "#,
+        )
+        .unwrap();
+
+        let gen = GeneratorBuilder::default().build();
+        let code = gen.generate_rs_file(&schema);
+        let derived = &code[code.find("pub struct Derived").unwrap()..];
+        let derived = &derived[..derived.find('}').unwrap()];
+        assert!(derived.contains("pub a: String,"));
+        assert!(derived.contains("pub a_2: i32,"));
+        let restricted = &code[code.find("pub struct Restricted").unwrap()..];
+        let restricted = &restricted[..restricted.find('}').unwrap()];
+        assert!(restricted.contains("pub a: i16,"));
+        assert!(!restricted.contains("a_2"));
+
+        assert_eq!(
+            gen.take_renamed(),
+            vec![Renamed {
+                scope: "Derived".to_string(),
+                name: "a".to_string(),
+                new_name: "a_2".to_string(),
+            }]
+        );
+    }
+}
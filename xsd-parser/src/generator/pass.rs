@@ -0,0 +1,349 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    generator::utils::split_name,
+    parser::types::{
+        Alias, Enum, EnumCase, QName, RsEntity, RsFile, Struct, StructField, TupleStruct,
+    },
+};
+
+// A rewrite of the parsed schema before the code is generated, registered with
+// GeneratorBuilder::with_pass. Passes run on a copy of every file in the order they
+// are registered. By default a pass visits all types, including nested ones.
+pub trait Pass {
+    fn run(&self, file: &mut RsFile) {
+        for entity in file.types.iter_mut() {
+            walk_entity(self, entity);
+        }
+    }
+
+    fn visit_struct(&self, _entity: &mut Struct) {}
+
+    fn visit_field(&self, _entity: &mut StructField) {}
+
+    fn visit_tuple_struct(&self, _entity: &mut TupleStruct) {}
+
+    fn visit_enum(&self, _entity: &mut Enum) {}
+
+    fn visit_enum_case(&self, _entity: &mut EnumCase) {}
+
+    fn visit_alias(&self, _entity: &mut Alias) {}
+}
+
+// Visits the entity before its fields, cases and nested types
+pub fn walk_entity<P: Pass + ?Sized>(pass: &P, entity: &mut RsEntity) {
+    match entity {
+        RsEntity::Struct(st) => {
+            pass.visit_struct(st);
+            for field in st.fields.iter_mut() {
+                pass.visit_field(field);
+                field.subtypes.iter_mut().for_each(|s| walk_entity(pass, s));
+            }
+            st.subtypes.iter_mut().for_each(|s| walk_entity(pass, s));
+        }
+        RsEntity::StructField(sf) => {
+            pass.visit_field(sf);
+            sf.subtypes.iter_mut().for_each(|s| walk_entity(pass, s));
+        }
+        RsEntity::TupleStruct(ts) => {
+            pass.visit_tuple_struct(ts);
+            ts.subtypes.iter_mut().for_each(|s| walk_entity(pass, s));
+        }
+        RsEntity::Enum(en) => {
+            pass.visit_enum(en);
+            en.cases.iter_mut().for_each(|case| pass.visit_enum_case(case));
+            en.subtypes.iter_mut().for_each(|s| walk_entity(pass, s));
+        }
+        RsEntity::EnumCase(ec) => pass.visit_enum_case(ec),
+        RsEntity::Alias(al) => {
+            pass.visit_alias(al);
+            al.subtypes.iter_mut().for_each(|s| walk_entity(pass, s));
+        }
+        RsEntity::Import(_) => (),
+    }
+}
+
+// Renames global types, together with the fields, cases and aliases referring to them
+#[derive(Default, Clone)]
+pub struct Rename {
+    types: HashMap<QName, String>,
+}
+
+impl Rename {
+    pub fn with_type(mut self, name: QName, new_name: &str) -> Self {
+        self.types.insert(name, new_name.to_string());
+        self
+    }
+
+    // The prefix of the reference is kept, it is bound to the namespace of the type
+    fn rename(&self, type_name: &mut String, qname: &mut Option<QName>) {
+        let new_name = match qname.as_ref().and_then(|qname| self.types.get(qname)) {
+            Some(new_name) => new_name,
+            None => return,
+        };
+        *type_name = match split_name(type_name) {
+            (Some(prefix), _) => format!("{}:{}", prefix, new_name),
+            (None, _) => new_name.clone(),
+        };
+        if let Some(qname) = qname {
+            qname.name = new_name.clone();
+        }
+    }
+}
+
+impl Pass for Rename {
+    fn run(&self, file: &mut RsFile) {
+        for entity in file.types.iter_mut() {
+            let name = QName::new(file.namespace.as_deref(), entity.name());
+            if let Some(new_name) = self.types.get(&name) {
+                entity.set_name(new_name);
+            }
+            walk_entity(self, entity);
+        }
+    }
+
+    fn visit_field(&self, entity: &mut StructField) {
+        self.rename(&mut entity.type_name, &mut entity.type_qname);
+    }
+
+    fn visit_tuple_struct(&self, entity: &mut TupleStruct) {
+        self.rename(&mut entity.type_name, &mut entity.type_qname);
+    }
+
+    fn visit_enum_case(&self, entity: &mut EnumCase) {
+        if let Some(type_name) = entity.type_name.as_mut() {
+            self.rename(type_name, &mut entity.type_qname);
+        }
+    }
+
+    fn visit_alias(&self, entity: &mut Alias) {
+        self.rename(&mut entity.original, &mut entity.original_qname);
+    }
+}
+
+// Removes global types and fields of structs. The generated code doesn't compile if
+// a removed type is still referred to.
+#[derive(Default, Clone)]
+pub struct Prune {
+    types: HashSet<QName>,
+    fields: HashSet<(QName, String)>,
+}
+
+impl Prune {
+    pub fn with_type(mut self, name: QName) -> Self {
+        self.types.insert(name);
+        self
+    }
+
+    // Fields are matched by the local name of the element or attribute
+    pub fn with_field(mut self, struct_name: QName, field_name: &str) -> Self {
+        self.fields.insert((struct_name, field_name.to_string()));
+        self
+    }
+}
+
+impl Pass for Prune {
+    fn run(&self, file: &mut RsFile) {
+        let namespace = file.namespace.as_deref();
+        file.types.retain(|entity| !self.types.contains(&QName::new(namespace, entity.name())));
+        for entity in file.types.iter_mut() {
+            entity.visit_structs(&mut |st| {
+                let name = QName::new(namespace, &st.name);
+                st.fields.retain(|f| {
+                    !self.fields.contains(&(name.clone(), split_name(&f.name).1.to_string()))
+                });
+            });
+        }
+    }
+}
+
+// Replaces the types of fields, cases and aliases referring to a type with a Rust type,
+// e.g. xs:dateTime with chrono::DateTime<chrono::Utc>
+#[derive(Default, Clone)]
+pub struct TypeOverride {
+    types: HashMap<QName, String>,
+}
+
+impl TypeOverride {
+    pub fn with_type(mut self, name: QName, rust_type: &str) -> Self {
+        self.types.insert(name, rust_type.to_string());
+        self
+    }
+
+    fn rust_type(&self, qname: &Option<QName>) -> Option<String> {
+        qname.as_ref().and_then(|qname| self.types.get(qname)).cloned()
+    }
+}
+
+impl Pass for TypeOverride {
+    fn visit_field(&self, entity: &mut StructField) {
+        if let Some(rust_type) = self.rust_type(&entity.type_qname) {
+            entity.rust_type = Some(rust_type);
+            entity.subtypes.clear();
+        }
+    }
+
+    fn visit_tuple_struct(&self, entity: &mut TupleStruct) {
+        if let Some(rust_type) = self.rust_type(&entity.type_qname) {
+            entity.rust_type = Some(rust_type);
+        }
+    }
+
+    fn visit_enum_case(&self, entity: &mut EnumCase) {
+        if let Some(rust_type) = self.rust_type(&entity.type_qname) {
+            entity.rust_type = Some(rust_type);
+        }
+    }
+
+    fn visit_alias(&self, entity: &mut Alias) {
+        if let Some(rust_type) = self.rust_type(&entity.original_qname) {
+            entity.rust_type = Some(rust_type);
+        }
+    }
+}
+
+// Adds derives to global types, e.g. Eq and Hash to use them as keys of maps
+#[derive(Default, Clone)]
+pub struct Derive {
+    types: HashMap<QName, Vec<String>>,
+}
+
+impl Derive {
+    pub fn with_type(mut self, name: QName, derives: &[&str]) -> Self {
+        self.types.entry(name).or_default().extend(derives.iter().map(|d| d.to_string()));
+        self
+    }
+}
+
+impl Pass for Derive {
+    fn run(&self, file: &mut RsFile) {
+        for entity in file.types.iter_mut() {
+            let derives =
+                match self.types.get(&QName::new(file.namespace.as_deref(), entity.name())) {
+                    Some(derives) => derives.iter().cloned(),
+                    None => continue,
+                };
+            match entity {
+                RsEntity::Struct(st) => st.derives.extend(derives),
+                RsEntity::TupleStruct(ts) => ts.derives.extend(derives),
+                RsEntity::Enum(en) => en.derives.extend(derives),
+                _ => (),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        generator::{
+            builder::GeneratorBuilder,
+            pass::{Derive, Pass, Prune, Rename, TypeOverride},
+        },
+        parser::{
+            constants::namespace,
+            parse,
+            types::{QName, RsFile, StructField, TypeModifier},
+        },
+    };
+
+    const TNS: &str = "http://example.com";
+
+    fn schema() -> RsFile {
+        parse(
+            r#"
+        <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"
+                   xmlns:tns="http://example.com"
+                   targetNamespace="http://example.com">
+            <xs:complexType name="Item">
+                <xs:sequence>
+                    <xs:element name="Code" type="tns:Code"/>
+                    <xs:element name="Created" type="xs:dateTime"/>
+                    <xs:element name="Internal" type="xs:string"/>
+                </xs:sequence>
+            </xs:complexType>
+            <xs:simpleType name="Code">
+                <xs:restriction base="xs:string"/>
+            </xs:simpleType>
+            <xs:simpleType name="Unused">
+                <xs:restriction base="xs:int"/>
+            </xs:simpleType>
+        </xs:schema>
+        "#,
+        )
+        .unwrap()
+    }
+
+    fn generate(builder: GeneratorBuilder) -> String {
+        builder.build().generate_rs_file(&schema())
+    }
+
+    #[test]
+    fn test_rename() {
+        let code = generate(GeneratorBuilder::default().with_pass(Box::new(
+            Rename::default().with_type(QName::new(Some(TNS), "Code"), "ItemCode"),
+        )));
+        assert!(code.contains("pub struct ItemCode (pub String);"));
+        assert!(code.contains("pub code: ItemCode,"));
+        assert!(!code.contains("pub struct Code "));
+    }
+
+    #[test]
+    fn test_prune() {
+        let code = generate(
+            GeneratorBuilder::default().with_pass(Box::new(
+                Prune::default()
+                    .with_type(QName::new(Some(TNS), "Unused"))
+                    .with_field(QName::new(Some(TNS), "Item"), "Internal"),
+            )),
+        );
+        assert!(!code.contains("Unused"));
+        assert!(!code.contains("internal"));
+        assert!(code.contains("pub code: Code,"));
+    }
+
+    #[test]
+    fn test_type_override() {
+        let code = generate(
+            GeneratorBuilder::default().with_pass(Box::new(
+                TypeOverride::default()
+                    .with_type(
+                        QName::new(Some(namespace::XSD), "dateTime"),
+                        "chrono::NaiveDateTime",
+                    )
+                    .with_type(QName::new(Some(TNS), "Code"), "u32"),
+            )),
+        );
+        assert!(code.contains("pub created: chrono::NaiveDateTime,"));
+        assert!(code.contains("pub code: u32,"));
+        // The documentation keeps the declared type, u32 isn't validated as a generated type
+        assert!(code.contains("/// XSD: element `Code` of type `tns:Code`"));
+        assert!(!code.contains("self.code.validate"));
+    }
+
+    #[test]
+    fn test_derive() {
+        let code = generate(GeneratorBuilder::default().with_pass(Box::new(
+            Derive::default().with_type(QName::new(Some(TNS), "Code"), &["Eq", "Hash"]),
+        )));
+        assert!(code.contains("#[derive(Eq, Hash)]\n#[derive(Default, Clone, PartialEq, Debug, UtilsTupleIo, UtilsDefaultSerde)]\npub struct Code "));
+        assert_eq!(code.matches("#[derive(Eq, Hash)]").count(), 1);
+    }
+
+    #[test]
+    fn test_custom_pass() {
+        // Passes run in the order they are registered
+        struct Optional;
+        impl Pass for Optional {
+            fn visit_field(&self, entity: &mut StructField) {
+                entity.type_modifiers = vec![TypeModifier::Option];
+            }
+        }
+
+        let code = generate(GeneratorBuilder::default().with_pass(Box::new(Optional)).with_pass(
+            Box::new(Rename::default().with_type(QName::new(Some(TNS), "Code"), "ItemCode")),
+        ));
+        assert!(code.contains("pub code: Option<ItemCode>,"));
+        assert!(code.contains("pub internal: Option<String>,"));
+    }
+}
@@ -1,40 +1,91 @@
 use std::borrow::Cow;
 
 use crate::{
-    generator::{validator::gen_validate_impl, Generator},
-    parser::types::Struct,
+    generator::{
+        default::default_fn_name,
+        error::GenerateError,
+        names::allocate_field_names,
+        pass::{walk_entity, Pass},
+        utils::shift,
+        validator::{
+            gen_checks, gen_child_path, gen_facets_validation, gen_fixed_value_validation,
+            gen_mixed_sequence_validation, gen_nested_validation, gen_occurs_validation,
+            gen_text_facet_validation, gen_validate_impl, gen_wildcard_validation,
+        },
+        Generator,
+    },
+    parser::{
+        constants::tag,
+        types::{
+            ChoiceBranch, Enum, EnumCase, EnumSource, RsEntity, RsFile, Struct, StructField,
+            StructFieldSource, TypeModifier,
+        },
+        xsd_elements::{FormType, ProcessContents},
+    },
 };
 
 pub trait StructGenerator {
     fn generate(&self, entity: &Struct, gen: &Generator) -> String {
+        let entity = &self.prepare(entity, gen);
+        // Fields go first as they qualify the types of fields with subtypes
+        let fields = self.fields(entity, gen);
         format!(
-            "{comment}{macros}pub struct {name} {{{fields}}}\n\n{validation}\n{subtypes}\n",
+            "{comment}{derives}{macros}pub struct {name} {{{fields}}}\n\n{default}{declared_attributes}{validation}\n{subtypes}\n",
             comment = self.format_comment(entity, gen),
+            derives = gen.base().format_derives(&entity.derives),
             macros = self.macros(entity, gen),
             name = self.get_type_name(entity, gen),
-            fields = self.fields(entity, gen),
+            fields = fields,
+            default = self.default_impl(entity, gen),
+            declared_attributes = self.declared_attributes(entity, gen),
             subtypes = self.subtypes(entity, gen),
             validation = self.validation(entity, gen),
         )
     }
 
-    fn fields(&self, entity: &Struct, gen: &Generator) -> String {
-        let mod_name = self.mod_name(entity, gen);
+    // Copy of the struct with the fields as they are generated
+    fn prepare(&self, entity: &Struct, gen: &Generator) -> Struct {
+        let mut entity = entity.clone();
+        let mod_name = self.mod_name(&entity, gen);
 
-        entity.fields.borrow_mut().iter_mut().for_each(|f| {
+        entity.fields.iter_mut().for_each(|f| {
             if !f.subtypes.is_empty() {
                 f.type_name = format!("{}::{}", mod_name, f.type_name)
             }
         });
 
+        // All unmatched elements (or attributes) go to a single flattened field,
+        // the wildcards of each kind are merged into the first one
+        let mut fields: Vec<StructField> = vec![];
+        for field in std::mem::take(&mut entity.fields) {
+            let merged = match field.source {
+                StructFieldSource::Any | StructFieldSource::AnyAttribute => {
+                    fields.iter_mut().find(|f| f.source == field.source)
+                }
+                _ => None,
+            };
+            match merged {
+                Some(merged) => merge_wildcards(merged, &field),
+                None => fields.push(field),
+            }
+        }
+        entity.fields = fields;
+
+        let name = self.get_type_name(&entity, gen);
+        allocate_field_names(&mut entity.fields, &name, gen);
+        entity
+    }
+
+    fn fields(&self, entity: &Struct, gen: &Generator) -> String {
+        let outer_struct = gen.struct_name.replace(Some(self.get_type_name(entity, gen)));
         let fields = entity
             .fields
-            .borrow()
             .iter()
             .map(|f| gen.struct_field_gen().generate(f, gen))
             .filter(|s| !s.is_empty())
             .collect::<Vec<String>>()
             .join("\n\n");
+        *gen.struct_name.borrow_mut() = outer_struct;
 
         if fields.is_empty() {
             fields
@@ -46,7 +97,6 @@ pub trait StructGenerator {
     fn subtypes(&self, entity: &Struct, gen: &Generator) -> String {
         let field_subtypes = entity
             .fields
-            .borrow()
             .iter()
             .map(|f| gen.base().join_subtypes(f.subtypes.as_ref(), gen))
             .collect::<Vec<String>>()
@@ -68,18 +118,123 @@ pub trait StructGenerator {
     }
 
     fn shift(&self, text: &str, indent: &str) -> String {
-        text.replace("\n\n\n", "\n") // TODO: fix this workaround replace
-            .split('\n')
-            .map(|s| if !s.is_empty() { format!("\n{}{}", indent, s) } else { "\n".to_string() })
-            .fold(indent.to_string(), |acc, x| acc + &x)
+        shift(text, indent)
     }
 
     fn get_type_name(&self, entity: &Struct, gen: &Generator) -> String {
         gen.base().format_type_name(entity.name.as_str(), gen).into()
     }
 
-    fn macros(&self, _entity: &Struct, gen: &Generator) -> Cow<'static, str> {
-        let derives = "#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]\n";
+    // Fields with a default or fixed value, paired with the name of the function
+    // returning that value
+    fn default_values(&self, entity: &Struct, gen: &Generator) -> Vec<(StructField, String)> {
+        let name = self.get_type_name(entity, gen);
+        entity
+            .fields
+            .iter()
+            .filter(|f| matches!(gen.struct_field_gen().default_value(f, gen), Ok(Some(_))))
+            .map(|f| {
+                let field_name = gen.struct_field_gen().get_name(f, gen);
+                (f.clone(), default_fn_name(&name, &field_name))
+            })
+            .collect()
+    }
+
+    // Default impl and value functions for structs with default or fixed values. Fields
+    // with an invalid value are reported and take the default value of their type.
+    fn default_impl(&self, entity: &Struct, gen: &Generator) -> String {
+        for field in entity.fields.iter() {
+            if let Err(reason) = gen.struct_field_gen().default_value(field, gen) {
+                gen.errors.borrow_mut().push(GenerateError::InvalidValue {
+                    owner: self.get_type_name(entity, gen),
+                    field: gen.struct_field_gen().get_name(field, gen),
+                    reason,
+                });
+            }
+        }
+        let default_values = self.default_values(entity, gen);
+        if default_values.is_empty() {
+            return "".into();
+        }
+
+        let indent = gen.base().indent();
+        let fields = entity
+            .fields
+            .iter()
+            .filter(|f| !f.type_modifiers.contains(&TypeModifier::Empty))
+            .map(|f| {
+                let name = gen.struct_field_gen().get_name(f, gen);
+                let value = default_values
+                    .iter()
+                    .find(|(field, _)| field.rust_name == f.rust_name && field.name == f.name)
+                    .map(|(_, value_fn)| format!("{}()", value_fn))
+                    .unwrap_or_else(|| "Default::default()".into());
+                format!("{indent}{indent}{indent}{}: {},\n", name, value, indent = indent)
+            })
+            .collect::<String>();
+
+        let value_fns = default_values
+            .iter()
+            .map(|(f, value_fn)| {
+                format!(
+                    "fn {}() -> {} {{\n{}{}\n}}\n\n",
+                    value_fn,
+                    gen.struct_field_gen().get_type_name(f, gen),
+                    indent,
+                    gen.struct_field_gen().default_value(f, gen).ok().flatten().unwrap()
+                )
+            })
+            .collect::<String>();
+
+        format!(
+            "impl Default for {name} {{\n{indent}fn default() -> Self {{\n{indent}{indent}Self {{\n{fields}{indent}{indent}}}\n{indent}}}\n}}\n\n{value_fns}",
+            name = self.get_type_name(entity, gen),
+            indent = indent,
+            fields = fields,
+            value_fns = value_fns
+        )
+    }
+
+    // Attribute names which the xs:anyAttribute field of the struct must skip
+    fn declared_attributes(&self, entity: &Struct, gen: &Generator) -> String {
+        let fields = &entity.fields;
+        if !fields.iter().any(|f| matches!(f.source, StructFieldSource::AnyAttribute)) {
+            return "".into();
+        }
+
+        // Attributes named by a reference are in the namespace of the declaration,
+        // the others are unqualified unless their form is qualified
+        let target_ns = gen.target_ns.borrow();
+        let names = fields
+            .iter()
+            .filter(|f| matches!(f.source, StructFieldSource::Attribute))
+            .filter(|f| !f.type_modifiers.contains(&TypeModifier::Empty))
+            .map(|f| {
+                let namespace = match f.name.split_once(':') {
+                    Some(_) => f.type_qname.as_ref().and_then(|qname| qname.namespace.as_deref()),
+                    None => target_ns
+                        .as_ref()
+                        .filter(|_| f.form == FormType::Qualified)
+                        .map(|ns| ns.uri()),
+                };
+                format!("({:?}, {:?})", namespace, f.name.rsplit(':').next().unwrap())
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "impl xs::DeclaredAttributes for {name} {{\n{indent}const NAMES: &'static [(Option<&'static str>, &'static str)] = &[{names}];\n}}\n\n",
+            name = self.get_type_name(entity, gen),
+            indent = gen.base().indent(),
+            names = names
+        )
+    }
+
+    fn macros(&self, entity: &Struct, gen: &Generator) -> Cow<'static, str> {
+        let derives = if self.default_values(entity, gen).is_empty() {
+            "#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]\n"
+        } else {
+            "#[derive(Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]\n"
+        };
         let tns = gen.target_ns.borrow();
         match tns.as_ref() {
             Some(tn) => match tn.name() {
@@ -101,16 +256,226 @@ pub trait StructGenerator {
     }
 
     fn format_comment(&self, entity: &Struct, gen: &Generator) -> String {
-        gen.base().format_comment(entity.comment.as_deref(), 0)
+        let details = gen.base().format_declaration(entity.qname.as_ref(), gen);
+        gen.base().format_doc(entity.comment.as_deref(), details.as_slice(), 0)
     }
 
     fn mod_name(&self, entity: &Struct, gen: &Generator) -> String {
         gen.base().mod_name(entity.name.as_str())
     }
 
+    // Each field is checked apart, so the errors of all the fields are reported.
+    // Fields of generated types are checked with their own Validate impls.
     fn validation(&self, entity: &Struct, gen: &Generator) -> Cow<'static, str> {
-        // Empty validation
-        Cow::Owned(gen_validate_impl(self.get_type_name(entity, gen).as_str(), ""))
+        let body = entity
+            .fields
+            .iter()
+            .filter(|f| !f.type_modifiers.contains(&TypeModifier::Empty))
+            .map(|f| {
+                let path = match f.source {
+                    StructFieldSource::Element => gen_child_path(&f.name, false),
+                    StructFieldSource::Attribute => gen_child_path(&f.name, true),
+                    _ => "path".into(),
+                };
+                let nested = match f.source {
+                    StructFieldSource::Element
+                    | StructFieldSource::Attribute
+                    | StructFieldSource::Base
+                    | StructFieldSource::Choice
+                    | StructFieldSource::Group
+                    | StructFieldSource::Mixed
+                        if f.rust_type.is_none()
+                            && gen.base().is_generated_type(&f.type_name, gen) =>
+                    {
+                        let name = gen.struct_field_gen().get_name(f, gen);
+                        gen_nested_validation(&format!("self.{}", name), &path)
+                    }
+                    _ => "".into(),
+                };
+                gen_checks(&path, &self.field_checks(entity, f, gen)) + &nested
+            })
+            .collect::<String>();
+        Cow::Owned(gen_validate_impl(self.get_type_name(entity, gen).as_str(), body.as_str()))
+    }
+
+    // Facets, fixed value and occurrence of a field
+    fn field_checks(&self, entity: &Struct, field: &StructField, gen: &Generator) -> String {
+        let name = gen.struct_field_gen().get_name(field, gen);
+        let facets = field.facets.iter().map(|f| f.facet_type.clone()).collect::<Vec<_>>();
+        let typename =
+            gen.base().format_rust_type(&field.type_name, field.rust_type.as_deref(), gen);
+        let facets = match (facets.is_empty(), field.is_text()) {
+            (true, _) => "".into(),
+            (false, true) => gen_text_facet_validation(&facets, &name, &typename),
+            (false, false) => gen_facets_validation(&facets, &name, &typename),
+        };
+        let fixed = if field.value_constraint.as_ref().is_some_and(|v| v.is_fixed())
+            && matches!(gen.struct_field_gen().default_value(field, gen), Ok(Some(_)))
+        {
+            gen_fixed_value_validation(
+                &name,
+                &default_fn_name(&self.get_type_name(entity, gen), &name),
+                field.type_modifiers.contains(&TypeModifier::Option),
+            )
+        } else {
+            "".into()
+        };
+        // Option and plain fields get their bounds from the type
+        let occurs = if field.type_modifiers.contains(&TypeModifier::Array) {
+            gen_occurs_validation(&field.occurs, &name)
+        } else {
+            "".into()
+        };
+        let sequence = match field.subtypes.first() {
+            Some(RsEntity::Enum(Enum { sequence: Some(sequence), .. }))
+                if matches!(field.source, StructFieldSource::Mixed) =>
+            {
+                gen_mixed_sequence_validation(sequence, &name)
+            }
+            _ => "".into(),
+        };
+        let is_any = matches!(field.source, StructFieldSource::Any);
+        let wildcard = match &field.wildcard {
+            Some(wildcard) => gen_wildcard_validation(
+                wildcard,
+                Some(&field.occurs).filter(|_| is_any),
+                Some(gen.elements.borrow().as_slice())
+                    .filter(|_| is_any && wildcard.process_contents == ProcessContents::Strict),
+                &name,
+                gen.target_ns.borrow().as_ref().map(|ns| ns.uri()),
+            ),
+            None => "".into(),
+        };
+        facets + &fixed + &occurs + &sequence + &wildcard
+    }
+}
+
+// Bounds of the merged field allow the content of both wildcards in any order
+fn merge_wildcards(merged: &mut StructField, field: &StructField) {
+    merged.occurs.min += field.occurs.min;
+    merged.occurs.max = None;
+    if let (Some(merged), Some(wildcard)) = (&mut merged.wildcard, &field.wildcard) {
+        merged.namespace = merge_namespaces(&merged.namespace, &wildcard.namespace);
+        // The weakest processing is kept, it rejects nothing allowed by either wildcard
+        if wildcard.process_contents > merged.process_contents {
+            merged.process_contents = wildcard.process_contents;
+        }
+    }
+}
+
+// Union of two namespace constraints, "##any" when it is not a list of namespaces
+fn merge_namespaces(a: &str, b: &str) -> String {
+    let is_list = |ns: &str| !matches!(ns.trim(), "##any" | "##other");
+    if a.trim() == b.trim() {
+        return a.trim().to_string();
+    }
+    if !is_list(a) || !is_list(b) {
+        return "##any".to_string();
+    }
+    let mut items = a.split_whitespace().collect::<Vec<_>>();
+    for item in b.split_whitespace() {
+        if !items.contains(&item) {
+            items.push(item);
+        }
+    }
+    items.join(" ")
+}
+
+// Text and child elements of mixed content are kept in document order by a single
+// field, a choice between the text and the elements of the struct. It is generated
+// before the names are allocated, as the choice is a new type. The order and occurrence
+// of the elements of a sequence are kept for validation.
+pub fn merge_mixed_contents(schemas: &mut [RsFile]) {
+    for entity in schemas.iter_mut().flat_map(|schema| schema.types.iter_mut()) {
+        walk_entity(&MixedContent, entity);
+    }
+}
+
+struct MixedContent;
+
+impl Pass for MixedContent {
+    fn visit_struct(&self, entity: &mut Struct) {
+        if !entity.mixed {
+            return;
+        }
+        let mut content = Enum {
+            name: format!("{}Content", entity.name),
+            cases: vec![EnumCase {
+                name: "Text".to_string(),
+                type_name: Some("String".to_string()),
+                source: EnumSource::Choice,
+                branch: ChoiceBranch::Text,
+                ..Default::default()
+            }],
+            type_name: "String".to_string(),
+            source: EnumSource::Choice,
+            // Only the content reads the child elements
+            siblings: Some(vec![]),
+            ..Default::default()
+        };
+
+        let mut fields = vec![];
+        let mut sequence = Some(vec![]);
+        for mut field in std::mem::take(&mut entity.fields) {
+            // The alternatives of a choice or a wildcard can come in any order
+            if matches!(field.source, StructFieldSource::Choice | StructFieldSource::Any) {
+                sequence = None;
+            }
+            match field.source {
+                StructFieldSource::Element => {
+                    if let Some(sequence) = sequence.as_mut() {
+                        let name = field.name.rsplit(':').next().unwrap_or_default();
+                        sequence.push((name.to_string(), field.occurs.clone()));
+                    }
+                    content.cases.push(EnumCase {
+                        name: field.name,
+                        comment: field.comment,
+                        type_name: Some(field.type_name),
+                        type_qname: field.type_qname,
+                        rust_type: field.rust_type,
+                        source: EnumSource::Choice,
+                        branch: ChoiceBranch::Element,
+                        form: field.form,
+                        ..Default::default()
+                    });
+                    content.subtypes.append(&mut field.subtypes);
+                }
+                StructFieldSource::Choice => match field.subtypes.pop() {
+                    Some(RsEntity::Enum(mut en)) => {
+                        content.cases.append(&mut en.cases);
+                        content.subtypes.append(&mut en.subtypes);
+                    }
+                    // A choice generated at the top level, e.g. by the base type
+                    _ => content.cases.push(EnumCase {
+                        name: field.type_name.clone(),
+                        type_name: Some(field.type_name),
+                        source: EnumSource::Choice,
+                        branch: ChoiceBranch::Choice,
+                        ..Default::default()
+                    }),
+                },
+                StructFieldSource::Any => content.cases.push(EnumCase {
+                    name: "Any".to_string(),
+                    type_name: Some("xs:AnyElement".to_string()),
+                    comment: field.comment,
+                    source: EnumSource::Choice,
+                    branch: ChoiceBranch::Any,
+                    wildcard: field.wildcard,
+                    ..Default::default()
+                }),
+                StructFieldSource::Text => (),
+                _ => fields.push(field),
+            }
+        }
+        content.sequence = sequence;
+        fields.push(StructField {
+            name: tag::CONTENT.to_string(),
+            type_name: content.name.clone(),
+            source: StructFieldSource::Mixed,
+            subtypes: vec![RsEntity::Enum(content)],
+            ..Default::default()
+        });
+        entity.fields = fields;
     }
 }
 
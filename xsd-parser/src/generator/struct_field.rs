@@ -1,9 +1,17 @@
 use crate::{
     generator::{
-        default::{yaserde_for_attribute, yaserde_for_element, yaserde_for_flatten_element},
+        default::{
+            code_span, default_fn_name, default_format_facets, default_format_value,
+            yaserde_for_attribute, yaserde_for_element, yaserde_for_flatten_element,
+            yaserde_for_text,
+        },
+        utils::match_built_in_type,
         Generator,
     },
-    parser::types::{StructField, StructFieldSource, TypeModifier},
+    parser::{
+        types::{EnumSource, QName, RsEntity, StructField, StructFieldSource, TypeModifier},
+        xsd_elements::{FormType, ProcessContents},
+    },
 };
 
 pub trait StructFieldGenerator {
@@ -22,38 +30,193 @@ pub trait StructFieldGenerator {
     }
 
     fn get_type_name(&self, entity: &StructField, gen: &Generator) -> String {
-        gen.base()
-            .modify_type(
-                gen.base().format_type_name(entity.type_name.as_str(), gen).as_ref(),
-                &entity.type_modifiers,
-            )
-            .into()
+        // yaserde serializes text content only from strings
+        if entity.is_text() {
+            return "String".into();
+        }
+        // Wildcard content is kept as raw XML
+        if matches!(entity.source, StructFieldSource::Any) {
+            return "xs::AnyElements".into();
+        }
+        // Repeated choices keep the order of the alternatives
+        if matches!(entity.source, StructFieldSource::Choice)
+            && entity.type_modifiers.contains(&TypeModifier::Array)
+        {
+            let choice = gen.base().format_type_name(entity.type_name.as_str(), gen);
+            return format!("xs::Choices<{}>", choice);
+        }
+        // Mixed content keeps the text between the elements
+        if matches!(entity.source, StructFieldSource::Mixed) {
+            let content = gen.base().format_type_name(entity.type_name.as_str(), gen);
+            return format!("xs::Mixed<{}>", content);
+        }
+        // Undeclared attributes are told apart with the names declared by the struct
+        if matches!(entity.source, StructFieldSource::AnyAttribute) {
+            let owner = gen.struct_name.borrow().clone().unwrap_or_else(|| "Self".into());
+            return format!("xs::AnyAttributes<{}>", owner);
+        }
+        let type_name =
+            gen.base().format_rust_type(&entity.type_name, entity.rust_type.as_deref(), gen);
+        gen.base().modify_type(type_name.as_ref(), &entity.type_modifiers).into()
     }
 
     fn get_name(&self, entity: &StructField, gen: &Generator) -> String {
-        gen.base().format_name(entity.name.as_str()).into()
+        match &entity.rust_name {
+            Some(name) => name.clone(),
+            None => gen.base().format_name(entity.name.as_str()).into(),
+        }
     }
 
     fn format_comment(&self, entity: &StructField, gen: &Generator) -> String {
-        gen.base().format_comment(entity.comment.as_deref(), gen.base().indent_size())
+        let details = self
+            .format_declaration(entity, gen)
+            .into_iter()
+            .chain(self.format_wildcard(entity))
+            .chain(default_format_facets(&entity.facets))
+            .collect::<Vec<_>>();
+        gen.base().format_doc(entity.comment.as_deref(), &details, gen.base().indent_size())
+    }
+
+    // Detail of the documentation naming the element or attribute and its type
+    fn format_declaration(&self, entity: &StructField, gen: &Generator) -> Option<String> {
+        let kind = match entity.source {
+            StructFieldSource::Element => "element",
+            StructFieldSource::Attribute => "attribute",
+            _ => return None,
+        };
+        Some(gen.base().format_element_declaration(
+            kind,
+            &entity.name,
+            entity.type_qname.as_ref(),
+            gen,
+        ))
+    }
+
+    // Details of xs:any and xs:anyAttribute, and how the generated code treats their content
+    fn format_wildcard(&self, entity: &StructField) -> Vec<String> {
+        let wildcard = match &entity.wildcard {
+            Some(wildcard) => wildcard,
+            None => return vec![],
+        };
+        let kind = match wildcard.process_contents {
+            ProcessContents::Strict => "strict",
+            ProcessContents::Lax => "lax",
+            ProcessContents::Skip => "skip",
+        };
+        let strict = wildcard.process_contents == ProcessContents::Strict;
+        let (item, note) = match entity.source {
+            StructFieldSource::Any if strict => (
+                "element",
+                "Elements without a global declaration in the generated schemas fail validation.",
+            ),
+            StructFieldSource::Any => ("element", "Elements are kept as raw XML."),
+            _ if strict => {
+                ("attribute", "Attributes are kept as raw XML, only their namespace is validated.")
+            }
+            _ => ("attribute", "Attributes are kept as raw XML."),
+        };
+        vec![
+            format!(
+                "XSD: any {} of namespace {}, processContents `{}`",
+                item,
+                code_span(wildcard.namespace.trim()),
+                kind
+            ),
+            note.to_string(),
+        ]
+    }
+
+    // Expression for the default or fixed value of the field, if it has one, or the reason
+    // why the value is not valid for the type of the field
+    fn default_value(
+        &self,
+        entity: &StructField,
+        gen: &Generator,
+    ) -> Result<Option<String>, String> {
+        if !matches!(entity.source, StructFieldSource::Element | StructFieldSource::Attribute) {
+            return Ok(None);
+        }
+        let value = match entity.value_constraint.as_ref() {
+            Some(constraint) => constraint.value(),
+            None => return Ok(None),
+        };
+        if entity.rust_type.is_none() {
+            if let Some(qname) = entity.type_qname.as_ref() {
+                check_declared_value(value, qname, gen)?;
+            }
+        }
+        default_format_value(
+            value,
+            gen.base()
+                .format_rust_type(&entity.type_name, entity.rust_type.as_deref(), gen)
+                .as_ref(),
+            &entity.type_modifiers,
+        )
     }
 
     fn macros(&self, entity: &StructField, gen: &Generator) -> String {
         let indent = gen.base().indent();
+        // Unqualified elements and attributes are not in the target namespace
+        let target_ns = gen.target_ns.borrow();
+        let namespace = target_ns.as_ref().filter(|_| entity.form == FormType::Qualified);
         match entity.source {
-            StructFieldSource::Choice => yaserde_for_flatten_element(indent.as_str()),
+            StructFieldSource::Choice
+            | StructFieldSource::Any
+            | StructFieldSource::AnyAttribute
+            | StructFieldSource::Mixed => yaserde_for_flatten_element(indent.as_str()),
             StructFieldSource::Attribute => {
-                yaserde_for_attribute(entity.name.as_str(), indent.as_str())
+                // Absent attributes take their default or fixed value
+                let default_fn = gen
+                    .struct_name
+                    .borrow()
+                    .as_ref()
+                    .filter(|_| matches!(self.default_value(entity, gen), Ok(Some(_))))
+                    .map(|st| default_fn_name(st, &self.get_name(entity, gen)));
+                yaserde_for_attribute(
+                    entity.name.as_str(),
+                    namespace,
+                    default_fn.as_deref(),
+                    indent.as_str(),
+                )
+            }
+            StructFieldSource::Text => yaserde_for_text(indent.as_str()),
+            StructFieldSource::Element => {
+                yaserde_for_element(entity.name.as_str(), namespace, indent.as_str())
             }
-            StructFieldSource::Element => yaserde_for_element(
-                entity.name.as_str(),
-                gen.target_ns.borrow().as_ref(),
-                indent.as_str(),
-            ),
             _ => "".into(),
         }
     }
 }
 
+// Checks a value of a type declared in the generated schemas: it is one of the values of
+// an enumeration, or a valid value of the type a simple type restricts. Other types are
+// checked by their Rust type, see default_format_value.
+fn check_declared_value(value: &str, qname: &QName, gen: &Generator) -> Result<(), String> {
+    let simple_types = gen.simple_types.borrow();
+    match simple_types.get(qname) {
+        Some(RsEntity::Enum(en)) if en.source == EnumSource::Restriction => {
+            if en.cases.iter().any(|case| case.value == value || case.value == value.trim()) {
+                Ok(())
+            } else {
+                Err(format!("{:?} is not one of the values of {}", value, qname.name))
+            }
+        }
+        Some(RsEntity::TupleStruct(ts))
+            if ts.rust_type.is_none() && !ts.type_modifiers.contains(&TypeModifier::Array) =>
+        {
+            match ts.type_qname.as_ref() {
+                // Built-in types are named without a prefix in the context of any schema
+                Some(base) if base.is_xsd() => match match_built_in_type(&base.name, &None, &[]) {
+                    Some(type_name) => default_format_value(value, type_name, &[]).map(|_| ()),
+                    None => Ok(()),
+                },
+                Some(base) => check_declared_value(value, base, gen),
+                None => Ok(()),
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
 pub struct DefaultStructFieldGen;
 impl StructFieldGenerator for DefaultStructFieldGen {}
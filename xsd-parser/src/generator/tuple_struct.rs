@@ -2,7 +2,8 @@ use std::borrow::Cow;
 
 use crate::{
     generator::{
-        validator::{gen_facet_validation, gen_validate_impl},
+        default::default_format_facets,
+        validator::{gen_checks, gen_facets_validation, gen_nested_validation, gen_validate_impl},
         Generator,
     },
     parser::types::TupleStruct,
@@ -11,8 +12,9 @@ use crate::{
 pub trait TupleStructGenerator {
     fn generate(&self, entity: &TupleStruct, gen: &Generator) -> String {
         format!(
-            "{comment}{macros}pub struct {name} (pub {typename});\n{subtypes}\n{validation}\n",
+            "{comment}{derives}{macros}pub struct {name} (pub {typename});\n{subtypes}\n{validation}\n",
             comment = self.format_comment(entity, gen),
+            derives = gen.base().format_derives(&entity.derives),
             name = self.get_name(entity, gen),
             macros = self.macros(entity, gen),
             typename = self.get_type_name(entity, gen),
@@ -26,12 +28,9 @@ pub trait TupleStructGenerator {
     }
 
     fn get_type_name(&self, entity: &TupleStruct, gen: &Generator) -> String {
-        gen.base()
-            .modify_type(
-                gen.base().format_type_name(entity.type_name.as_str(), gen).as_ref(),
-                &entity.type_modifiers,
-            )
-            .into()
+        let type_name =
+            gen.base().format_rust_type(&entity.type_name, entity.rust_type.as_deref(), gen);
+        gen.base().modify_type(type_name.as_ref(), &entity.type_modifiers).into()
     }
 
     fn get_name(&self, entity: &TupleStruct, gen: &Generator) -> String {
@@ -43,15 +42,26 @@ pub trait TupleStructGenerator {
     }
 
     fn format_comment(&self, entity: &TupleStruct, gen: &Generator) -> String {
-        gen.base().format_comment(entity.comment.as_deref(), 0)
+        let details = gen
+            .base()
+            .format_declaration(entity.qname.as_ref(), gen)
+            .into_iter()
+            .chain(default_format_facets(&entity.facets))
+            .collect::<Vec<_>>();
+        gen.base().format_doc(entity.comment.as_deref(), &details, 0)
     }
 
+    // The facets of the base type are checked by its own Validate impl
     fn validation(&self, entity: &TupleStruct, gen: &Generator) -> Cow<'static, str> {
-        let body = entity
-            .facets
-            .iter()
-            .map(|f| gen_facet_validation(&f.facet_type, "0", &self.get_type_name(entity, gen)))
-            .fold(String::new(), |x, y| (x + &y));
+        let facets = entity.facets.iter().map(|f| f.facet_type.clone()).collect::<Vec<_>>();
+        let checks = gen_facets_validation(&facets, "0", &self.get_type_name(entity, gen));
+        let nested =
+            if entity.rust_type.is_none() && gen.base().is_generated_type(&entity.type_name, gen) {
+                gen_nested_validation("self.0", "path")
+            } else {
+                "".into()
+            };
+        let body = gen_checks("path", &checks) + &nested;
         Cow::Owned(gen_validate_impl(self.get_name(entity, gen).as_str(), body.as_str()))
     }
 }
@@ -1,27 +1,43 @@
-use roxmltree::Namespace;
+use crate::{
+    generator::default::escape_doc,
+    parser::{constants::namespace, types::Namespace, xsd_elements::FormType},
+};
 
 pub fn split_comment_line(s: &str, max_len: usize, indent: usize) -> String {
     let indent_str = " ".repeat(indent);
 
-    let mut splitted = format!("{}//", indent_str);
-    let mut current_line_length = indent + 2;
-    for word in s.split_whitespace() {
+    let mut splitted = format!("{}///", indent_str);
+    let mut current_line_length = indent + 3;
+    for word in s.split_whitespace().map(escape_doc) {
         let len = word.len();
         if current_line_length + len < max_len {
             splitted = format!("{} {}", splitted, word);
             current_line_length += 1 + len;
         } else {
-            splitted = format!("{}\n{}// {}", splitted, indent_str, word);
-            current_line_length = indent + 3 + len;
+            splitted = format!("{}\n{}/// {}", splitted, indent_str, word);
+            current_line_length = indent + 4 + len;
         }
     }
     format!("{}\n", splitted)
 }
 
-pub fn match_built_in_type(type_name: &str, xsd_ns: &Option<Namespace>) -> Option<&'static str> {
+// The namespace uri bound to the prefix of the type name, if the prefix is declared
+pub fn namespace_uri<'a>(type_name: &str, namespaces: &'a [Namespace]) -> Option<&'a str> {
+    let (prefix, _) = split_name(type_name);
+    namespaces.iter().find(|ns| ns.name() == prefix).map(|ns| ns.uri())
+}
+
+pub fn match_built_in_type(
+    type_name: &str,
+    xsd_ns: &Option<Namespace>,
+    namespaces: &[Namespace],
+) -> Option<&'static str> {
     let (prefix, name) = split_name(type_name);
-    let xsd_prefix = xsd_ns.as_ref().and_then(|ns| ns.name());
-    if xsd_prefix != prefix {
+    let is_xsd = match namespace_uri(type_name, namespaces) {
+        Some(uri) => uri == namespace::XSD,
+        None => xsd_ns.as_ref().and_then(|ns| ns.name()) == prefix,
+    };
+    if !is_xsd {
         return None;
     }
     let res = match name {
@@ -95,6 +111,25 @@ pub fn match_built_in_type(type_name: &str, xsd_ns: &Option<Namespace>) -> Optio
     }
 }
 
+// Rust types which built-in XSD types are mapped to, except types from xsd-types
+pub fn is_primitive_type(type_name: &str) -> bool {
+    matches!(
+        type_name,
+        "String"
+            | "bool"
+            | "i8"
+            | "i16"
+            | "i32"
+            | "i64"
+            | "u8"
+            | "u16"
+            | "u32"
+            | "u64"
+            | "f32"
+            | "f64"
+    )
+}
+
 pub fn sanitize(s: String) -> String {
     if s.is_empty() {
         s
@@ -120,6 +155,29 @@ pub fn split_name(name: &str) -> (Option<&str>, &str) {
     }
 }
 
+// Name of an element as it is written, e.g. tns:Item, and its namespace. Local elements
+// are in the target namespace if they are qualified, references keep their prefix.
+pub fn qualified_element_name(
+    name: &str,
+    form: FormType,
+    target_ns: &Option<Namespace>,
+    namespaces: &[Namespace],
+) -> (String, Option<String>) {
+    match (split_name(name), target_ns) {
+        ((Some(prefix), local_name), _) => {
+            match namespaces.iter().chain(target_ns).find(|ns| ns.name() == Some(prefix)) {
+                Some(ns) => (name.to_string(), Some(ns.uri().to_string())),
+                None => (local_name.to_string(), None),
+            }
+        }
+        ((None, local_name), Some(tns)) if form == FormType::Qualified => match tns.name() {
+            Some(prefix) => (format!("{}:{}", prefix, local_name), Some(tns.uri().to_string())),
+            None => (local_name.to_string(), Some(tns.uri().to_string())),
+        },
+        ((None, local_name), _) => (local_name.to_string(), None),
+    }
+}
+
 const RS_KEYWORDS: &[&str] = &[
     "abstract",
     "alignof",
@@ -180,9 +238,20 @@ const RS_KEYWORDS: &[&str] = &[
     "yield",
 ];
 
+// Indents the generated code of a module
+pub fn shift(text: &str, indent: &str) -> String {
+    text.replace("\n\n\n", "\n") // TODO: fix this workaround replace
+        .split('\n')
+        .map(|s| if !s.is_empty() { format!("\n{}{}", indent, s) } else { "\n".to_string() })
+        .fold(indent.to_string(), |acc, x| acc + &x)
+}
+
 #[cfg(test)]
 mod test {
-    use crate::generator::utils::{filter_type_name, match_built_in_type, split_name};
+    use crate::{
+        generator::utils::{filter_type_name, match_built_in_type, split_name},
+        parser::types::Namespace,
+    };
 
     #[test]
     fn test_filter_type_name() {
@@ -219,13 +288,31 @@ mod test {
             .root_element()
             .namespaces()
             .next()
-            .cloned()
+            .map(Namespace::from)
             .unwrap(),
         );
 
-        let match_type = |name| match_built_in_type(name, &xsd_ns);
+        let match_type = |name| match_built_in_type(name, &xsd_ns, &[]);
 
         assert_eq!(match_type("xsd:string"), Some("String"));
         assert!(match_type("xs:string").is_none());
     }
+
+    #[test]
+    fn test_match_built_in_types_by_namespace_uri() {
+        let doc = roxmltree::Document::parse(
+            r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"
+                          xmlns:xsd="http://www.w3.org/2001/XMLSchema"
+                          xmlns:tns="http://example.com"/>"#,
+        )
+        .unwrap();
+        let namespaces = doc.root_element().namespaces().map(Namespace::from).collect::<Vec<_>>();
+        let xsd_ns = namespaces.first().cloned();
+
+        let match_type = |name| match_built_in_type(name, &xsd_ns, &namespaces);
+
+        assert_eq!(match_type("xs:int"), Some("i32"));
+        assert_eq!(match_type("xsd:int"), Some("i32"));
+        assert!(match_type("tns:int").is_none());
+    }
 }
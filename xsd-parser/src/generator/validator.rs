@@ -1,12 +1,14 @@
 use std::borrow::Cow;
 
-use crate::parser::xsd_elements::FacetType;
+use xsd_types::types as xs;
 
-pub trait Validate {
-    fn validate(&self) -> Result<(), String> {
-        Ok(())
-    }
-}
+use crate::parser::{
+    types::{Occurs, QName, Wildcard},
+    xsd_elements::{FacetType, WhiteSpace},
+};
+
+// The trait implemented by the generated code is part of the runtime types
+pub use xsd_types::types::{FacetKind, Validate, ValidationError, ValidationErrors};
 
 pub trait ValidateGenerator {}
 
@@ -16,8 +18,7 @@ pub fn gen_validate_impl(name: &str, body: &str) -> String {
     } else {
         format!(
             r#"impl Validate for {name} {{
-    fn validate(&self) -> Result<(), String> {{ {body}
-        Ok(())
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {{{body}
     }}
 }}
 "#,
@@ -27,113 +28,487 @@ pub fn gen_validate_impl(name: &str, body: &str) -> String {
     }
 }
 
+// Runs the checks of a value, `path` is an expression of the path of its element
+pub fn gen_checks(path: &str, checks: &str) -> String {
+    if checks.is_empty() {
+        return "".into();
+    }
+    format!(
+        r#"
+        errors.check({path}, || {{{checks}
+            Ok(())
+        }});"#,
+        path = path,
+        checks = checks.replace('\n', "\n    ")
+    )
+}
+
+// Adds the errors of a value of a generated type
+pub fn gen_nested_validation(value: &str, path: &str) -> String {
+    format!(
+        r#"
+        {value}.validate_at({path}, errors);"#,
+        value = value,
+        path = path
+    )
+}
+
+// Expression of the path of a child element or attribute
+pub fn gen_child_path(name: &str, is_attribute: bool) -> String {
+    format!(
+        r#"&format!("{{}}/{at}{name}", path)"#,
+        at = if is_attribute { "@" } else { "" },
+        name = name.rsplit(':').next().unwrap_or(name)
+    )
+}
+
 pub fn gen_facet_validation(facet: &FacetType, name: &str, typename: &str) -> Cow<'static, str> {
+    gen_facets_validation(std::slice::from_ref(facet), name, typename).into()
+}
+
+// Checks of all the facets of a value. The patterns (and enumerations) of a type are
+// alternatives, so they are checked together.
+pub fn gen_facets_validation(facets: &[FacetType], name: &str, typename: &str) -> String {
+    let field = format!("self.{}", name);
+    gen_value_facets_validation(facets, &field, &lexical_form(&field, typename), name, typename)
+}
+
+// The text value of a simple content is stored as a string (yaserde can't serialize
+// text content of other types), so it is parsed before checking non-string facets.
+pub fn gen_text_facet_validation(facets: &[FacetType], name: &str, typename: &str) -> String {
+    if typename == "String" {
+        return gen_facets_validation(facets, name, typename);
+    }
+
+    // Patterns and digits are checked against the text as it is
+    let (lexical, typed): (Vec<FacetType>, Vec<FacetType>) =
+        facets.iter().cloned().partition(|f| {
+            matches!(
+                f,
+                FacetType::Pattern(_)
+                    | FacetType::TotalDigits(_)
+                    | FacetType::FractionDigits(_)
+                    | FacetType::WhiteSpace(_)
+            )
+        });
+    let text = format!("self.{}", name);
+    let lexical = gen_value_facets_validation(&lexical, &text, &text, name, typename);
+    let typed =
+        gen_value_facets_validation(&typed, name, &lexical_form(name, typename), name, typename);
+    if lexical.is_empty() && typed.is_empty() {
+        return "".into();
+    }
+
+    let parse = format!(
+        r#"self.{name}.parse::<{typename}>().map_err(|_| xs::ValidationError::new(xs::FacetKind::Type, "{name}", "is a valid {typename}", format!("{{:?}}", self.{name})))?;"#,
+        name = name,
+        typename = typename
+    );
+    if typed.is_empty() {
+        format!("\n        {}{}", parse, lexical)
+    } else {
+        format!("\n        let {} = {}{}{}", name, parse, lexical, typed)
+    }
+}
+
+// Expression for the string representation of a value, which patterns, enumerations
+// and digits are checked against
+fn lexical_form(field: &str, typename: &str) -> String {
+    if typename == "String" {
+        field.to_string()
+    } else if typename.starts_with("Vec<") {
+        format!(r#"{}.iter().map(|item| item.to_string()).collect::<Vec<_>>().join(" ")"#, field)
+    } else {
+        format!("{}.to_string()", field)
+    }
+}
+
+fn gen_value_facets_validation(
+    facets: &[FacetType],
+    field: &str,
+    lexical: &str,
+    name: &str,
+    typename: &str,
+) -> String {
+    let original = field;
+    let patterns = facets
+        .iter()
+        .filter_map(|facet| match facet {
+            FacetType::Pattern(pattern) => Some(pattern.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let values = facets
+        .iter()
+        .filter_map(|facet| match facet {
+            FacetType::Enumeration(value) => Some(value.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    // whiteSpace doesn't make any value invalid, the other facets are checked against
+    // the value normalized as it requires
+    let normalize = facets.iter().find_map(|facet| match facet {
+        FacetType::WhiteSpace(WhiteSpace::Replace) => Some("replace_white_space"),
+        FacetType::WhiteSpace(WhiteSpace::Collapse) => Some("collapse_white_space"),
+        _ => None,
+    });
+    let (field, lexical) = match normalize {
+        Some(_) if typename == "String" => ("normalized", "normalized"),
+        _ => (field, lexical),
+    };
+
+    let checks = facets
+        .iter()
+        .map(|facet| gen_value_facet_validation(facet, field, lexical, name, typename))
+        .collect::<String>();
+    let patterns = if patterns.is_empty() {
+        "".into()
+    } else {
+        gen_pattern_validation(&patterns, field, lexical, name)
+    };
+    let values = if values.is_empty() {
+        "".into()
+    } else if typename.starts_with("Vec<") {
+        // Lists are compared by their items separated by spaces
+        gen_enumeration_validation(&values, lexical, name, "String")
+    } else {
+        gen_enumeration_validation(&values, field, name, typename)
+    };
+    let checks = checks + &patterns + &values;
+    match normalize {
+        Some(normalize) if typename == "String" && !checks.is_empty() => format!(
+            r#"
+        let normalized = xs::{normalize}(&{value});{checks}"#,
+            normalize = normalize,
+            value = original,
+            checks = checks
+        ),
+        _ => checks,
+    }
+}
+
+fn gen_value_facet_validation(
+    facet: &FacetType,
+    field: &str,
+    lexical: &str,
+    name: &str,
+    typename: &str,
+) -> String {
     match facet {
-        FacetType::Enumeration(_) => "".into(),
-        FacetType::FractionDigits(_) => "".into(),
-        FacetType::Length(value) => gen_length_validation(value.as_str(), name).into(),
+        FacetType::Enumeration(_) | FacetType::Pattern(_) => "".into(),
+        // Digits of lists aren't restricted
+        FacetType::FractionDigits(_) | FacetType::TotalDigits(_)
+            if typename.starts_with("Vec<") =>
+        {
+            "".into()
+        }
+        FacetType::FractionDigits(value) => {
+            gen_fraction_digits_validation(value.as_str(), field, lexical, name)
+        }
+        FacetType::Length(value) => gen_length_validation(value.as_str(), field, name),
         FacetType::MaxExclusive(value) => {
-            gen_max_exclusive_validation(value.as_str(), name, typename).into()
+            gen_max_exclusive_validation(value.as_str(), field, name, typename)
         }
         FacetType::MaxInclusive(value) => {
-            gen_max_inclusive_validation(value.as_str(), name, typename).into()
+            gen_max_inclusive_validation(value.as_str(), field, name, typename)
         }
-        FacetType::MaxLength(value) => gen_max_length_validation(value.as_str(), name).into(),
+        FacetType::MaxLength(value) => gen_max_length_validation(value.as_str(), field, name),
         FacetType::MinExclusive(value) => {
-            gen_min_exclusive_validation(value.as_str(), name, typename).into()
+            gen_min_exclusive_validation(value.as_str(), field, name, typename)
         }
         FacetType::MinInclusive(value) => {
-            gen_min_inclusive_validation(value.as_str(), name, typename).into()
+            gen_min_inclusive_validation(value.as_str(), field, name, typename)
         }
-        FacetType::MinLength(value) => gen_min_length_validation(value.as_str(), name).into(),
-        _ => "".into(), // TODO: All Facet Types
+        FacetType::MinLength(value) => gen_min_length_validation(value.as_str(), field, name),
+        FacetType::TotalDigits(value) => {
+            gen_total_digits_validation(value.as_str(), field, lexical, name)
+        }
+        FacetType::WhiteSpace(_) => "".into(),
     }
 }
 
-fn gen_max_exclusive_validation(value: &str, name: &str, typename: &str) -> String {
+pub fn gen_fixed_value_validation(name: &str, value_fn: &str, optional: bool) -> String {
+    // An absent optional value is replaced with the fixed one
+    let condition = if optional {
+        format!(
+            "self.{name}.is_some() && self.{name} != {value_fn}()",
+            name = name,
+            value_fn = value_fn
+        )
+    } else {
+        format!("self.{name} != {value_fn}()", name = name, value_fn = value_fn)
+    };
     format!(
         r#"
-        if self.{name} >= "{value}".parse::<{typename}>().unwrap() {{
-            return Err(format!("MaxExclusive validation error: invalid value of {name}! \nExpected: {name} < {value}.\nActual: {name} == {{}}", self.{name}));
+        if {condition} {{
+            return Err(xs::ValidationError::new(xs::FacetKind::Fixed, "{name}", format!("== {{:?}}", {value_fn}()), format!("{{:?}}", self.{name})));
         }}"#,
+        condition = condition,
+        name = name,
+        value_fn = value_fn
+    )
+}
+
+// Number of elements of a repeated field
+pub fn gen_occurs_validation(occurs: &Occurs, name: &str) -> String {
+    let min = if occurs.min > 0 {
+        format!(
+            r#"
+        #[allow(clippy::len_zero)]
+        if self.{name}.len() < {min} {{
+            return Err(xs::ValidationError::new(xs::FacetKind::MinOccurs, "{name}", ">= {min}", self.{name}.len()));
+        }}"#,
+            name = name,
+            min = occurs.min
+        )
+    } else {
+        "".into()
+    };
+    let max = match occurs.max {
+        Some(max) => format!(
+            r#"
+        if self.{name}.len() > {max} {{
+            return Err(xs::ValidationError::new(xs::FacetKind::MaxOccurs, "{name}", "<= {max}", self.{name}.len()));
+        }}"#,
+            name = name,
+            max = max
+        ),
+        None => "".into(),
+    };
+    min + &max
+}
+
+// Order and number of the child elements of mixed content with a sequence of elements
+pub fn gen_mixed_sequence_validation(sequence: &[(String, Occurs)], name: &str) -> String {
+    let steps = sequence
+        .iter()
+        .map(|(element, occurs)| {
+            format!(
+                "xs::MixedStep {{ name: {:?}, min: {}, max: {:?} }}",
+                element, occurs.min, occurs.max
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        r#"
+        self.{name}.validate_sequence(&[{steps}])?;"#,
+        name = name,
+        steps = steps
+    )
+}
+
+// Namespaces of the elements or attributes matched by a wildcard, and the number of elements
+// The elements matched by a strict wildcard must be one of the declared ones
+pub fn gen_wildcard_validation(
+    wildcard: &Wildcard,
+    occurs: Option<&Occurs>,
+    declared: Option<&[QName]>,
+    name: &str,
+    target_namespace: Option<&str>,
+) -> String {
+    let namespace = if wildcard.namespace.trim() == "##any" {
+        "".into()
+    } else {
+        format!(
+            r#"
+        self.{name}.validate_namespace({constraint:?}, {target_namespace:?})?;"#,
+            name = name,
+            constraint = wildcard.namespace,
+            target_namespace = target_namespace
+        )
+    };
+    let declared = match declared {
+        Some(declared) => {
+            let elements = declared
+                .iter()
+                .filter(|qname| {
+                    xs::namespace_allowed(
+                        &wildcard.namespace,
+                        qname.namespace.as_deref(),
+                        target_namespace,
+                    )
+                })
+                .map(|qname| format!("({:?}, {:?})", qname.namespace.as_deref(), qname.name))
+                .collect::<Vec<_>>();
+            format!(
+                r#"
+        self.{name}.validate_declared(&[{elements}])?;"#,
+                name = name,
+                elements = elements.join(", ")
+            )
+        }
+        None => "".into(),
+    };
+    occurs.map(|occurs| gen_occurs_validation(occurs, name)).unwrap_or_default()
+        + &namespace
+        + &declared
+}
+
+fn gen_max_exclusive_validation(value: &str, field: &str, name: &str, typename: &str) -> String {
+    format!(
+        r#"
+        if {field} >= "{value}".parse::<{typename}>().unwrap() {{
+            return Err(xs::ValidationError::new(xs::FacetKind::MaxExclusive, "{name}", "< {value}", {field}.to_string()));
+        }}"#,
+        field = field,
         name = name,
         value = value,
         typename = typename
     )
 }
 
-fn gen_max_inclusive_validation(value: &str, name: &str, typename: &str) -> String {
+fn gen_max_inclusive_validation(value: &str, field: &str, name: &str, typename: &str) -> String {
     format!(
         r#"
-        if self.{name} > "{value}".parse::<{typename}>().unwrap() {{
-            return Err(format!("MaxInclusive validation error: invalid value of {name}! \nExpected: {name} <= {value}.\nActual: {name} == {{}}", self.{name}));
+        if {field} > "{value}".parse::<{typename}>().unwrap() {{
+            return Err(xs::ValidationError::new(xs::FacetKind::MaxInclusive, "{name}", "<= {value}", {field}.to_string()));
         }}"#,
+        field = field,
         name = name,
         value = value,
         typename = typename
     )
 }
 
-fn gen_length_validation(value: &str, name: &str) -> String {
-    let value: u32 = value.parse().unwrap();
+fn gen_length_validation(value: &str, field: &str, name: &str) -> String {
+    // The value is a number, checked when the facets are parsed
+    let value = value.trim();
     format!(
         r#"
-        if self.{name}.len() != {value} {{
-            return Err(format!("Length validation error. \nExpected: {name} length == {value} \nActual: {name} length == {{}}", self.{name}.len()));
+        if {field}.len() != {value} {{
+            return Err(xs::ValidationError::new(xs::FacetKind::Length, "{name}", "== {value}", {field}.len()));
         }}"#,
+        field = field,
         name = name,
         value = value
     )
 }
 
-fn gen_max_length_validation(value: &str, name: &str) -> String {
-    let value: u32 = value.parse().unwrap();
+fn gen_max_length_validation(value: &str, field: &str, name: &str) -> String {
+    // The value is a number, checked when the facets are parsed
+    let value = value.trim();
     format!(
         r#"
-        if self.{name}.len() > {value} {{
-            return Err(format!("MaxLength validation error. \nExpected: {name} length <= {value} \nActual: {name} length == {{}}", self.{name}.len()));
+        if {field}.len() > {value} {{
+            return Err(xs::ValidationError::new(xs::FacetKind::MaxLength, "{name}", "<= {value}", {field}.len()));
         }}"#,
+        field = field,
         name = name,
         value = value
     )
 }
 
-fn gen_min_exclusive_validation(value: &str, name: &str, typename: &str) -> String {
+fn gen_min_exclusive_validation(value: &str, field: &str, name: &str, typename: &str) -> String {
     format!(
         r#"
-        if self.{name} <= "{value}".parse::<{typename}>().unwrap() {{
-            return Err(format!("MinExclusive validation error: invalid value of {name}! \nExpected: {name} > {value}.\nActual: {name} == {{}}", self.{name}));
+        if {field} <= "{value}".parse::<{typename}>().unwrap() {{
+            return Err(xs::ValidationError::new(xs::FacetKind::MinExclusive, "{name}", "> {value}", {field}.to_string()));
         }}"#,
+        field = field,
         name = name,
         value = value,
         typename = typename
     )
 }
 
-fn gen_min_inclusive_validation(value: &str, name: &str, typename: &str) -> String {
+fn gen_min_inclusive_validation(value: &str, field: &str, name: &str, typename: &str) -> String {
     format!(
         r#"
-        if self.{name} < "{value}".parse::<{typename}>().unwrap() {{
-            return Err(format!("MinInclusive validation error: invalid value of {name}! \nExpected: {name} >= {value}.\nActual: {name} == {{}}", self.{name}));
+        if {field} < "{value}".parse::<{typename}>().unwrap() {{
+            return Err(xs::ValidationError::new(xs::FacetKind::MinInclusive, "{name}", ">= {value}", {field}.to_string()));
         }}"#,
+        field = field,
         name = name,
         value = value,
         typename = typename
     )
 }
 
-fn gen_min_length_validation(value: &str, name: &str) -> String {
-    let value: u32 = value.parse().unwrap();
-    if value == 0 {
+fn gen_min_length_validation(value: &str, field: &str, name: &str) -> String {
+    // The value is a number, checked when the facets are parsed
+    let value = value.trim();
+    if value.parse::<u32>() == Ok(0) {
         return "".into();
     }
 
     format!(
         r#"
         #[allow(clippy::len_zero)]
-        if self.{name}.len() < {value} {{
-            return Err(format!("MinLength validation error. \nExpected: {name} length >= {value} \nActual: {name} length == {{}}", self.{name}.len()));
+        if {field}.len() < {value} {{
+            return Err(xs::ValidationError::new(xs::FacetKind::MinLength, "{name}", ">= {value}", {field}.len()));
+        }}"#,
+        field = field,
+        name = name,
+        value = value
+    )
+}
+
+fn gen_pattern_validation(patterns: &[&str], field: &str, lexical: &str, name: &str) -> String {
+    let patterns = patterns.iter().map(|p| format!("{:?}", p)).collect::<Vec<_>>().join(", ");
+    format!(
+        r#"
+        if !xs::matches_pattern(&{lexical}, &[{patterns}])? {{
+            return Err(xs::ValidationError::new(xs::FacetKind::Pattern, "{name}", format!("matches one of {{:?}}", [{patterns}]), format!("{{:?}}", {field})));
+        }}"#,
+        field = field,
+        lexical = lexical,
+        patterns = patterns,
+        name = name
+    )
+}
+
+// The value is compared with the enumerated values parsed as its type
+fn gen_enumeration_validation(values: &[&str], field: &str, name: &str, typename: &str) -> String {
+    let values = values.iter().map(|v| format!("{:?}", v)).collect::<Vec<_>>().join(", ");
+    let condition = if typename == "String" {
+        format!("![{values}].contains(&{field}.as_str())", values = values, field = field)
+    } else {
+        format!(
+            "![{values}].iter().any(|value| value.parse::<{typename}>().is_ok_and(|value| value == {field}))",
+            values = values,
+            typename = typename,
+            field = field
+        )
+    };
+    format!(
+        r#"
+        if {condition} {{
+            return Err(xs::ValidationError::new(xs::FacetKind::Enumeration, "{name}", format!("is one of {{:?}}", [{values}]), format!("{{:?}}", {field})));
+        }}"#,
+        condition = condition,
+        name = name,
+        values = values,
+        field = field
+    )
+}
+
+fn gen_total_digits_validation(value: &str, field: &str, lexical: &str, name: &str) -> String {
+    // The value is a number, checked when the facets are parsed
+    let value = value.trim();
+    format!(
+        r#"
+        if xs::total_digits(&{lexical}).unwrap_or(usize::MAX) > {value} {{
+            return Err(xs::ValidationError::new(xs::FacetKind::TotalDigits, "{name}", "has at most {value} digits", {field}.to_string()));
+        }}"#,
+        field = field,
+        lexical = lexical,
+        name = name,
+        value = value
+    )
+}
+
+fn gen_fraction_digits_validation(value: &str, field: &str, lexical: &str, name: &str) -> String {
+    // The value is a number, checked when the facets are parsed
+    let value = value.trim();
+    format!(
+        r#"
+        if xs::fraction_digits(&{lexical}).unwrap_or(usize::MAX) > {value} {{
+            return Err(xs::ValidationError::new(xs::FacetKind::FractionDigits, "{name}", "has at most {value} fraction digits", {field}.to_string()));
         }}"#,
+        field = field,
+        lexical = lexical,
         name = name,
         value = value
     )
@@ -147,54 +522,68 @@ mod test {
     fn test_gen_max_exclusive_validation() {
         let expected = r#"
         if self.count >= "5".parse::<i32>().unwrap() {
-            return Err(format!("MaxExclusive validation error: invalid value of count! \nExpected: count < 5.\nActual: count == {}", self.count));
+            return Err(xs::ValidationError::new(xs::FacetKind::MaxExclusive, "count", "< 5", self.count.to_string()));
         }"#;
-        assert_eq!(gen_max_exclusive_validation("5", "count", "i32"), expected);
+        assert_eq!(gen_max_exclusive_validation("5", "self.count", "count", "i32"), expected);
     }
 
     #[test]
     fn test_gen_max_inclusive_validation() {
         let expected = r#"
         if self.count > "5".parse::<i32>().unwrap() {
-            return Err(format!("MaxInclusive validation error: invalid value of count! \nExpected: count <= 5.\nActual: count == {}", self.count));
+            return Err(xs::ValidationError::new(xs::FacetKind::MaxInclusive, "count", "<= 5", self.count.to_string()));
+        }"#;
+        assert_eq!(gen_max_inclusive_validation("5", "self.count", "count", "i32"), expected);
+    }
+
+    #[test]
+    fn test_gen_occurs_validation() {
+        let expected = r#"
+        #[allow(clippy::len_zero)]
+        if self.items.len() < 2 {
+            return Err(xs::ValidationError::new(xs::FacetKind::MinOccurs, "items", ">= 2", self.items.len()));
+        }
+        if self.items.len() > 5 {
+            return Err(xs::ValidationError::new(xs::FacetKind::MaxOccurs, "items", "<= 5", self.items.len()));
         }"#;
-        assert_eq!(gen_max_inclusive_validation("5", "count", "i32"), expected);
+        assert_eq!(gen_occurs_validation(&Occurs { min: 2, max: Some(5) }, "items"), expected);
+        assert_eq!(gen_occurs_validation(&Occurs { min: 0, max: None }, "items"), "");
     }
 
     #[test]
     fn test_gen_length_validation() {
         let expected = r#"
         if self.name.len() != 50 {
-            return Err(format!("Length validation error. \nExpected: name length == 50 \nActual: name length == {}", self.name.len()));
+            return Err(xs::ValidationError::new(xs::FacetKind::Length, "name", "== 50", self.name.len()));
         }"#;
-        assert_eq!(gen_length_validation("50", "name"), expected);
+        assert_eq!(gen_length_validation("50", "self.name", "name"), expected);
     }
 
     #[test]
     fn test_gen_max_length_validation() {
         let expected = r#"
         if self.name.len() > 50 {
-            return Err(format!("MaxLength validation error. \nExpected: name length <= 50 \nActual: name length == {}", self.name.len()));
+            return Err(xs::ValidationError::new(xs::FacetKind::MaxLength, "name", "<= 50", self.name.len()));
         }"#;
-        assert_eq!(gen_max_length_validation("50", "name",), expected);
+        assert_eq!(gen_max_length_validation("50", "self.name", "name",), expected);
     }
 
     #[test]
     fn test_gen_min_exclusive_validation() {
         let expected = r#"
         if self.count <= "5".parse::<i32>().unwrap() {
-            return Err(format!("MinExclusive validation error: invalid value of count! \nExpected: count > 5.\nActual: count == {}", self.count));
+            return Err(xs::ValidationError::new(xs::FacetKind::MinExclusive, "count", "> 5", self.count.to_string()));
         }"#;
-        assert_eq!(gen_min_exclusive_validation("5", "count", "i32"), expected);
+        assert_eq!(gen_min_exclusive_validation("5", "self.count", "count", "i32"), expected);
     }
 
     #[test]
     fn test_gen_min_inclusive_validation() {
         let expected = r#"
         if self.count < "5".parse::<i32>().unwrap() {
-            return Err(format!("MinInclusive validation error: invalid value of count! \nExpected: count >= 5.\nActual: count == {}", self.count));
+            return Err(xs::ValidationError::new(xs::FacetKind::MinInclusive, "count", ">= 5", self.count.to_string()));
         }"#;
-        assert_eq!(gen_min_inclusive_validation("5", "count", "i32"), expected);
+        assert_eq!(gen_min_inclusive_validation("5", "self.count", "count", "i32"), expected);
     }
 
     #[test]
@@ -202,14 +591,76 @@ mod test {
         let expected = r#"
         #[allow(clippy::len_zero)]
         if self.name.len() < 50 {
-            return Err(format!("MinLength validation error. \nExpected: name length >= 50 \nActual: name length == {}", self.name.len()));
+            return Err(xs::ValidationError::new(xs::FacetKind::MinLength, "name", ">= 50", self.name.len()));
         }"#;
-        assert_eq!(gen_min_length_validation("50", "name"), expected);
+        assert_eq!(gen_min_length_validation("50", "self.name", "name"), expected);
     }
 
     #[test]
     fn test_gen_min_length_zero_validation() {
         let expected = "";
-        assert_eq!(gen_min_length_validation("0", "name"), expected);
+        assert_eq!(gen_min_length_validation("0", "self.name", "name"), expected);
+    }
+
+    #[test]
+    fn test_gen_pattern_validation() {
+        let expected = r#"
+        if !xs::matches_pattern(&self.0, &["\\d+", "[a-z]+"])? {
+            return Err(xs::ValidationError::new(xs::FacetKind::Pattern, "0", format!("matches one of {:?}", ["\\d+", "[a-z]+"]), format!("{:?}", self.0)));
+        }"#;
+        let facets = [FacetType::Pattern(r"\d+".into()), FacetType::Pattern("[a-z]+".into())];
+        assert_eq!(gen_facets_validation(&facets, "0", "String"), expected);
+    }
+
+    #[test]
+    fn test_gen_enumeration_validation() {
+        let expected = r#"
+        if !["1.5", "2.5"].iter().any(|value| value.parse::<f64>().is_ok_and(|value| value == self.ratio)) {
+            return Err(xs::ValidationError::new(xs::FacetKind::Enumeration, "ratio", format!("is one of {:?}", ["1.5", "2.5"]), format!("{:?}", self.ratio)));
+        }"#;
+        let facets = [FacetType::Enumeration("1.5".into()), FacetType::Enumeration("2.5".into())];
+        assert_eq!(gen_facets_validation(&facets, "ratio", "f64"), expected);
+    }
+
+    #[test]
+    fn test_gen_digits_validation() {
+        let expected = r#"
+        if xs::total_digits(&self.price.to_string()).unwrap_or(usize::MAX) > 5 {
+            return Err(xs::ValidationError::new(xs::FacetKind::TotalDigits, "price", "has at most 5 digits", self.price.to_string()));
+        }
+        if xs::fraction_digits(&self.price.to_string()).unwrap_or(usize::MAX) > 2 {
+            return Err(xs::ValidationError::new(xs::FacetKind::FractionDigits, "price", "has at most 2 fraction digits", self.price.to_string()));
+        }"#;
+        let facets = [FacetType::TotalDigits("5".into()), FacetType::FractionDigits("2".into())];
+        assert_eq!(gen_facets_validation(&facets, "price", "xs::Decimal"), expected);
+    }
+
+    #[test]
+    fn test_gen_white_space_validation() {
+        let expected = r#"
+        let normalized = xs::collapse_white_space(&self.label);
+        if normalized.len() > 10 {
+            return Err(xs::ValidationError::new(xs::FacetKind::MaxLength, "label", "<= 10", normalized.len()));
+        }"#;
+        let facets =
+            [FacetType::WhiteSpace(WhiteSpace::Collapse), FacetType::MaxLength("10".into())];
+        assert_eq!(gen_facets_validation(&facets, "label", "String"), expected);
+        // whiteSpace alone doesn't check anything
+        assert_eq!(gen_facets_validation(&facets[..1], "label", "String"), "");
+    }
+
+    #[test]
+    fn test_gen_text_facet_validation() {
+        // Patterns are checked against the text, ranges against the parsed value
+        let expected = r#"
+        let value = self.value.parse::<i32>().map_err(|_| xs::ValidationError::new(xs::FacetKind::Type, "value", "is a valid i32", format!("{:?}", self.value)))?;
+        if !xs::matches_pattern(&self.value, &["\\d+"])? {
+            return Err(xs::ValidationError::new(xs::FacetKind::Pattern, "value", format!("matches one of {:?}", ["\\d+"]), format!("{:?}", self.value)));
+        }
+        if value > "100".parse::<i32>().unwrap() {
+            return Err(xs::ValidationError::new(xs::FacetKind::MaxInclusive, "value", "<= 100", value.to_string()));
+        }"#;
+        let facets = [FacetType::Pattern(r"\d+".into()), FacetType::MaxInclusive("100".into())];
+        assert_eq!(gen_text_facet_validation(&facets, "value", "i32"), expected);
     }
 }
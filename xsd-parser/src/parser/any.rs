@@ -1,17 +1,63 @@
 use roxmltree::Node;
 
 use crate::parser::{
-    types::{RsEntity, StructField, StructFieldSource, TypeModifier},
+    constants::attribute,
+    error::ParseResult,
+    types::{RsEntity, StructField, StructFieldSource, TypeModifier, Wildcard},
     utils::get_documentation,
+    xsd_elements::{occurs, process_contents},
 };
 
-pub fn parse_any(node: &Node) -> RsEntity {
-    RsEntity::StructField(StructField {
+// Matched elements are kept as raw XML. With processContents="strict" the validation
+// checks that they are declared by the generated schemas.
+pub fn parse_any(node: &Node) -> ParseResult<RsEntity> {
+    Ok(RsEntity::StructField(StructField {
         name: "any".to_string(),
-        type_name: "String".to_string(),
+        type_name: "xs:AnyElements".to_string(),
         comment: get_documentation(node),
-        source: StructFieldSource::Element,
-        type_modifiers: vec![TypeModifier::Empty],
+        source: StructFieldSource::Any,
+        type_modifiers: vec![TypeModifier::None],
+        occurs: occurs(node),
+        wildcard: Some(Wildcard {
+            namespace: node.attribute(attribute::NAMESPACE).unwrap_or("##any").to_string(),
+            process_contents: process_contents(node),
+        }),
         ..Default::default()
-    })
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parser::{
+        any::parse_any,
+        types::{Occurs, RsEntity, StructFieldSource, Wildcard},
+        utils::find_child,
+        xsd_elements::ProcessContents,
+    };
+
+    #[test]
+    fn test_parse_any() {
+        let doc = roxmltree::Document::parse(
+            r###"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+                <xs:any namespace="##other" processContents="lax" minOccurs="0" maxOccurs="unbounded"/>
+            </xs:schema>"###,
+        )
+        .unwrap();
+        let any = find_child(&doc.root_element(), "any").unwrap();
+
+        match parse_any(&any).unwrap() {
+            RsEntity::StructField(sf) => {
+                assert!(matches!(sf.source, StructFieldSource::Any));
+                assert_eq!(sf.occurs, Occurs { min: 0, max: None });
+                assert_eq!(
+                    sf.wildcard,
+                    Some(Wildcard {
+                        namespace: "##other".to_string(),
+                        process_contents: ProcessContents::Lax
+                    })
+                );
+            }
+            _ => unreachable!("Test failed!"),
+        }
+    }
 }
@@ -1,17 +1,24 @@
 use roxmltree::Node;
 
 use crate::parser::{
-    types::{RsEntity, StructField, StructFieldSource, TypeModifier},
+    constants::attribute,
+    error::ParseResult,
+    types::{RsEntity, StructField, StructFieldSource, TypeModifier, Wildcard},
     utils::get_documentation,
+    xsd_elements::process_contents,
 };
 
-pub fn parse_any_attribute(node: &Node) -> RsEntity {
-    RsEntity::StructField(StructField {
+pub fn parse_any_attribute(node: &Node) -> ParseResult<RsEntity> {
+    Ok(RsEntity::StructField(StructField {
         name: "any_attribute".to_string(),
-        type_name: "String".to_string(),
+        type_name: "xs:AnyAttributes".to_string(),
         comment: get_documentation(node),
-        source: StructFieldSource::Attribute,
-        type_modifiers: vec![TypeModifier::Empty],
+        source: StructFieldSource::AnyAttribute,
+        type_modifiers: vec![TypeModifier::None],
+        wildcard: Some(Wildcard {
+            namespace: node.attribute(attribute::NAMESPACE).unwrap_or("##any").to_string(),
+            process_contents: process_contents(node),
+        }),
         ..Default::default()
-    })
+    }))
 }
@@ -1,13 +1,15 @@
 use roxmltree::Node;
 
 use crate::parser::{
+    constants::attribute,
+    error::{ParseError, ParseResult},
     node_parser::parse_node,
     types::{Alias, RsEntity, Struct, StructField, StructFieldSource, TypeModifier},
     utils::get_documentation,
-    xsd_elements::{ElementType, UseType, XsdNode},
+    xsd_elements::{attribute_form, value_constraint, ElementType, UseType, XsdNode},
 };
 
-pub fn parse_attribute(node: &Node, parent: &Node) -> RsEntity {
+pub fn parse_attribute(node: &Node, parent: &Node) -> ParseResult<RsEntity> {
     if parent.xsd_type() == ElementType::Schema {
         return parse_global_attribute(node);
     }
@@ -15,57 +17,68 @@ pub fn parse_attribute(node: &Node, parent: &Node) -> RsEntity {
     let name = node
         .attr_name()
         .or_else(|| node.attr_ref())
-        .expect("All attributes have name or ref")
+        .ok_or_else(|| ParseError::invalid(node, "All attributes have name or ref"))?
         .to_string();
 
     let type_name = node.attr_type().or_else(|| node.attr_ref()).unwrap_or("String").to_string();
+    let type_qname = match node.attr_qname(attribute::TYPE)? {
+        Some(qname) => Some(qname),
+        None => node.attr_qname(attribute::REF)?,
+    };
 
-    let type_modifier = match node.attr_use() {
+    let type_modifier = match node.attr_use()? {
         UseType::Optional => TypeModifier::Option,
         UseType::Prohibited => TypeModifier::Empty,
         UseType::Required => TypeModifier::None,
     };
 
-    RsEntity::StructField(StructField {
+    Ok(RsEntity::StructField(StructField {
         type_name,
         comment: get_documentation(node),
         subtypes: vec![],
         name,
         source: StructFieldSource::Attribute,
         type_modifiers: vec![type_modifier],
-    })
+        type_qname,
+        form: attribute_form(node),
+        value_constraint: value_constraint(node),
+        ..Default::default()
+    }))
 }
 
-fn parse_global_attribute(node: &Node) -> RsEntity {
+fn parse_global_attribute(node: &Node) -> ParseResult<RsEntity> {
     if let Some(reference) = node.attr_ref() {
-        return RsEntity::Alias(Alias {
+        return Ok(RsEntity::Alias(Alias {
             name: reference.to_string(),
             original: reference.to_string(),
+            original_qname: Some(node.resolve_qname(reference)?),
             comment: get_documentation(node),
             ..Default::default()
-        });
+        }));
     }
 
-    let name = node.attr_name().unwrap_or_else(|| panic!("Name attribute required. {:?}", node));
+    let name =
+        node.attr_name().ok_or_else(|| ParseError::invalid(node, "Name attribute required"))?;
 
     if let Some(ty) = node.attr_type() {
-        return RsEntity::Alias(Alias {
+        return Ok(RsEntity::Alias(Alias {
             name: name.to_string(),
             original: ty.to_string(),
+            original_qname: Some(node.resolve_qname(ty)?),
             comment: get_documentation(node),
             ..Default::default()
-        });
+        }));
     }
 
     if let Some(content) =
-        node.children().filter(|n| n.is_element() && n.xsd_type() == ElementType::SimpleType).last()
+        node.children().rfind(|n| n.is_element() && n.xsd_type() == ElementType::SimpleType)
     {
-        let mut entity = parse_node(&content, node);
+        let mut entity = parse_node(&content, node)?;
         entity.set_name(name);
-        return entity;
+        return Ok(entity);
     }
 
-    RsEntity::Struct(Struct { name: name.to_string(), ..Default::default() })
+    Ok(RsEntity::Struct(Struct { name: name.to_string(), ..Default::default() }))
 }
 
 #[cfg(test)]
@@ -94,7 +107,7 @@ mod test {
 
         let schema = doc.root_element();
         let attribute = find_child(&schema, "attribute").unwrap();
-        match parse_global_attribute(&attribute) {
+        match parse_global_attribute(&attribute).unwrap() {
             RsEntity::TupleStruct(ts) => {
                 assert_eq!(ts.name, "contentType");
                 assert_eq!(ts.type_name, "xs:string");
@@ -119,7 +132,7 @@ mod test {
 
         let schema = doc.root_element();
         let attribute = find_child(&schema, "attribute").unwrap();
-        match parse_global_attribute(&attribute) {
+        match parse_global_attribute(&attribute).unwrap() {
             RsEntity::Alias(ts) => {
                 assert_eq!(ts.name, "expectedContentTypes");
                 assert_eq!(ts.original, "xs:string");
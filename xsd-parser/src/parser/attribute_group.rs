@@ -1,48 +1,49 @@
 use roxmltree::Node;
 
 use crate::parser::{
+    error::{ParseError, ParseResult},
     node_parser::parse_node,
     types::{Alias, RsEntity, Struct, StructField},
     utils::get_documentation,
     xsd_elements::{ElementType, XsdNode},
 };
 
-pub fn parse_attribute_group(node: &Node, parent: &Node) -> RsEntity {
+pub fn parse_attribute_group(node: &Node, parent: &Node) -> ParseResult<RsEntity> {
     if parent.xsd_type() == ElementType::Schema {
         return parse_global_attribute_group(node);
     }
 
-    let reference =
-        node.attr_ref().expect("Non-global attributeGroups must be references.").to_string();
+    let reference = node
+        .attr_ref()
+        .ok_or_else(|| ParseError::invalid(node, "Non-global attributeGroups must be references."))?
+        .to_string();
 
-    RsEntity::Alias(Alias {
+    Ok(RsEntity::Alias(Alias {
         name: reference.to_string(),
+        original_qname: Some(node.resolve_qname(&reference)?),
         original: reference,
         comment: get_documentation(node),
         ..Default::default()
-    })
+    }))
 }
 
-fn parse_global_attribute_group(node: &Node) -> RsEntity {
-    let name = node.attr_name().unwrap_or_else(|| panic!("Name attribute required. {:?}", node));
+fn parse_global_attribute_group(node: &Node) -> ParseResult<RsEntity> {
+    let name =
+        node.attr_name().ok_or_else(|| ParseError::invalid(node, "Name attribute required"))?;
 
-    let fields = attributes_to_fields(node);
+    let fields = attributes_to_fields(node)?;
 
-    RsEntity::Struct(Struct {
-        name: name.to_string(),
-        fields: std::cell::RefCell::new(fields),
-        ..Default::default()
-    })
+    Ok(RsEntity::Struct(Struct { name: name.to_string(), fields, ..Default::default() }))
 }
 
-pub fn attributes_to_fields(node: &Node) -> Vec<StructField> {
+pub fn attributes_to_fields(node: &Node) -> ParseResult<Vec<StructField>> {
     node.children()
         .filter(|n| {
             n.xsd_type() == ElementType::Attribute || n.xsd_type() == ElementType::AnyAttribute
         })
-        .map(|n| match parse_node(&n, node) {
-            RsEntity::StructField(sf) => sf,
-            _ => unreachable!("Invalid attribute parsing: {:?}", n),
+        .map(|n| match parse_node(&n, node)? {
+            RsEntity::StructField(sf) => Ok(sf),
+            _ => Err(ParseError::invalid(&n, "Invalid attribute parsing")),
         })
         .collect()
 }
@@ -78,10 +79,10 @@ mod test {
 
         let schema = doc.root_element();
         let attribute = find_child(&schema, "attributeGroup").unwrap();
-        match parse_global_attribute_group(&attribute) {
+        match parse_global_attribute_group(&attribute).unwrap() {
             RsEntity::Struct(ts) => {
                 assert_eq!(ts.name, "contentGroup");
-                assert_eq!(ts.fields.borrow().len(), 2);
+                assert_eq!(ts.fields.len(), 2);
             }
             _ => unreachable!("Test Failed!"),
         }
@@ -1,25 +1,152 @@
 use roxmltree::Node;
 
 use crate::parser::{
+    element::element_modifier,
+    error::{ParseError, ParseResult},
     node_parser::parse_node,
-    types::{Enum, EnumSource, RsEntity},
+    types::{ChoiceBranch, Enum, EnumCase, EnumSource, RsEntity, StructFieldSource, TypeModifier},
+    utils::{choice_modifiers, get_documentation},
     xsd_elements::{ElementType, XsdNode},
 };
 
-pub fn parse_choice(choice: &Node) -> RsEntity {
+pub fn parse_choice(choice: &Node) -> ParseResult<RsEntity> {
+    let mut subtypes = vec![];
     let enum_cases = choice
         .children()
-        .filter(|n| n.is_element() && n.xsd_type() == ElementType::Element)
+        .filter(|n| n.is_element() && n.xsd_type() != ElementType::Annotation)
         .map(|n| match parse_node(&n, choice) {
-            RsEntity::EnumCase(case) => case,
-            _ => unreachable!("Elements in choice must be a enum variants"),
+            Ok(RsEntity::EnumCase(case)) => Ok(case),
+            // Nested particles get types named after their first element, the naming
+            // registry tells apart those starting with the same element
+            Ok(RsEntity::Struct(mut st)) => {
+                st.name = branch_name(&n, "Sequence");
+                let case = branch_case(&n, &st.name, ChoiceBranch::Sequence, element_modifier(&n));
+                subtypes.push(RsEntity::Struct(st));
+                Ok(case)
+            }
+            Ok(RsEntity::Enum(mut en)) => {
+                en.name = branch_name(&n, "Choice");
+                let modifier = choice_modifiers(&n).pop().unwrap_or(TypeModifier::None);
+                let case = branch_case(&n, &en.name, ChoiceBranch::Choice, modifier);
+                subtypes.push(RsEntity::Enum(en));
+                Ok(case)
+            }
+            // Elements matched by xs:any are kept as raw XML
+            Ok(RsEntity::StructField(field)) if field.source == StructFieldSource::Any => {
+                Ok(EnumCase {
+                    name: "Any".to_string(),
+                    type_name: Some("xs:AnyElement".to_string()),
+                    comment: field.comment,
+                    type_modifiers: vec![element_modifier(&n)],
+                    source: EnumSource::Choice,
+                    branch: ChoiceBranch::Any,
+                    wildcard: field.wildcard,
+                    ..Default::default()
+                })
+            }
+            Ok(_) => Err(ParseError::invalid(&n, "Unsupported alternative of choice")),
+            Err(err) => Err(err),
         })
-        .collect();
+        .collect::<ParseResult<_>>()?;
 
-    RsEntity::Enum(Enum {
+    Ok(RsEntity::Enum(Enum {
         cases: enum_cases,
         type_name: "String".to_string(),
         source: EnumSource::Choice,
+        subtypes,
         ..Default::default()
-    })
+    }))
+}
+
+// Name of a nested particle after the first element it can start with, e.g. KeySequence
+fn branch_name(node: &Node, kind: &str) -> String {
+    let first = node.descendants().find_map(|n| match n.xsd_type() {
+        ElementType::Element => n.attr_name().or_else(|| n.attr_ref()).map(str::to_string),
+        ElementType::Group => n.attr_ref().map(str::to_string),
+        ElementType::Any => Some("Any".to_string()),
+        _ => None,
+    });
+    let first = first.unwrap_or_default();
+    format!("{}{}", first.rsplit(':').next().unwrap_or_default(), kind)
+}
+
+fn branch_case(
+    node: &Node,
+    type_name: &str,
+    branch: ChoiceBranch,
+    modifier: TypeModifier,
+) -> EnumCase {
+    EnumCase {
+        name: type_name.to_string(),
+        type_name: Some(type_name.to_string()),
+        comment: get_documentation(node),
+        type_modifiers: vec![modifier],
+        source: EnumSource::Choice,
+        branch,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parser::{
+        choice::parse_choice,
+        types::{ChoiceBranch, RsEntity, TypeModifier},
+        utils::find_child,
+    };
+
+    #[test]
+    fn test_nested_particles() {
+        let doc = roxmltree::Document::parse(
+            r###"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:tns="http://example.com">
+                <xs:choice>
+                    <xs:element name="Id" type="xs:int"/>
+                    <xs:sequence maxOccurs="unbounded">
+                        <xs:element name="Key" type="xs:string"/>
+                    </xs:sequence>
+                    <xs:choice maxOccurs="2">
+                        <xs:element name="Red" type="xs:int"/>
+                    </xs:choice>
+                    <xs:group ref="tns:Header"/>
+                    <xs:sequence>
+                        <xs:element ref="tns:Key"/>
+                    </xs:sequence>
+                    <xs:any namespace="##other" maxOccurs="unbounded"/>
+                </xs:choice>
+            </xs:schema>"###,
+        )
+        .unwrap();
+        let choice = find_child(&doc.root_element(), "choice").unwrap();
+
+        match parse_choice(&choice).unwrap() {
+            RsEntity::Enum(en) => {
+                let cases = en
+                    .cases
+                    .iter()
+                    .map(|c| (c.name.as_str(), c.branch.clone(), c.type_modifiers.clone()))
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    cases[1..],
+                    [
+                        ("KeySequence", ChoiceBranch::Sequence, vec![TypeModifier::Array]),
+                        ("RedChoice", ChoiceBranch::Choice, vec![TypeModifier::Array]),
+                        ("HeaderSequence", ChoiceBranch::Sequence, vec![TypeModifier::None]),
+                        ("KeySequence", ChoiceBranch::Sequence, vec![TypeModifier::None]),
+                        ("Any", ChoiceBranch::Any, vec![TypeModifier::Array]),
+                    ]
+                );
+                assert_eq!(cases[0].1, ChoiceBranch::Element);
+                assert_eq!(
+                    en.cases[5].wildcard.as_ref().map(|w| w.namespace.as_str()),
+                    Some("##other")
+                );
+                let subtypes = en.subtypes.iter().map(|s| s.name()).collect::<Vec<_>>();
+                assert_eq!(
+                    subtypes,
+                    vec!["KeySequence", "RedChoice", "HeaderSequence", "KeySequence"]
+                );
+            }
+            _ => unreachable!("Test failed!"),
+        }
+    }
 }
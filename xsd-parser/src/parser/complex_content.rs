@@ -1,17 +1,17 @@
 use roxmltree::Node;
 
 use crate::parser::{
+    error::{ParseError, ParseResult},
     node_parser::parse_node,
     types::RsEntity,
     xsd_elements::{ElementType, XsdNode},
 };
 
-pub fn parse_complex_content(node: &Node) -> RsEntity {
+pub fn parse_complex_content(node: &Node) -> ParseResult<RsEntity> {
     let content = node
         .children()
-        .filter(|n| n.is_element() && n.xsd_type() != ElementType::Annotation)
-        .last()
-        .expect("Content in complexContent required");
+        .rfind(|n| n.is_element() && n.xsd_type() != ElementType::Annotation)
+        .ok_or_else(|| ParseError::invalid(node, "Content in complexContent required"))?;
 
     parse_node(&content, node)
 }
@@ -1,14 +1,15 @@
-use std::cell::RefCell;
-
 use roxmltree::Node;
 
 use crate::parser::{
+    constants::attribute,
+    error::{ParseError, ParseResult},
     node_parser::parse_node,
     types::{RsEntity, Struct, StructField, StructFieldSource},
     utils::{
-        attribute_groups_to_aliases, attributes_to_fields, get_documentation, get_parent_name,
+        attribute_groups_to_aliases, attributes_to_fields, choice_modifiers, choice_to_field,
+        get_documentation, get_parent_name,
     },
-    xsd_elements::{ElementType, XsdNode},
+    xsd_elements::{occurs, ElementType, XsdNode},
 };
 
 // A complex type can contain one and only one of the following elements,
@@ -17,44 +18,67 @@ const AVAILABLE_CONTENT_TYPES: [ElementType; 6] = [
     ElementType::All, //No in ONVIF
     ElementType::Choice,
     ElementType::ComplexContent,
-    ElementType::Group, //No in ONVIF
+    ElementType::Group,
     ElementType::Sequence,
     ElementType::SimpleContent,
 ];
 
-pub fn parse_complex_type(node: &Node, parent: &Node) -> RsEntity {
+pub fn parse_complex_type(node: &Node, parent: &Node) -> ParseResult<RsEntity> {
     let name = if parent.xsd_type() == ElementType::Schema {
-        node.attr_name()
-            .expect("Name required if the complexType element is a child of the schema element")
+        node.attr_name().ok_or_else(|| {
+            ParseError::invalid(
+                node,
+                "Name required if the complexType element is a child of the schema element",
+            )
+        })?
     } else {
         get_parent_name(node)
     };
 
-    let mut fields = attributes_to_fields(node);
+    let mut fields = attributes_to_fields(node)?;
+    let mixed = is_mixed(node);
 
     let content = node
         .children()
-        .filter(|n| n.is_element() && AVAILABLE_CONTENT_TYPES.contains(&n.xsd_type()))
-        .last();
+        .rfind(|n| n.is_element() && AVAILABLE_CONTENT_TYPES.contains(&n.xsd_type()));
+
+    let is_empty = |n: &Node| {
+        n.xsd_type() != ElementType::Group && n.children().filter(|c| c.is_element()).count() == 0
+    };
 
-    if content.is_none() || content.unwrap().children().filter(|n| n.is_element()).count() == 0 {
+    if content.is_none() || is_empty(&content.unwrap()) {
         //No content (or empty), only attributes
 
-        return RsEntity::Struct(Struct {
-            fields: RefCell::new(fields),
-            attribute_groups: RefCell::new(attribute_groups_to_aliases(node)),
+        return Ok(RsEntity::Struct(Struct {
+            fields,
+            attribute_groups: attribute_groups_to_aliases(node)?,
             comment: get_documentation(node),
             subtypes: vec![],
             name: name.to_string(),
-        });
+            mixed,
+            ..Default::default()
+        }));
     }
     let content_node = content.unwrap();
 
-    let mut res = parse_node(&content_node, node);
+    let mut res = parse_node(&content_node, node)?;
+    // The choice of mixed content is merged with the text, see generator::struct
+    if let (RsEntity::Enum(en), true) = (&res, mixed) {
+        fields.push(choice_to_field(en.clone(), &content_node));
+        return Ok(RsEntity::Struct(Struct {
+            name: name.to_string(),
+            comment: get_documentation(node),
+            fields,
+            attribute_groups: attribute_groups_to_aliases(node)?,
+            mixed,
+            ..Default::default()
+        }));
+    }
     match &mut res {
         RsEntity::Struct(st) => {
-            st.fields.borrow_mut().append(&mut fields);
+            st.fields.append(&mut fields);
             st.name = name.to_string();
+            st.mixed = mixed;
         }
         RsEntity::Enum(en) => {
             en.name = format!("{}Choice", name);
@@ -62,17 +86,71 @@ pub fn parse_complex_type(node: &Node, parent: &Node) -> RsEntity {
                 name: en.name.clone(),
                 type_name: en.name.clone(),
                 source: StructFieldSource::Choice,
+                type_modifiers: choice_modifiers(&content_node),
+                occurs: occurs(&content_node),
                 ..Default::default()
             });
-            en.subtypes = vec![RsEntity::Struct(Struct {
+            // The nested particles of the choice are kept next to the struct
+            en.subtypes.push(RsEntity::Struct(Struct {
                 name: name.to_string(),
                 subtypes: vec![],
                 comment: get_documentation(node),
-                fields: RefCell::new(fields),
-                attribute_groups: RefCell::new(attribute_groups_to_aliases(node)),
-            })];
+                fields,
+                attribute_groups: attribute_groups_to_aliases(node)?,
+                mixed,
+                ..Default::default()
+            }));
         }
         _ => (),
     };
-    res
+    Ok(res)
+}
+
+// The mixed attribute of complexContent overrides the one of complexType
+fn is_mixed(node: &Node) -> bool {
+    node.children()
+        .find(|n| n.xsd_type() == ElementType::ComplexContent)
+        .and_then(|n| n.attribute(attribute::MIXED))
+        .or_else(|| node.attribute(attribute::MIXED))
+        .is_some_and(|mixed| mixed == "true" || mixed == "1")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parser::{complex_type::parse_complex_type, types::RsEntity};
+
+    #[test]
+    fn test_parse_mixed() {
+        let doc = roxmltree::Document::parse(
+            r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+                <xs:complexType name="Paragraph" mixed="true">
+                    <xs:sequence>
+                        <xs:element name="Bold" type="xs:string"/>
+                    </xs:sequence>
+                </xs:complexType>
+                <xs:complexType name="Note">
+                    <xs:complexContent mixed="true">
+                        <xs:extension base="Paragraph"/>
+                    </xs:complexContent>
+                </xs:complexType>
+                <xs:complexType name="Text">
+                    <xs:complexContent mixed="false">
+                        <xs:extension base="Paragraph"/>
+                    </xs:complexContent>
+                </xs:complexType>
+            </xs:schema>"#,
+        )
+        .unwrap();
+        let schema = doc.root_element();
+        let types = schema.children().filter(|n| n.is_element()).collect::<Vec<_>>();
+
+        let mixed = types
+            .iter()
+            .map(|ty| match parse_complex_type(ty, &schema).unwrap() {
+                RsEntity::Struct(st) => st.mixed,
+                _ => unreachable!("Test failed!"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(mixed, vec![true, true, false]);
+    }
 }
@@ -1,5 +1,9 @@
 pub mod tag {
     pub const BASE: &str = "__base__";
+    // Name of the field holding the text value of a simple content
+    pub const TEXT: &str = "value";
+    // Name of the field holding the content of a complex type with mixed content
+    pub const CONTENT: &str = "content";
 }
 
 pub mod attribute {
@@ -16,4 +20,20 @@ pub mod attribute {
     pub const MIN_OCCURS: &str = "minOccurs";
     pub const MAX_OCCURS: &str = "maxOccurs";
     pub const MEMBER_TYPES: &str = "memberTypes";
+    pub const ABSTRACT: &str = "abstract";
+    pub const SUBSTITUTION_GROUP: &str = "substitutionGroup";
+    pub const FORM: &str = "form";
+    pub const NILLABLE: &str = "nillable";
+    pub const DEFAULT: &str = "default";
+    pub const FIXED: &str = "fixed";
+    pub const PROCESS_CONTENTS: &str = "processContents";
+    pub const MIXED: &str = "mixed";
+    pub const ELEMENT_FORM_DEFAULT: &str = "elementFormDefault";
+    pub const ATTRIBUTE_FORM_DEFAULT: &str = "attributeFormDefault";
+}
+
+pub mod namespace {
+    pub const XSD: &str = "http://www.w3.org/2001/XMLSchema";
+    // Bound to the xml prefix without a declaration
+    pub const XML: &str = "http://www.w3.org/XML/1998/namespace";
 }
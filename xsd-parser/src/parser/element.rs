@@ -2,18 +2,23 @@ use roxmltree::Node;
 
 use crate::parser::{
     constants::attribute,
+    error::{ParseError, ParseResult},
     node_parser::parse_node,
     types::{
-        Alias, EnumCase, EnumSource, RsEntity, Struct, StructField, StructFieldSource, TypeModifier,
+        Alias, ChoiceBranch, EnumCase, EnumSource, GlobalElement, QName, RsEntity, Struct,
+        StructField, StructFieldSource, TypeModifier,
     },
     utils::get_documentation,
-    xsd_elements::{max_occurs, min_occurs, ElementType, MaxOccurs, XsdNode},
+    xsd_elements::{
+        element_form, max_occurs, min_occurs, occurs, value_constraint, ElementType, MaxOccurs,
+        XsdNode,
+    },
 };
 
 const SUPPORTED_CONTENT_TYPES: [ElementType; 2] =
     [ElementType::SimpleType, ElementType::ComplexType];
 
-pub fn parse_element(node: &Node, parent: &Node) -> RsEntity {
+pub fn parse_element(node: &Node, parent: &Node) -> ParseResult<RsEntity> {
     match parent.xsd_type() {
         ElementType::Schema => parse_global_element(node),
         ElementType::All => parse_field_of_sequence(node, parent),
@@ -23,54 +28,68 @@ pub fn parse_element(node: &Node, parent: &Node) -> RsEntity {
     }
 }
 
-fn element_default(node: &Node) -> RsEntity {
+fn element_default(node: &Node) -> ParseResult<RsEntity> {
     let ty = node.attr_type().unwrap_or("UNSUPPORTED");
-    RsEntity::Alias(Alias {
+    Ok(RsEntity::Alias(Alias {
         name: "UNSUPPORTED".into(),
         original: ty.into(),
+        original_qname: node.attr_qname(attribute::TYPE)?,
+        rust_type: None,
         comment: get_documentation(node),
         subtypes: vec![],
-    })
+    }))
 }
 
-fn parse_case_of_choice(element: &Node) -> RsEntity {
+fn parse_case_of_choice(element: &Node) -> ParseResult<RsEntity> {
     if element.has_attribute(attribute::REF) {
         let ref_attr = element.attr_ref().unwrap();
 
-        return RsEntity::EnumCase(EnumCase {
+        return Ok(RsEntity::EnumCase(EnumCase {
             name: ref_attr.to_string(),
             value: String::default(),
             type_name: Some(ref_attr.to_string()),
+            type_qname: element.attr_qname(attribute::REF)?,
             comment: get_documentation(element),
-            type_modifiers: vec![element_modifier(element)],
+            type_modifiers: element_modifiers(element),
             source: EnumSource::Choice,
-        });
+            branch: ChoiceBranch::Element,
+            form: element_form(element),
+            ..Default::default()
+        }));
     }
 
     let name = element.attr_name().unwrap_or("UNSUPPORTED_ELEMENT_NAME");
 
     if element.has_attribute(attribute::TYPE) {
-        return RsEntity::EnumCase(EnumCase {
+        return Ok(RsEntity::EnumCase(EnumCase {
             name: name.to_string(),
             value: String::default(),
             type_name: Some(element.attr_type().unwrap().to_string()),
+            type_qname: element.attr_qname(attribute::TYPE)?,
             comment: get_documentation(element),
-            type_modifiers: vec![element_modifier(element)],
+            type_modifiers: element_modifiers(element),
             source: EnumSource::Choice,
-        });
+            branch: ChoiceBranch::Element,
+            form: element_form(element),
+            ..Default::default()
+        }));
     }
 
-    RsEntity::EnumCase(EnumCase {
+    Ok(RsEntity::EnumCase(EnumCase {
         name: name.to_string(),
         value: String::default(),
         type_name: None,
+        type_qname: None,
         comment: get_documentation(element),
-        type_modifiers: vec![element_modifier(element)],
+        type_modifiers: element_modifiers(element),
         source: EnumSource::Choice,
-    })
+        branch: ChoiceBranch::Element,
+        form: element_form(element),
+        ..Default::default()
+    }))
 }
 
-fn parse_field_of_sequence(node: &Node, _: &Node) -> RsEntity {
+fn parse_field_of_sequence(node: &Node, _: &Node) -> ParseResult<RsEntity> {
     let name = node
         .attr_name()
         .unwrap_or_else(|| node.attr_ref().unwrap_or("UNSUPPORTED_ELEMENT_NAME"))
@@ -80,65 +99,114 @@ fn parse_field_of_sequence(node: &Node, _: &Node) -> RsEntity {
         let type_name =
             node.attr_type().unwrap_or_else(|| node.attr_ref().unwrap_or("String")).to_string();
 
-        return RsEntity::StructField(StructField {
+        return Ok(RsEntity::StructField(StructField {
             name,
             type_name,
             comment: get_documentation(node),
             source: StructFieldSource::Element,
-            type_modifiers: vec![element_modifier(node)],
+            type_modifiers: element_modifiers(node),
+            type_qname: match node.attr_qname(attribute::TYPE)? {
+                Some(qname) => Some(qname),
+                None => node.attr_qname(attribute::REF)?,
+            },
+            form: element_form(node),
+            value_constraint: value_constraint(node),
+            occurs: occurs(node),
             ..Default::default()
-        });
+        }));
     }
 
-    let content_node = node
-        .children()
-        .filter(|n| SUPPORTED_CONTENT_TYPES.contains(&n.xsd_type()))
-        .last()
-        .unwrap_or_else(|| panic!("Must have content if no 'type' or 'ref' attribute: {:?}", node));
+    let content_node =
+        node.children().rfind(|n| SUPPORTED_CONTENT_TYPES.contains(&n.xsd_type())).ok_or_else(
+            || ParseError::invalid(node, "Must have content if no 'type' or 'ref' attribute"),
+        )?;
 
-    let mut field_type = parse_node(&content_node, node);
+    let mut field_type = parse_node(&content_node, node)?;
 
     field_type.set_name(format!("{}Type", name).as_str());
 
-    RsEntity::StructField(StructField {
+    Ok(RsEntity::StructField(StructField {
         name,
         type_name: field_type.name().to_string(),
         comment: get_documentation(node),
         subtypes: vec![field_type],
         source: StructFieldSource::Element,
-        type_modifiers: vec![element_modifier(node)],
-    })
+        type_modifiers: element_modifiers(node),
+        form: element_form(node),
+        value_constraint: value_constraint(node),
+        occurs: occurs(node),
+        ..Default::default()
+    }))
 }
 
-fn parse_global_element(node: &Node) -> RsEntity {
-    let name = node.attr_name().expect("Name required if the element is a child of the schema");
+fn parse_global_element(node: &Node) -> ParseResult<RsEntity> {
+    let name = node.attr_name().ok_or_else(|| {
+        ParseError::invalid(node, "Name required if the element is a child of the schema")
+    })?;
 
     if node.has_attribute(attribute::TYPE) {
-        return RsEntity::Alias(Alias {
+        return Ok(RsEntity::Alias(Alias {
             name: name.into(),
             original: node.attr_type().unwrap().into(),
+            original_qname: node.attr_qname(attribute::TYPE)?,
+            rust_type: None,
             comment: get_documentation(node),
             subtypes: vec![],
-        });
+        }));
     }
 
-    let content_node =
-        node.children().filter(|n| SUPPORTED_CONTENT_TYPES.contains(&n.xsd_type())).last();
+    let content_node = node.children().rfind(|n| SUPPORTED_CONTENT_TYPES.contains(&n.xsd_type()));
 
     if let Some(content) = content_node {
-        let mut content_entity = parse_node(&content, node);
+        let mut content_entity = parse_node(&content, node)?;
         content_entity.set_name(name);
-        return content_entity;
+        return Ok(content_entity);
     }
 
     // No content => empty struct
-    RsEntity::Struct(Struct {
+    Ok(RsEntity::Struct(Struct {
         name: name.to_string(),
         comment: get_documentation(node),
         ..Default::default()
+    }))
+}
+
+pub fn global_element(node: &Node) -> ParseResult<GlobalElement> {
+    let name = node.attr_name().ok_or_else(|| {
+        ParseError::invalid(node, "Name required if the element is a child of the schema")
+    })?;
+
+    let target_namespace =
+        node.parent_element().and_then(|schema| schema.attribute(attribute::TARGET_NAMESPACE));
+
+    // An element with anonymous type gets a type named after the element
+    let (type_name, type_qname) = match node.attr_type() {
+        Some(ty) => (Some(ty.to_string()), node.attr_qname(attribute::TYPE)?),
+        None if node.children().any(|n| SUPPORTED_CONTENT_TYPES.contains(&n.xsd_type())) => {
+            (Some(name.to_string()), Some(QName::new(target_namespace, name)))
+        }
+        None => (None, None),
+    };
+
+    Ok(GlobalElement {
+        name: QName::new(target_namespace, name),
+        type_name,
+        type_qname,
+        substitution_group: node.attr_qname(attribute::SUBSTITUTION_GROUP)?,
+        is_abstract: node.attribute(attribute::ABSTRACT) == Some("true"),
+        comment: get_documentation(node),
     })
 }
 
+// Occurrence of the element, and whether it can be sent as xsi:nil
+fn element_modifiers(node: &Node) -> Vec<TypeModifier> {
+    let mut modifiers = vec![element_modifier(node)];
+    if node.attribute(attribute::NILLABLE) == Some("true") {
+        modifiers.push(TypeModifier::Nillable);
+    }
+    modifiers
+}
+
 pub fn element_modifier(node: &Node) -> TypeModifier {
     let min = min_occurs(node);
     let max = max_occurs(node);
@@ -187,12 +255,12 @@ mod test {
         let schema = doc.root_element();
         let element = find_child(&schema, "element").unwrap();
 
-        match parse_global_element(&element) {
+        match parse_global_element(&element).unwrap() {
             RsEntity::Struct(st) => {
                 assert_eq!(st.name, "ChangedOnly");
                 assert_eq!(st.comment.unwrap().trim(), "Doc Text");
                 assert!(st.subtypes.is_empty());
-                assert!(st.fields.borrow().is_empty());
+                assert!(st.fields.is_empty());
             }
             _ => unreachable!("Test failed!"),
         }
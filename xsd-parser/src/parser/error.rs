@@ -0,0 +1,121 @@
+use std::{fmt, io, path::PathBuf};
+
+use roxmltree::{Node, TextPos};
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+#[derive(Debug)]
+pub enum ParseError {
+    /// A schema document could not be read.
+    Io { path: PathBuf, error: io::Error },
+    /// An error occurred while parsing one of the documents of a schema set.
+    File { path: PathBuf, error: Box<ParseError> },
+    /// The input is not a well-formed XML document.
+    Document(roxmltree::Error),
+    /// The document does not contain a root `schema` element.
+    MissingSchema,
+    /// An XSD node violates the schema for schemas (missing or invalid attributes, content, etc.).
+    InvalidNode { element: String, pos: TextPos, reason: String },
+    /// An XSD node is valid, but the parser does not support it yet.
+    UnsupportedNode { element: String, pos: TextPos, parent: Option<String> },
+    /// A type, group or attribute group referenced by a declaration is not declared.
+    UnresolvedReference { kind: String, name: String, referrer: String },
+    /// Types, groups or attribute groups are derived from or include each other.
+    CyclicReference { kind: String, names: Vec<String> },
+}
+
+impl ParseError {
+    pub fn in_file(self, path: impl Into<PathBuf>) -> Self {
+        match self {
+            ParseError::Io { .. } | ParseError::File { .. } => self,
+            _ => ParseError::File { path: path.into(), error: Box::new(self) },
+        }
+    }
+
+    pub fn invalid(node: &Node, reason: impl Into<String>) -> Self {
+        ParseError::InvalidNode {
+            element: node.tag_name().name().to_string(),
+            pos: node_pos(node),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn unsupported(node: &Node) -> Self {
+        ParseError::UnsupportedNode {
+            element: node.tag_name().name().to_string(),
+            pos: node_pos(node),
+            parent: node.parent_element().map(|p| p.tag_name().name().to_string()),
+        }
+    }
+
+    /// Name of the offending XSD element, if the error is bound to a node.
+    pub fn element(&self) -> Option<&str> {
+        match self {
+            ParseError::InvalidNode { element, .. } => Some(element),
+            ParseError::UnsupportedNode { element, .. } => Some(element),
+            ParseError::File { error, .. } => error.element(),
+            _ => None,
+        }
+    }
+
+    /// Source position (row and column) of the offending XSD element.
+    pub fn pos(&self) -> Option<TextPos> {
+        match self {
+            ParseError::Document(err) => Some(err.pos()),
+            ParseError::InvalidNode { pos, .. } => Some(*pos),
+            ParseError::UnsupportedNode { pos, .. } => Some(*pos),
+            ParseError::File { error, .. } => error.pos(),
+            ParseError::Io { .. }
+            | ParseError::MissingSchema
+            | ParseError::UnresolvedReference { .. }
+            | ParseError::CyclicReference { .. } => None,
+        }
+    }
+}
+
+fn node_pos(node: &Node) -> TextPos {
+    node.document().text_pos_at(node.range().start)
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            ParseError::File { path, error } => write!(f, "{}: {}", path.display(), error),
+            ParseError::Document(err) => write!(f, "Parse document error: {}", err),
+            ParseError::MissingSchema => write!(f, "Schema element is required"),
+            ParseError::InvalidNode { element, pos, reason } => {
+                write!(f, "Invalid '{}' element at {}: {}", element, pos, reason)
+            }
+            ParseError::UnsupportedNode { element, pos, parent: Some(parent) } => {
+                write!(f, "Unsupported '{}' element in '{}' at {}", element, parent, pos)
+            }
+            ParseError::UnsupportedNode { element, pos, parent: None } => {
+                write!(f, "Unsupported '{}' element at {}", element, pos)
+            }
+            ParseError::UnresolvedReference { kind, name, referrer } => {
+                write!(f, "Unresolved {} '{}' referenced by '{}'", kind, name, referrer)
+            }
+            ParseError::CyclicReference { kind, names } => {
+                write!(f, "Cyclic {} references: {}", kind, names.join(" -> "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseError::Io { error, .. } => Some(error),
+            ParseError::File { error, .. } => Some(error.as_ref()),
+            ParseError::Document(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<roxmltree::Error> for ParseError {
+    fn from(err: roxmltree::Error) -> Self {
+        ParseError::Document(err)
+    }
+}
@@ -1,9 +1,8 @@
-use std::cell::RefCell;
-
 use roxmltree::Node;
 
 use crate::parser::{
-    constants::{attribute, tag},
+    constants::tag,
+    error::{ParseError, ParseResult},
     node_parser::parse_node,
     types::{RsEntity, Struct, StructField, StructFieldSource},
     utils::{attribute_groups_to_aliases, attributes_to_fields, get_base, get_documentation},
@@ -15,75 +14,76 @@ const AVAILABLE_CONTENT_TYPES: [ElementType; 6] = [
     ElementType::Attribute,
     ElementType::AttributeGroup,
     ElementType::Choice,
-    ElementType::Group, // Not presented in ONVIF
+    ElementType::Group,
     ElementType::Sequence,
 ];
 
-pub fn parse_extension(node: &Node, _: &Node) -> RsEntity {
+pub fn parse_extension(node: &Node, _: &Node) -> ParseResult<RsEntity> {
     use ElementType::Extension;
     match node.xsd_type() {
         Extension(ExtensionType::SimpleContent) => simple_content_extension(node),
         Extension(ExtensionType::ComplexContent) => complex_content_extension(node),
-        _ => unreachable!("Invalid extension node: {:#?}", node),
+        _ => Err(ParseError::invalid(node, "Invalid extension node")),
     }
 }
 
-fn simple_content_extension(node: &Node) -> RsEntity {
-    let base = get_base(node);
-    let mut fields = attributes_to_fields(node);
+fn simple_content_extension(node: &Node) -> ParseResult<RsEntity> {
+    let base = get_base(node)?;
+    let mut fields = attributes_to_fields(node)?;
 
     fields.push(StructField {
         name: tag::BASE.to_string(),
         type_name: base.to_string(),
+        type_qname: Some(node.resolve_qname(base)?),
         comment: get_documentation(node),
-        source: StructFieldSource::Base,
+        source: StructFieldSource::Text,
         ..Default::default()
     });
 
-    RsEntity::Struct(Struct {
+    Ok(RsEntity::Struct(Struct {
         name: String::default(),
         subtypes: vec![],
         comment: get_documentation(node),
-        fields: RefCell::new(fields),
-        attribute_groups: RefCell::new(attribute_groups_to_aliases(node)),
-    })
+        fields,
+        attribute_groups: attribute_groups_to_aliases(node)?,
+        mixed: false,
+        ..Default::default()
+    }))
 }
 
-fn complex_content_extension(node: &Node) -> RsEntity {
-    let base = node.attribute(attribute::BASE).expect("The base value is required");
+fn complex_content_extension(node: &Node) -> ParseResult<RsEntity> {
+    let base = get_base(node)?;
 
-    let mut fields = attributes_to_fields(node);
+    let mut fields = attributes_to_fields(node)?;
 
     fields.push(StructField {
         name: tag::BASE.to_string(),
         type_name: base.to_string(),
+        type_qname: Some(node.resolve_qname(base)?),
         comment: get_documentation(node),
         source: StructFieldSource::Base,
         ..Default::default()
     });
 
-    let content = node
-        .children()
-        .filter(|n| {
-            n.is_element()
-                && n.xsd_type() != ElementType::Attribute
-                && AVAILABLE_CONTENT_TYPES.contains(&n.xsd_type())
-        })
-        .last();
+    let content = node.children().rfind(|n| {
+        n.is_element()
+            && n.xsd_type() != ElementType::Attribute
+            && AVAILABLE_CONTENT_TYPES.contains(&n.xsd_type())
+    });
 
     if let Some(cont) = content {
-        let mut res = parse_node(&cont, node);
+        let mut res = parse_node(&cont, node)?;
         if let RsEntity::Struct(s) = &mut res {
-            s.fields.borrow_mut().append(&mut fields);
+            s.fields.append(&mut fields);
             s.comment = get_documentation(node);
-            return res;
+            return Ok(res);
         }
     }
 
-    RsEntity::Struct(Struct {
+    Ok(RsEntity::Struct(Struct {
         comment: get_documentation(node),
-        fields: RefCell::new(fields),
-        attribute_groups: RefCell::new(attribute_groups_to_aliases(node)),
+        fields,
+        attribute_groups: attribute_groups_to_aliases(node)?,
         ..Default::default()
-    })
+    }))
 }
@@ -0,0 +1,172 @@
+use roxmltree::Node;
+
+use crate::parser::{
+    element::element_modifier,
+    error::{ParseError, ParseResult},
+    node_parser::parse_node,
+    types::{RsEntity, Struct, StructField, StructFieldSource},
+    utils::{choice_to_field, get_documentation},
+    xsd_elements::{occurs, ElementType, XsdNode},
+};
+
+const AVAILABLE_CONTENT_TYPES: [ElementType; 3] =
+    [ElementType::All, ElementType::Choice, ElementType::Sequence];
+
+pub fn parse_group(node: &Node, parent: &Node) -> ParseResult<RsEntity> {
+    if parent.xsd_type() == ElementType::Schema {
+        return parse_global_group(node);
+    }
+
+    let reference = node
+        .attr_ref()
+        .ok_or_else(|| ParseError::invalid(node, "Non-global groups must be references."))?;
+
+    // The fields of the group are inlined later, see Struct::extend_group
+    let field = StructField {
+        name: reference.to_string(),
+        type_name: reference.to_string(),
+        comment: get_documentation(node),
+        source: StructFieldSource::Group,
+        type_modifiers: vec![element_modifier(node)],
+        occurs: occurs(node),
+        type_qname: Some(node.resolve_qname(reference)?),
+        ..Default::default()
+    };
+
+    match parent.xsd_type() {
+        ElementType::Sequence | ElementType::All => Ok(RsEntity::StructField(field)),
+        // A group reference as the content of complexType, extension or restriction
+        _ => Ok(RsEntity::Struct(Struct {
+            comment: get_documentation(node),
+            fields: vec![field],
+            ..Default::default()
+        })),
+    }
+}
+
+fn parse_global_group(node: &Node) -> ParseResult<RsEntity> {
+    let name =
+        node.attr_name().ok_or_else(|| ParseError::invalid(node, "Name attribute required"))?;
+
+    let content = node
+        .children()
+        .find(|n| n.is_element() && AVAILABLE_CONTENT_TYPES.contains(&n.xsd_type()))
+        .ok_or_else(|| ParseError::invalid(node, "Group must contain all, choice or sequence"))?;
+
+    let fields = match parse_node(&content, node)? {
+        RsEntity::Struct(st) => st.fields,
+        RsEntity::Enum(mut en) => {
+            en.name = format!("{}Choice", name);
+            vec![choice_to_field(en, &content)]
+        }
+        _ => return Err(ParseError::unsupported(&content)),
+    };
+
+    Ok(RsEntity::Struct(Struct {
+        name: name.to_string(),
+        comment: get_documentation(node),
+        fields,
+        ..Default::default()
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parser::{
+        group::parse_group,
+        types::{RsEntity, StructFieldSource, TypeModifier},
+        utils::find_child,
+    };
+
+    #[test]
+    fn test_global_group() {
+        let doc = roxmltree::Document::parse(
+            r#"
+        <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+            <xs:group name="Header">
+                <xs:sequence>
+                    <xs:element name="id" type="xs:int"/>
+                    <xs:element name="comment" type="xs:string" minOccurs="0"/>
+                </xs:sequence>
+            </xs:group>
+        </xs:schema>
+        "#,
+        )
+        .unwrap();
+
+        let schema = doc.root_element();
+        let group = find_child(&schema, "group").unwrap();
+        match parse_group(&group, &schema).unwrap() {
+            RsEntity::Struct(st) => {
+                assert_eq!(st.name, "Header");
+                let fields = &st.fields;
+                assert_eq!(fields.len(), 2);
+                assert_eq!(fields[0].name, "id");
+                assert_eq!(fields[1].type_modifiers, vec![TypeModifier::Option]);
+            }
+            _ => unreachable!("Test Failed!"),
+        }
+    }
+
+    #[test]
+    fn test_global_choice_group() {
+        let doc = roxmltree::Document::parse(
+            r#"
+        <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+            <xs:group name="Shape">
+                <xs:choice>
+                    <xs:element name="circle" type="xs:int"/>
+                    <xs:element name="square" type="xs:int"/>
+                </xs:choice>
+            </xs:group>
+        </xs:schema>
+        "#,
+        )
+        .unwrap();
+
+        let schema = doc.root_element();
+        let group = find_child(&schema, "group").unwrap();
+        match parse_group(&group, &schema).unwrap() {
+            RsEntity::Struct(st) => {
+                let fields = &st.fields;
+                assert_eq!(fields.len(), 1);
+                assert_eq!(fields[0].type_name, "ShapeChoice");
+                assert!(matches!(fields[0].source, StructFieldSource::Choice));
+                match &fields[0].subtypes[0] {
+                    RsEntity::Enum(en) => assert_eq!(en.cases.len(), 2),
+                    _ => unreachable!("Test Failed!"),
+                }
+            }
+            _ => unreachable!("Test Failed!"),
+        }
+    }
+
+    #[test]
+    fn test_group_reference() {
+        let doc = roxmltree::Document::parse(
+            r#"
+        <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:tns="http://example.com">
+            <xs:complexType name="Foo">
+                <xs:sequence>
+                    <xs:group ref="tns:Header" maxOccurs="unbounded"/>
+                </xs:sequence>
+            </xs:complexType>
+        </xs:schema>
+        "#,
+        )
+        .unwrap();
+
+        let schema = doc.root_element();
+        let sequence =
+            find_child(&find_child(&schema, "complexType").unwrap(), "sequence").unwrap();
+        let group = find_child(&sequence, "group").unwrap();
+        match parse_group(&group, &sequence).unwrap() {
+            RsEntity::StructField(sf) => {
+                assert_eq!(sf.type_name, "tns:Header");
+                assert!(matches!(sf.source, StructFieldSource::Group));
+                assert_eq!(sf.type_modifiers, vec![TypeModifier::Array]);
+            }
+            _ => unreachable!("Test Failed!"),
+        }
+    }
+}
@@ -2,13 +2,14 @@ use roxmltree::Node;
 
 use crate::parser::{
     constants::attribute,
+    error::ParseResult,
     types::{Import, RsEntity},
 };
 
-pub fn parse_import(node: &Node) -> RsEntity {
-    RsEntity::Import(Import {
+pub fn parse_import(node: &Node) -> ParseResult<RsEntity> {
+    Ok(RsEntity::Import(Import {
         name: node.attribute(attribute::NAMESPACE).unwrap_or("").into(),
         location: node.attribute(attribute::SCHEMA_LOCATION).unwrap_or("").into(),
         comment: None,
-    })
+    }))
 }
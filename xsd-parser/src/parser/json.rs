@@ -0,0 +1,486 @@
+use serde::Serialize;
+
+use crate::parser::{
+    types::{self as ir, RsEntity, RsFile, StructFieldSource, TypeModifier},
+    xsd_elements::{FacetType, FormType, ProcessContents, WhiteSpace},
+};
+
+// Version of the JSON format, incremented on changes which can break its consumers.
+// The format is made of the types below, which the parsed model is mapped onto, so
+// changes of the model don't change it (see tests/json_export).
+pub const FORMAT_VERSION: u32 = 2;
+
+#[derive(Serialize)]
+struct Document {
+    version: u32,
+    files: Vec<File>,
+}
+
+#[derive(Serialize)]
+struct File {
+    namespace: Option<String>,
+    namespaces: Vec<Namespace>,
+    types: Vec<Type>,
+    elements: Vec<Element>,
+}
+
+#[derive(Serialize)]
+struct Namespace {
+    prefix: Option<String>,
+    uri: String,
+}
+
+#[derive(Serialize)]
+struct QName {
+    namespace: Option<String>,
+    name: String,
+}
+
+// Type of a field, a case or a simple type: a global type, which is one of the types
+// of the file of its namespace unless it is built into XML Schema, or an anonymous
+// type nested in the declaring one
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum TypeRef {
+    Global { namespace: Option<String>, name: String },
+    Nested { name: String },
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Type {
+    Struct {
+        name: String,
+        comment: Option<String>,
+        mixed: bool,
+        fields: Vec<Field>,
+        types: Vec<Type>,
+    },
+    Simple {
+        name: String,
+        comment: Option<String>,
+        base: Option<TypeRef>,
+        list: bool,
+        facets: Vec<Facet>,
+        types: Vec<Type>,
+    },
+    Enum {
+        name: String,
+        comment: Option<String>,
+        source: EnumSource,
+        cases: Vec<Case>,
+        types: Vec<Type>,
+    },
+    Alias {
+        name: String,
+        comment: Option<String>,
+        target: Option<TypeRef>,
+        types: Vec<Type>,
+    },
+}
+
+#[derive(Serialize)]
+struct Field {
+    name: String,
+    source: FieldSource,
+    #[serde(rename = "type")]
+    type_ref: Option<TypeRef>,
+    comment: Option<String>,
+    occurs: Occurs,
+    nillable: bool,
+    form: Form,
+    value_constraint: Option<ValueConstraint>,
+    facets: Vec<Facet>,
+    wildcard: Option<Wildcard>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum FieldSource {
+    Element,
+    Attribute,
+    Text,
+    Choice,
+    Any,
+    AnyAttribute,
+}
+
+#[derive(Serialize)]
+struct Case {
+    name: String,
+    comment: Option<String>,
+    // Enumerated value of a restriction
+    value: Option<String>,
+    #[serde(rename = "type")]
+    type_ref: Option<TypeRef>,
+    list: bool,
+    // Content of an alternative of a choice
+    branch: Option<Branch>,
+    form: Form,
+    wildcard: Option<Wildcard>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum EnumSource {
+    Restriction,
+    Choice,
+    Union,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Branch {
+    Element,
+    Sequence,
+    Choice,
+    Any,
+    Text,
+}
+
+#[derive(Serialize)]
+struct Element {
+    name: String,
+    #[serde(rename = "type")]
+    type_ref: Option<TypeRef>,
+    substitution_group: Option<QName>,
+    #[serde(rename = "abstract")]
+    is_abstract: bool,
+    comment: Option<String>,
+}
+
+// Allowed number of occurrences, `max` is null if unbounded
+#[derive(Serialize)]
+struct Occurs {
+    min: usize,
+    max: Option<usize>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Form {
+    Qualified,
+    Unqualified,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+enum ValueConstraint {
+    Default(String),
+    Fixed(String),
+}
+
+#[derive(Serialize)]
+struct Facet {
+    kind: &'static str,
+    value: String,
+    comment: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Wildcard {
+    namespace: String,
+    process_contents: &'static str,
+}
+
+// The parsed and resolved schema model as JSON, for tools which don't generate code.
+// Types and type references are tagged with a snake_case "kind".
+pub fn to_json(files: &[RsFile]) -> serde_json::Result<String> {
+    let files = files.iter().map(|file| Exporter { file }.file()).collect();
+    serde_json::to_string_pretty(&Document { version: FORMAT_VERSION, files })
+}
+
+struct Exporter<'a> {
+    file: &'a RsFile,
+}
+
+impl Exporter<'_> {
+    fn file(&self) -> File {
+        File {
+            namespace: self.file.namespace.clone(),
+            namespaces: self
+                .file
+                .namespaces
+                .iter()
+                .map(|ns| Namespace { prefix: ns.prefix.clone(), uri: ns.uri.clone() })
+                .collect(),
+            types: self.types(&self.file.types),
+            elements: self.file.elements.iter().map(|el| self.element(el)).collect(),
+        }
+    }
+
+    fn types<'b>(&self, entities: impl IntoIterator<Item = &'b RsEntity>) -> Vec<Type> {
+        entities.into_iter().filter_map(|entity| self.entity(entity)).collect()
+    }
+
+    fn entity(&self, entity: &RsEntity) -> Option<Type> {
+        Some(match entity {
+            RsEntity::Struct(st) => {
+                let nested = st.subtypes.iter().chain(st.fields.iter().flat_map(|f| &f.subtypes));
+                let nested = nested.collect::<Vec<_>>();
+                Type::Struct {
+                    name: st.name.clone(),
+                    comment: st.comment.clone(),
+                    mixed: st.mixed,
+                    fields: st.fields.iter().filter_map(|f| self.field(f, &nested)).collect(),
+                    types: self.types(nested),
+                }
+            }
+            RsEntity::TupleStruct(ts) => Type::Simple {
+                name: ts.name.clone(),
+                comment: ts.comment.clone(),
+                base: self.type_ref(
+                    &ts.type_name,
+                    ts.type_qname.as_ref(),
+                    &ts.subtypes.iter().collect::<Vec<_>>(),
+                ),
+                list: ts.type_modifiers.contains(&TypeModifier::Array),
+                facets: facets(&ts.facets),
+                types: self.types(&ts.subtypes),
+            },
+            RsEntity::Enum(en) => Type::Enum {
+                name: en.name.clone(),
+                comment: en.comment.clone(),
+                source: match en.source {
+                    ir::EnumSource::Choice => EnumSource::Choice,
+                    ir::EnumSource::Union => EnumSource::Union,
+                    ir::EnumSource::Restriction | ir::EnumSource::NA => EnumSource::Restriction,
+                },
+                cases: en.cases.iter().map(|case| self.case(case, en)).collect(),
+                types: self.types(&en.subtypes),
+            },
+            RsEntity::Alias(al) => Type::Alias {
+                name: al.name.clone(),
+                comment: al.comment.clone(),
+                target: self.type_ref(
+                    &al.original,
+                    al.original_qname.as_ref(),
+                    &al.subtypes.iter().collect::<Vec<_>>(),
+                ),
+                types: self.types(&al.subtypes),
+            },
+            RsEntity::StructField(_) | RsEntity::EnumCase(_) | RsEntity::Import(_) => return None,
+        })
+    }
+
+    // Fields of base types and groups are inlined when the types are resolved,
+    // prohibited attributes are left out
+    fn field(&self, field: &ir::StructField, nested: &[&RsEntity]) -> Option<Field> {
+        let source = match field.source {
+            StructFieldSource::Element => FieldSource::Element,
+            StructFieldSource::Attribute => FieldSource::Attribute,
+            StructFieldSource::Text => FieldSource::Text,
+            StructFieldSource::Choice => FieldSource::Choice,
+            StructFieldSource::Any => FieldSource::Any,
+            StructFieldSource::AnyAttribute => FieldSource::AnyAttribute,
+            StructFieldSource::Mixed
+            | StructFieldSource::Base
+            | StructFieldSource::Group
+            | StructFieldSource::NA => return None,
+        };
+        if field.type_modifiers.contains(&TypeModifier::Empty) {
+            return None;
+        }
+        let occurs = match field.source {
+            StructFieldSource::Attribute => Occurs {
+                min: if field.type_modifiers.contains(&TypeModifier::Option) { 0 } else { 1 },
+                max: Some(1),
+            },
+            _ => Occurs { min: field.occurs.min, max: field.occurs.max },
+        };
+        let type_ref = match field.source {
+            StructFieldSource::Any | StructFieldSource::AnyAttribute => None,
+            _ => self.type_ref(&field.type_name, field.type_qname.as_ref(), nested),
+        };
+        Some(Field {
+            name: field.name.clone(),
+            source,
+            type_ref,
+            comment: field.comment.clone(),
+            occurs,
+            nillable: field.type_modifiers.contains(&TypeModifier::Nillable),
+            form: form(field.form),
+            value_constraint: field.value_constraint.as_ref().map(|value| match value {
+                ir::ValueConstraint::Default(value) => ValueConstraint::Default(value.clone()),
+                ir::ValueConstraint::Fixed(value) => ValueConstraint::Fixed(value.clone()),
+            }),
+            facets: facets(&field.facets),
+            wildcard: field.wildcard.as_ref().map(wildcard),
+        })
+    }
+
+    fn case(&self, case: &ir::EnumCase, en: &ir::Enum) -> Case {
+        let choice = en.source == ir::EnumSource::Choice;
+        let nested = en.subtypes.iter().collect::<Vec<_>>();
+        let type_ref = match (&case.type_name, case.branch == ir::ChoiceBranch::Any) {
+            (Some(type_name), false) => self.type_ref(type_name, case.type_qname.as_ref(), &nested),
+            _ => None,
+        };
+        Case {
+            name: case.name.clone(),
+            comment: case.comment.clone(),
+            value: Some(case.value.clone()).filter(|_| en.source == ir::EnumSource::Restriction),
+            type_ref,
+            list: case.type_modifiers.contains(&TypeModifier::Array),
+            branch: Some(match case.branch {
+                ir::ChoiceBranch::Element => Branch::Element,
+                ir::ChoiceBranch::Sequence => Branch::Sequence,
+                ir::ChoiceBranch::Choice => Branch::Choice,
+                ir::ChoiceBranch::Any => Branch::Any,
+                ir::ChoiceBranch::Text => Branch::Text,
+            })
+            .filter(|_| choice),
+            form: form(case.form),
+            wildcard: case.wildcard.as_ref().map(wildcard),
+        }
+    }
+
+    fn element(&self, element: &ir::GlobalElement) -> Element {
+        Element {
+            name: element.name.name.clone(),
+            type_ref: element.type_qname.as_ref().map(global),
+            substitution_group: element.substitution_group.as_ref().map(qname),
+            is_abstract: element.is_abstract,
+            comment: element.comment.clone(),
+        }
+    }
+
+    // Resolved type, a type nested in the declaring one, or a type of the file which
+    // the parser added without a declaration (e.g. the choice of a complex type)
+    fn type_ref(
+        &self,
+        type_name: &str,
+        type_qname: Option<&ir::QName>,
+        nested: &[&RsEntity],
+    ) -> Option<TypeRef> {
+        if let Some(qname) = type_qname {
+            return Some(global(qname));
+        }
+        if nested.iter().any(|entity| entity.name() == type_name) {
+            return Some(TypeRef::Nested { name: type_name.to_string() });
+        }
+        self.file.types.iter().find(|entity| entity.name() == type_name).map(|entity| {
+            TypeRef::Global {
+                namespace: self.file.namespace.clone(),
+                name: entity.name().to_string(),
+            }
+        })
+    }
+}
+
+fn global(qname: &ir::QName) -> TypeRef {
+    TypeRef::Global { namespace: qname.namespace.clone(), name: qname.name.clone() }
+}
+
+fn qname(qname: &ir::QName) -> QName {
+    QName { namespace: qname.namespace.clone(), name: qname.name.clone() }
+}
+
+fn form(form: FormType) -> Form {
+    match form {
+        FormType::Qualified => Form::Qualified,
+        FormType::Unqualified => Form::Unqualified,
+    }
+}
+
+fn wildcard(wildcard: &ir::Wildcard) -> Wildcard {
+    Wildcard {
+        namespace: wildcard.namespace.clone(),
+        process_contents: match wildcard.process_contents {
+            ProcessContents::Strict => "strict",
+            ProcessContents::Lax => "lax",
+            ProcessContents::Skip => "skip",
+        },
+    }
+}
+
+fn facets(facets: &[ir::Facet]) -> Vec<Facet> {
+    facets
+        .iter()
+        .map(|facet| {
+            use FacetType::*;
+            let (kind, value) = match &facet.facet_type {
+                Enumeration(value) => ("enumeration", value.as_str()),
+                FractionDigits(value) => ("fraction_digits", value.as_str()),
+                Length(value) => ("length", value.as_str()),
+                MaxExclusive(value) => ("max_exclusive", value.as_str()),
+                MaxInclusive(value) => ("max_inclusive", value.as_str()),
+                MaxLength(value) => ("max_length", value.as_str()),
+                MinExclusive(value) => ("min_exclusive", value.as_str()),
+                MinInclusive(value) => ("min_inclusive", value.as_str()),
+                MinLength(value) => ("min_length", value.as_str()),
+                Pattern(value) => ("pattern", value.as_str()),
+                TotalDigits(value) => ("total_digits", value.as_str()),
+                WhiteSpace(value) => (
+                    "white_space",
+                    match value {
+                        self::WhiteSpace::Preserve => "preserve",
+                        self::WhiteSpace::Replace => "replace",
+                        self::WhiteSpace::Collapse => "collapse",
+                    },
+                ),
+            };
+            Facet { kind, value: value.to_string(), comment: facet.comment.clone() }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::{json, Value};
+
+    use crate::parser::{json::to_json, parse};
+
+    #[test]
+    fn test_to_json() {
+        let file = parse(
+            r#"
+        <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"
+                   xmlns:tns="http://example.com"
+                   targetNamespace="http://example.com">
+            <xs:complexType name="Item">
+                <xs:annotation><xs:documentation>An item</xs:documentation></xs:annotation>
+                <xs:sequence>
+                    <xs:element name="Code" type="tns:Code" maxOccurs="unbounded"/>
+                </xs:sequence>
+                <xs:attribute name="id" type="xs:string" use="required"/>
+                <xs:attribute name="old" type="xs:string" use="prohibited"/>
+            </xs:complexType>
+            <xs:simpleType name="Code">
+                <xs:restriction base="xs:string">
+                    <xs:maxLength value="3"/>
+                </xs:restriction>
+            </xs:simpleType>
+        </xs:schema>
+        "#,
+        )
+        .unwrap();
+
+        let value: Value = serde_json::from_str(&to_json(&[file]).unwrap()).unwrap();
+        assert_eq!(value["version"], 2);
+
+        let file = &value["files"][0];
+        assert_eq!(file["namespace"], "http://example.com");
+
+        let item = &file["types"][0];
+        assert_eq!(item["kind"], "struct");
+        assert_eq!(item["name"], "Item");
+        assert_eq!(item["comment"], "An item");
+        assert_eq!(item["fields"].as_array().unwrap().len(), 2);
+
+        let code = &item["fields"][0];
+        assert_eq!(code["name"], "Code");
+        assert_eq!(code["source"], "element");
+        assert_eq!(
+            code["type"],
+            json!({"kind": "global", "namespace": "http://example.com", "name": "Code"})
+        );
+        assert_eq!(code["occurs"], json!({"min": 1, "max": null}));
+        assert_eq!(item["fields"][1]["source"], "attribute");
+        assert_eq!(item["fields"][1]["occurs"], json!({"min": 1, "max": 1}));
+
+        let code = &file["types"][1];
+        assert_eq!(code["kind"], "simple");
+        assert_eq!(code["facets"][0], json!({"kind": "max_length", "value": "3", "comment": null}));
+    }
+}
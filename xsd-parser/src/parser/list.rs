@@ -2,30 +2,39 @@ use roxmltree::Node;
 
 use crate::parser::{
     constants::attribute,
+    error::{ParseError, ParseResult},
     node_parser::parse_node,
     types::{RsEntity, TupleStruct, TypeModifier},
     utils::find_child,
+    xsd_elements::XsdNode,
 };
 
-pub fn parse_list(list: &Node) -> RsEntity {
+pub fn parse_list(list: &Node) -> ParseResult<RsEntity> {
     let mut result = match list.attribute(attribute::ITEM_TYPE) {
-        Some(item_type) => TupleStruct { type_name: item_type.to_string(), ..Default::default() },
+        Some(item_type) => TupleStruct {
+            type_name: item_type.to_string(),
+            type_qname: Some(list.resolve_qname(item_type)?),
+            ..Default::default()
+        },
         None => {
-            let nested_simple_type = find_child(list, "simpleType").expect(
-                "itemType not allowed if the content contains a simpleType element. Otherwise, required."
-            );
+            let nested_simple_type = find_child(list, "simpleType").ok_or_else(|| {
+                ParseError::invalid(
+                    list,
+                    "itemType not allowed if the content contains a simpleType element. Otherwise, required.",
+                )
+            })?;
 
-            match parse_node(&nested_simple_type, list) {
+            match parse_node(&nested_simple_type, list)? {
                 RsEntity::Enum(en) => TupleStruct {
                     type_name: en.name.clone(),
                     subtypes: vec![RsEntity::Enum(en)],
                     ..Default::default()
                 },
                 RsEntity::TupleStruct(ts) => ts,
-                _ => unreachable!(),
+                _ => return Err(ParseError::unsupported(&nested_simple_type)),
             }
         }
     };
     result.type_modifiers.push(TypeModifier::Array);
-    RsEntity::TupleStruct(result)
+    Ok(RsEntity::TupleStruct(result))
 }
@@ -8,56 +8,215 @@ mod complex_content;
 mod complex_type;
 pub mod constants;
 mod element;
+pub mod error;
 mod extension;
+mod group;
 mod import;
+pub mod json;
 mod list;
 mod node_parser;
+pub mod recursion;
 mod restriction;
 pub mod schema;
+pub mod schema_set;
 mod sequence;
 mod simple_content;
 mod simple_type;
+mod substitution_group;
 mod tests;
 pub mod types;
 mod union;
 mod utils;
 pub mod xsd_elements;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::parser::{
+    error::{ParseError, ParseResult},
+    recursion::resolve_recursion,
     schema::parse_schema,
-    types::{RsEntity, RsFile},
+    substitution_group::resolve_substitution_groups,
+    types::{Placeholder, QName, Reference, ResolvedFields, RsEntity, RsFile, Struct},
+    xsd_elements::{ElementType, XsdNode},
 };
 
-// FIXME: Actually pass up errors
-#[allow(clippy::result_unit_err)]
-pub fn parse(text: &str) -> Result<RsFile, ()> {
-    let doc = roxmltree::Document::parse(text).expect("Parse document error");
+pub fn parse(text: &str) -> ParseResult<RsFile> {
+    let doc = roxmltree::Document::parse(text)?;
     let root = doc.root();
 
-    let mut map = HashMap::new();
+    let schema = root.children().rfind(|e| e.is_element()).ok_or(ParseError::MissingSchema)?;
 
-    let schema =
-        root.children().filter(|e| e.is_element()).last().expect("Schema element is required");
+    let mut schema_rs = parse_schema(&schema)?;
+    resolve(std::slice::from_mut(&mut schema_rs), &HashSet::new())?;
 
-    let schema_rs = parse_schema(&schema);
-    for ty in &schema_rs.types {
-        if let RsEntity::Struct(st) = ty {
-            map.extend(st.get_types_map());
+    Ok(schema_rs)
+}
+
+// Parses schemas which are resolved together, e.g. the types of a WSDL file. Declarations
+// of the namespaces they import but don't declare are taken as empty placeholders, see
+// schema_set::SchemaSet::parse.
+pub fn parse_schemas(schemas: &[roxmltree::Node]) -> ParseResult<Vec<RsFile>> {
+    let mut files = schemas.iter().map(parse_schema).collect::<ParseResult<Vec<_>>>()?;
+
+    let mut missing = schemas
+        .iter()
+        .flat_map(|schema| schema.children())
+        .filter(|node| node.is_element() && node.xsd_type() == ElementType::Import)
+        .filter_map(|node| node.attribute(constants::attribute::NAMESPACE).map(|ns| ns.to_string()))
+        .collect::<HashSet<_>>();
+    for file in files.iter() {
+        if let Some(namespace) = &file.namespace {
+            missing.remove(namespace);
         }
     }
-    for ag in &schema_rs.attribute_groups {
-        if let RsEntity::Struct(st) = ag {
-            map.extend(st.get_types_map());
+
+    resolve(&mut files, &missing)?;
+    Ok(files)
+}
+
+// Resolution of parsed schemas shared by all the entry points
+fn resolve(files: &mut [RsFile], missing: &HashSet<String>) -> ParseResult<()> {
+    resolve_substitution_groups(files);
+    resolve_types(files, missing)?;
+    resolve_recursion(files);
+    Ok(())
+}
+
+// Inlines groups, base types and attribute groups, which can be defined in any of the files.
+// The global declarations are indexed once by their resolved names, and each one is
+// extended once after the declarations it references. References to the `missing`
+// namespaces are taken as empty declarations and recorded in the placeholders of the file.
+fn resolve_types(files: &mut [RsFile], missing: &HashSet<String>) -> ParseResult<()> {
+    let placeholders = placeholders(files, missing);
+    let mut declarations = HashMap::new();
+    for file in files.iter() {
+        let entities = [
+            (Reference::Type, &file.types),
+            (Reference::AttributeGroup, &file.attribute_groups),
+            (Reference::Group, &file.groups),
+        ];
+        for (kind, entities) in entities {
+            for entity in entities {
+                if let RsEntity::Struct(st) = entity {
+                    let qname = QName::new(file.namespace.as_deref(), &st.name);
+                    declarations.insert((kind, qname), st);
+                }
+            }
         }
     }
-    for ty in &schema_rs.types {
-        if let RsEntity::Struct(st) = ty {
-            st.extend_base(&map);
-            st.extend_attribute_group(&map);
+
+    let mut resolved = ResolvedFields::default();
+    for placeholder in placeholders.iter().flatten() {
+        resolved.get_mut(placeholder.kind).insert(placeholder.name.clone(), vec![]);
+    }
+
+    let mut resolver = Resolver { declarations, resolved, visiting: vec![] };
+    let mut keys = resolver.declarations.keys().cloned().collect::<Vec<_>>();
+    keys.sort_by(|a, b| a.1.name.cmp(&b.1.name));
+    for (kind, qname) in keys {
+        resolver.resolve(kind, &qname)?;
+    }
+    let resolved = resolver.resolved;
+
+    // Nested types are extended with the resolved declarations too
+    let mut result = Ok(());
+    for ty in files.iter_mut().flat_map(|f| &mut f.types) {
+        ty.visit_structs(&mut |st| {
+            if result.is_ok() {
+                result = st.extend_group(&resolved.groups).and_then(|_| st.extend(&resolved));
+            }
+        });
+    }
+    for (file, placeholders) in files.iter_mut().zip(placeholders) {
+        file.placeholders = placeholders;
+    }
+    result
+}
+
+// References of each file to undeclared names of the `missing` namespaces
+fn placeholders(files: &mut [RsFile], missing: &HashSet<String>) -> Vec<Vec<Placeholder>> {
+    let declared = files
+        .iter()
+        .flat_map(|file| {
+            let entities = vec![
+                (Reference::Type, &file.types),
+                (Reference::AttributeGroup, &file.attribute_groups),
+                (Reference::Group, &file.groups),
+            ];
+            entities.into_iter().flat_map(move |(kind, entities)| {
+                entities
+                    .iter()
+                    .map(move |e| (kind, QName::new(file.namespace.as_deref(), e.name())))
+            })
+        })
+        .collect::<HashSet<_>>();
+
+    let mut result = vec![];
+    for file in files.iter_mut() {
+        let mut placeholders: Vec<Placeholder> = vec![];
+        let entities =
+            file.types.iter_mut().chain(&mut file.attribute_groups).chain(&mut file.groups);
+        for entity in entities {
+            entity.visit_structs(&mut |st| {
+                for (kind, qname) in st.references() {
+                    // Simple content of an undeclared type is kept as its text value
+                    if st.fields.iter().any(|f| f.is_text() && f.type_qname.as_ref() == Some(qname))
+                    {
+                        continue;
+                    }
+                    let placeholder =
+                        Placeholder { kind, name: qname.clone(), referrer: st.name.clone() };
+                    if qname.namespace.as_ref().is_some_and(|ns| missing.contains(ns))
+                        && !declared.contains(&(kind, qname.clone()))
+                        && !placeholders.contains(&placeholder)
+                    {
+                        placeholders.push(placeholder);
+                    }
+                }
+            });
         }
+        result.push(placeholders);
     }
+    result
+}
 
-    Ok(schema_rs)
+struct Resolver<'a> {
+    declarations: HashMap<(Reference, QName), &'a Struct>,
+    resolved: ResolvedFields,
+    // Declarations being resolved, a reference to one of them is a cycle
+    visiting: Vec<(Reference, QName)>,
+}
+
+impl Resolver<'_> {
+    fn resolve(&mut self, kind: Reference, qname: &QName) -> ParseResult<()> {
+        if self.resolved.get(kind).contains_key(qname) {
+            return Ok(());
+        }
+        let key = (kind, qname.clone());
+        // Undeclared references are reported by the referencing struct
+        let st = match self.declarations.get(&key) {
+            Some(st) => *st,
+            None => return Ok(()),
+        };
+        if let Some(start) = self.visiting.iter().position(|v| v == &key) {
+            let names = self.visiting[start..]
+                .iter()
+                .chain(std::iter::once(&key))
+                .map(|(_, qname)| qname.to_string())
+                .collect();
+            return Err(ParseError::CyclicReference { kind: kind.to_string(), names });
+        }
+
+        self.visiting.push(key);
+        for (kind, reference) in st.references() {
+            self.resolve(kind, reference)?;
+        }
+        let mut st = st.clone();
+        st.extend_group(&self.resolved.groups)?;
+        st.extend(&self.resolved)?;
+        self.visiting.pop();
+
+        self.resolved.get_mut(kind).insert(qname.clone(), st.fields);
+        Ok(())
+    }
 }
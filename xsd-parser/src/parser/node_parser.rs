@@ -10,7 +10,9 @@ use crate::parser::{
     complex_content::parse_complex_content,
     complex_type::parse_complex_type,
     element::parse_element,
+    error::{ParseError, ParseResult},
     extension::parse_extension,
+    group::parse_group,
     import::parse_import,
     list::parse_list,
     restriction::parse_restriction,
@@ -22,7 +24,7 @@ use crate::parser::{
     xsd_elements::{ElementType, XsdNode},
 };
 
-pub fn parse_node(node: &Node, parent: &Node) -> RsEntity {
+pub fn parse_node(node: &Node, parent: &Node) -> ParseResult<RsEntity> {
     use ElementType::*;
 
     match node.xsd_type() {
@@ -36,6 +38,7 @@ pub fn parse_node(node: &Node, parent: &Node) -> RsEntity {
         ComplexType => parse_complex_type(node, parent),
         Element => parse_element(node, parent),
         Extension(_) => parse_extension(node, parent),
+        Group => parse_group(node, parent),
         Import | Include => parse_import(node),
         List => parse_list(node),
         Restriction(_) => parse_restriction(node, parent),
@@ -44,6 +47,7 @@ pub fn parse_node(node: &Node, parent: &Node) -> RsEntity {
         SimpleType => parse_simple_type(node, parent),
         Union => parse_union(node),
 
-        _ => unreachable!("Unsupported node:\n {:?}\nparent = {:?}\n", node, node.parent()),
+        XsdError(reason) => Err(ParseError::invalid(node, reason)),
+        _ => Err(ParseError::unsupported(node)),
     }
 }
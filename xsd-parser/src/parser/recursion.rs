@@ -0,0 +1,347 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::parser::types::{QName, RsEntity, RsFile, StructFieldSource, TypeModifier};
+
+// Finds types which contain themselves, directly or through other types, and marks
+// the fields (or choice cases) which break every such cycle as `Recursive`, so they
+// are generated with a boxed type. Repeated fields are already stored in a `Vec`,
+// so they never need to be boxed.
+pub fn resolve_recursion(files: &mut [RsFile]) {
+    let mut graph = Graph::default();
+    for (index, file) in files.iter().enumerate() {
+        let scope = Scope { file: index, namespace: file.namespace.as_deref() };
+        for ty in &file.types {
+            let id = graph.add_entity(ty, &scope);
+            graph.globals.insert((index, QName::new(scope.namespace, ty.name())), id);
+        }
+    }
+
+    let marks = graph.find_cycles();
+    if marks.is_empty() {
+        return;
+    }
+
+    let mut next_id = 0;
+    for file in files.iter_mut() {
+        for ty in file.types.iter_mut() {
+            mark_entity(ty, &mut next_id, &marks);
+        }
+    }
+}
+
+// File of the types being added, unqualified type names belong to its namespace
+struct Scope<'a> {
+    file: usize,
+    namespace: Option<&'a str>,
+}
+
+enum Target {
+    Node(usize),
+    Global(usize, QName),
+}
+
+// A type contained by value in another one, as a field, a choice case or an alias
+struct Edge {
+    target: Target,
+    // Aliases can't be boxed, cycles through them are broken at another edge
+    boxable: bool,
+}
+
+#[derive(Default)]
+struct Graph {
+    // Edges of every type with the positions of their fields or cases
+    nodes: Vec<Vec<(usize, Edge)>>,
+    globals: HashMap<(usize, QName), usize>,
+}
+
+impl Graph {
+    // Adds the entity and its nested types in the order of `mark_entity`
+    fn add_entity(&mut self, entity: &RsEntity, scope: &Scope) -> usize {
+        let id = self.nodes.len();
+        self.nodes.push(vec![]);
+        // Some nested types are generated next to their parent, e.g. the struct of a
+        // complex type with a choice, so they can be referenced by their names too
+        let qname = QName::new(scope.namespace, entity.name());
+        self.globals.entry((scope.file, qname)).or_insert(id);
+
+        let mut edges = vec![];
+        match entity {
+            RsEntity::Struct(st) => {
+                for (index, field) in st.fields.iter().enumerate() {
+                    let nested = field
+                        .subtypes
+                        .iter()
+                        .map(|subtype| self.add_entity(subtype, scope))
+                        .collect::<Vec<usize>>();
+                    if !is_contained(&field.type_modifiers)
+                        || !matches!(
+                            field.source,
+                            StructFieldSource::Element | StructFieldSource::Choice
+                        )
+                    {
+                        continue;
+                    }
+                    let target = match (nested.first(), &field.type_qname) {
+                        (Some(nested), _) => Target::Node(*nested),
+                        (None, Some(qname)) => Target::Global(scope.file, qname.clone()),
+                        (None, None) => Target::Global(
+                            scope.file,
+                            QName::new(scope.namespace, &field.type_name),
+                        ),
+                    };
+                    edges.push((index, Edge { target, boxable: true }));
+                }
+                for subtype in &st.subtypes {
+                    self.add_entity(subtype, scope);
+                }
+            }
+            RsEntity::Enum(en) => {
+                let nested = en
+                    .subtypes
+                    .iter()
+                    .map(|subtype| (subtype.name(), self.add_entity(subtype, scope)))
+                    .collect::<HashMap<&str, usize>>();
+                for (index, case) in en.cases.iter().enumerate() {
+                    let type_name = match &case.type_name {
+                        Some(type_name) if is_contained(&case.type_modifiers) => type_name,
+                        _ => continue,
+                    };
+                    let target = match (nested.get(type_name.as_str()), &case.type_qname) {
+                        (Some(nested), _) => Target::Node(*nested),
+                        (None, Some(qname)) => Target::Global(scope.file, qname.clone()),
+                        (None, None) => continue,
+                    };
+                    edges.push((index, Edge { target, boxable: true }));
+                }
+            }
+            RsEntity::Alias(al) => {
+                for subtype in &al.subtypes {
+                    self.add_entity(subtype, scope);
+                }
+                if let Some(qname) = &al.original_qname {
+                    let target = Target::Global(scope.file, qname.clone());
+                    edges.push((0, Edge { target, boxable: false }));
+                }
+            }
+            RsEntity::TupleStruct(ts) => {
+                for subtype in &ts.subtypes {
+                    self.add_entity(subtype, scope);
+                }
+            }
+            _ => (),
+        }
+
+        self.nodes[id] = edges;
+        id
+    }
+
+    // Types of the same file are preferred, as included types are copied to every file
+    fn target(&self, target: &Target) -> Option<usize> {
+        match target {
+            Target::Node(id) => Some(*id),
+            Target::Global(file, qname) => {
+                self.globals.get(&(*file, qname.clone())).copied().or_else(|| {
+                    self.globals
+                        .iter()
+                        .filter(|((_, name), _)| name == qname)
+                        .min_by_key(|((file, _), _)| *file)
+                        .map(|(_, id)| *id)
+                })
+            }
+        }
+    }
+
+    // Depth-first search, every cycle contains an edge back to a type on the stack.
+    // Returns the edges to box as pairs of a type and a field (or case) position.
+    fn find_cycles(&self) -> HashSet<(usize, usize)> {
+        let targets = self
+            .nodes
+            .iter()
+            .map(|edges| edges.iter().map(|(_, edge)| self.target(&edge.target)).collect())
+            .collect::<Vec<Vec<Option<usize>>>>();
+
+        let mut search = Search {
+            graph: self,
+            targets: &targets,
+            state: vec![State::New; self.nodes.len()],
+            path: vec![],
+            marks: HashSet::new(),
+        };
+        for id in 0..self.nodes.len() {
+            if search.state[id] == State::New {
+                search.visit(id);
+            }
+        }
+        search.marks
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    New,
+    OnStack,
+    Done,
+}
+
+struct Search<'a> {
+    graph: &'a Graph,
+    targets: &'a [Vec<Option<usize>>],
+    state: Vec<State>,
+    // Edges from the root of the search to the current type
+    path: Vec<(usize, usize)>,
+    marks: HashSet<(usize, usize)>,
+}
+
+impl Search<'_> {
+    fn visit(&mut self, id: usize) {
+        self.state[id] = State::OnStack;
+        let targets = self.targets;
+        for (position, target) in targets[id].iter().enumerate() {
+            let target = match target {
+                Some(target) => *target,
+                None => continue,
+            };
+            match self.state[target] {
+                State::New => {
+                    self.path.push((id, position));
+                    self.visit(target);
+                    self.path.pop();
+                }
+                State::OnStack => self.break_cycle(id, position, target),
+                State::Done => (),
+            }
+        }
+        self.state[id] = State::Done;
+    }
+
+    // Boxes the edge back to `target`, or the closest edge of the cycle which can be boxed
+    fn break_cycle(&mut self, id: usize, position: usize, target: usize) {
+        let start = self.path.iter().rposition(|(from, _)| *from == target);
+        // A type containing itself is not on the path yet
+        let start = start.unwrap_or(self.path.len());
+        let cycle = self.path[start..].iter().copied().chain(Some((id, position)));
+        let boxable =
+            cycle.rev().find(|(from, position)| self.graph.nodes[*from][*position].1.boxable);
+        if let Some((from, position)) = boxable {
+            self.marks.insert((from, self.graph.nodes[from][position].0));
+        }
+    }
+}
+
+// Repeated and empty content doesn't make a type infinitely large
+fn is_contained(modifiers: &[TypeModifier]) -> bool {
+    !modifiers.iter().any(|m| matches!(m, TypeModifier::Array | TypeModifier::Empty))
+}
+
+// Visits the entities in the same order as `Graph::add_entity`
+fn mark_entity(entity: &mut RsEntity, next_id: &mut usize, marks: &HashSet<(usize, usize)>) {
+    let id = *next_id;
+    *next_id += 1;
+    let marked = |index: usize| marks.contains(&(id, index));
+
+    match entity {
+        RsEntity::Struct(st) => {
+            for (index, field) in st.fields.iter_mut().enumerate() {
+                for subtype in field.subtypes.iter_mut() {
+                    mark_entity(subtype, next_id, marks);
+                }
+                if marked(index) {
+                    field.type_modifiers.push(TypeModifier::Recursive);
+                }
+            }
+            for subtype in st.subtypes.iter_mut() {
+                mark_entity(subtype, next_id, marks);
+            }
+        }
+        RsEntity::Enum(en) => {
+            for subtype in en.subtypes.iter_mut() {
+                mark_entity(subtype, next_id, marks);
+            }
+            for (index, case) in en.cases.iter_mut().enumerate() {
+                if marked(index) {
+                    case.type_modifiers.push(TypeModifier::Recursive);
+                }
+            }
+        }
+        RsEntity::Alias(al) => {
+            for subtype in al.subtypes.iter_mut() {
+                mark_entity(subtype, next_id, marks);
+            }
+        }
+        RsEntity::TupleStruct(ts) => {
+            for subtype in ts.subtypes.iter_mut() {
+                mark_entity(subtype, next_id, marks);
+            }
+        }
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parser::{
+        parse,
+        types::{RsEntity, Struct, TypeModifier},
+    };
+
+    fn find_struct<'a>(types: &'a [RsEntity], name: &str) -> &'a Struct {
+        types
+            .iter()
+            .find_map(|t| match t {
+                RsEntity::Struct(st) if st.name == name => Some(st),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    fn is_recursive(st: &Struct, field: &str) -> bool {
+        st.fields
+            .iter()
+            .find(|f| f.name == field)
+            .unwrap()
+            .type_modifiers
+            .contains(&TypeModifier::Recursive)
+    }
+
+    #[test]
+    fn test_resolve_recursion() {
+        let file = parse(
+            r#"
+        <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"
+                   xmlns:tns="http://example.com"
+                   targetNamespace="http://example.com">
+            <xs:element name="Tree">
+                <xs:complexType>
+                    <xs:sequence>
+                        <xs:element ref="tns:Tree" minOccurs="0"/>
+                        <xs:element name="Leaf" type="tns:LeafType"/>
+                        <xs:element name="Subtree" type="tns:TreeType" maxOccurs="unbounded"/>
+                    </xs:sequence>
+                </xs:complexType>
+            </xs:element>
+            <xs:element name="Subtree" type="tns:TreeType"/>
+            <xs:complexType name="TreeType">
+                <xs:sequence>
+                    <xs:element ref="tns:Subtree" minOccurs="0"/>
+                </xs:sequence>
+            </xs:complexType>
+            <xs:complexType name="LeafType">
+                <xs:sequence>
+                    <xs:element name="Value" type="xs:string"/>
+                </xs:sequence>
+            </xs:complexType>
+        </xs:schema>
+        "#,
+        )
+        .unwrap();
+
+        let tree = find_struct(&file.types, "Tree");
+        assert!(is_recursive(tree, "tns:Tree"));
+        assert!(!is_recursive(tree, "Leaf"));
+        assert!(!is_recursive(tree, "Subtree"));
+
+        // The cycle goes through the alias of the element
+        let subtree = find_struct(&file.types, "TreeType");
+        assert!(is_recursive(subtree, "tns:Subtree"));
+    }
+}
@@ -1,9 +1,9 @@
-use std::cell::RefCell;
-
 use roxmltree::Node;
+use xsd_types::types::Pattern;
 
 use crate::parser::{
     constants::tag,
+    error::{ParseError, ParseResult},
     node_parser::parse_node,
     types::{
         Enum, EnumCase, EnumSource, Facet, RsEntity, Struct, StructField, StructFieldSource,
@@ -22,88 +22,137 @@ const AVAILABLE_CONTENT_TYPES: [ElementType; 7] = [
     ElementType::Attribute,
     ElementType::AttributeGroup, // Not presented in ONVIF
     ElementType::Choice,         // Not presented in ONVIF
-    ElementType::Group,          // Not presented in ONVIF
-    ElementType::Sequence,       // Not presented in ONVIF
+    ElementType::Group,
+    ElementType::Sequence, // Not presented in ONVIF
 ];
 
-pub fn parse_restriction(node: &Node, _: &Node) -> RsEntity {
+pub fn parse_restriction(node: &Node, _: &Node) -> ParseResult<RsEntity> {
     use ElementType::Restriction;
     match node.xsd_type() {
         Restriction(RestrictionType::SimpleType) => simple_type_restriction(node),
         Restriction(RestrictionType::SimpleContent) => simple_content_restriction(node),
         Restriction(RestrictionType::ComplexContent) => complex_content_restriction(node),
-        _ => unreachable!("Invalid restriction node: {:#?}", node),
+        _ => Err(ParseError::invalid(node, "Invalid restriction node")),
     }
 }
 
-fn simple_type_restriction(node: &Node) -> RsEntity {
-    let base = get_base(node);
-    let facets = facets(node);
+fn simple_type_restriction(node: &Node) -> ParseResult<RsEntity> {
+    let base = get_base(node)?;
+    let facets = facets(node)?;
 
     if is_simple_enumerations(node) {
         let cases = cases(facets.as_ref());
         if !cases.is_empty() {
-            return RsEntity::Enum(Enum {
+            return Ok(RsEntity::Enum(Enum {
                 name: format!("{}Enum", get_parent_name(node)),
                 cases,
                 type_name: base.to_string(),
                 source: EnumSource::Restriction,
                 ..Default::default()
-            });
+            }));
         }
     };
 
-    RsEntity::TupleStruct(TupleStruct { type_name: base.to_string(), facets, ..Default::default() })
+    Ok(RsEntity::TupleStruct(TupleStruct {
+        type_name: base.to_string(),
+        type_qname: Some(node.resolve_qname(base)?),
+        facets,
+        ..Default::default()
+    }))
 }
 
-fn simple_content_restriction(node: &Node) -> RsEntity {
-    unimplemented!("\n{:?}\n", node)
+// The base of a simple content restriction is a complex type with simple content,
+// so the restriction keeps its text value and attributes. Declared attributes replace
+// the attributes of the base (or remove them with use="prohibited") and facets
+// restrict the text value.
+fn simple_content_restriction(node: &Node) -> ParseResult<RsEntity> {
+    let base = get_base(node)?;
+    let mut fields = attributes_to_fields(node)?;
+
+    fields.push(StructField {
+        name: tag::BASE.to_string(),
+        type_name: base.to_string(),
+        type_qname: Some(node.resolve_qname(base)?),
+        comment: get_documentation(node),
+        source: StructFieldSource::Text,
+        facets: facets(node)?,
+        restricts_base: true,
+        ..Default::default()
+    });
+
+    Ok(RsEntity::Struct(Struct {
+        comment: get_documentation(node),
+        fields,
+        attribute_groups: attribute_groups_to_aliases(node)?,
+        ..Default::default()
+    }))
 }
 
 // NOTE: current implementation works for types from ONVIF, but might not work
 // in a general case.
-fn complex_content_restriction(node: &Node) -> RsEntity {
-    let base = get_base(node);
-    let mut fields = attributes_to_fields(node);
+fn complex_content_restriction(node: &Node) -> ParseResult<RsEntity> {
+    let base = get_base(node)?;
+    let mut fields = attributes_to_fields(node)?;
 
     fields.push(StructField {
         name: tag::BASE.to_string(),
         type_name: base.to_string(),
+        type_qname: Some(node.resolve_qname(base)?),
         comment: get_documentation(node),
         source: StructFieldSource::Base,
+        restricts_base: true,
         ..Default::default()
     });
 
-    let content = node
-        .children()
-        .filter(|n| {
-            n.is_element()
-                && n.xsd_type() != ElementType::Attribute
-                && AVAILABLE_CONTENT_TYPES.contains(&n.xsd_type())
-        })
-        .last();
+    let content = node.children().rfind(|n| {
+        n.is_element()
+            && n.xsd_type() != ElementType::Attribute
+            && AVAILABLE_CONTENT_TYPES.contains(&n.xsd_type())
+    });
 
     if let Some(cont) = content {
-        let mut res = parse_node(&cont, node);
+        let mut res = parse_node(&cont, node)?;
         if let RsEntity::Struct(s) = &mut res {
             s.comment = get_documentation(node);
-            s.fields.borrow_mut().append(&mut fields);
-            return res;
+            s.fields.append(&mut fields);
+            return Ok(res);
         }
     }
 
-    RsEntity::Struct(Struct {
+    Ok(RsEntity::Struct(Struct {
         comment: get_documentation(node),
-        fields: RefCell::new(fields),
-        attribute_groups: RefCell::new(attribute_groups_to_aliases(node)),
+        fields,
+        attribute_groups: attribute_groups_to_aliases(node)?,
         ..Default::default()
-    })
+    }))
 }
 
-fn facets(node: &Node) -> Vec<Facet> {
+// Patterns are compiled and lengths and digits parsed here to report the invalid ones
+// with their position in the schema, rather than when the code is generated or
+// validates a value
+fn facets(node: &Node) -> ParseResult<Vec<Facet>> {
     node.children()
         .filter_map(|n| match n.xsd_type() {
-            ElementType::Facet(x) => Some(Facet { facet_type: x, comment: get_documentation(&n) }),
+            ElementType::Facet(x) => Some(
+                match &x {
+                    FacetType::Pattern(pattern) => {
+                        Pattern::new(pattern).map(|_| ()).map_err(|e| e.to_string())
+                    }
+                    FacetType::Length(value)
+                    | FacetType::MaxLength(value)
+                    | FacetType::MinLength(value)
+                    | FacetType::TotalDigits(value)
+                    | FacetType::FractionDigits(value) => {
+                        value.trim().parse::<u32>().map(|_| ()).map_err(|_| {
+                            format!("Invalid {} value: {}", n.tag_name().name(), value)
+                        })
+                    }
+                    _ => Ok(()),
+                }
+                .map(|_| Facet { facet_type: x, comment: get_documentation(&n) })
+                .map_err(|e| ParseError::invalid(&n, e)),
+            ),
+            ElementType::XsdError(reason) => Some(Err(ParseError::invalid(&n, reason))),
             _ => None,
         })
         .collect()
@@ -120,6 +169,7 @@ fn cases(facets: &[Facet]) -> Vec<EnumCase> {
                 type_name: None,
                 type_modifiers: vec![],
                 source: EnumSource::Restriction,
+                ..Default::default()
             }),
             _ => None,
         })
@@ -133,8 +183,8 @@ fn is_simple_enumerations(node: &Node) -> bool {
 }
 
 fn is_simple_enumeration(node: &Node) -> bool {
-    let val = node.attr_value().expect("Value required for xsd:enumeration");
-    !val.is_empty() && val.chars().all(|c| c.is_alphanumeric() || c == '-')
+    node.attr_value()
+        .is_some_and(|val| !val.is_empty() && val.chars().all(|c| c.is_alphanumeric() || c == '-'))
 }
 
 #[cfg(test)]
@@ -158,11 +208,32 @@ mod test {
         let root = doc.root_element().first_element_child().unwrap();
         let restriction = find_child(&root, "restriction").unwrap();
 
-        match simple_type_restriction(&restriction) {
+        match simple_type_restriction(&restriction).unwrap() {
             RsEntity::TupleStruct(ts) => {
                 assert_eq!(ts.type_name, "xs:string");
             }
             _ => unreachable!("Test failed"),
         }
     }
+
+    #[test]
+    fn test_invalid_facet_value() {
+        let doc = roxmltree::Document::parse(
+            r#"
+    <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+        <xs:simpleType name="SomeType">
+            <xs:restriction base="xs:string">
+                <xs:maxLength value="ten"/>
+            </xs:restriction>
+        </xs:simpleType>
+    </xs:schema>
+                "#,
+        )
+        .unwrap();
+        let root = doc.root_element().first_element_child().unwrap();
+        let restriction = find_child(&root, "restriction").unwrap();
+
+        let err = simple_type_restriction(&restriction).unwrap_err();
+        assert!(err.to_string().contains("Invalid maxLength value: ten"), "{}", err);
+    }
 }
@@ -1,41 +1,62 @@
 use roxmltree::Node;
 
 use crate::parser::{
+    constants::{attribute, namespace},
+    element::global_element,
+    error::ParseResult,
     node_parser::parse_node,
-    types::RsFile,
+    types::{Namespace, QName, RsFile},
     utils::target_namespace,
     xsd_elements::{ElementType, XsdNode},
 };
 
-pub fn parse_schema<'input>(schema: &Node<'_, 'input>) -> RsFile<'input> {
-    let mut xsd_namespaces = schema
-        .namespaces()
-        .filter(|namespace| namespace.uri() == "http://www.w3.org/2001/XMLSchema");
+pub fn parse_schema(schema: &Node) -> ParseResult<RsFile> {
+    let mut xsd_namespaces = schema.namespaces().filter(|ns| ns.uri() == namespace::XSD);
 
-    RsFile {
+    Ok(RsFile {
         name: "".into(),
-        namespace: None,
-        target_ns: target_namespace(schema).cloned(),
+        namespace: schema.attribute(attribute::TARGET_NAMESPACE).map(|s| s.to_string()),
+        target_ns: target_namespace(schema).map(Namespace::from),
         xsd_ns: xsd_namespaces
             .clone()
             .find(|namespace| namespace.name().is_some())
             .or_else(|| xsd_namespaces.next())
-            .cloned(),
+            .map(Namespace::from),
+        namespaces: schema.namespaces().map(Namespace::from).collect(),
         types: schema
             .children()
             .filter(|n| {
                 n.is_element()
                     && n.xsd_type() != ElementType::Annotation
                     && n.xsd_type() != ElementType::AttributeGroup
+                    && n.xsd_type() != ElementType::Group
             })
-            .map(|node| parse_node(&node, schema))
-            .collect(),
+            .map(|node| {
+                let mut entity = parse_node(&node, schema)?;
+                if let Some(name) = node.attr_name() {
+                    let namespace = schema.attribute(attribute::TARGET_NAMESPACE);
+                    entity.set_qname(QName::new(namespace, name));
+                }
+                Ok(entity)
+            })
+            .collect::<ParseResult<_>>()?,
         attribute_groups: schema
             .children()
             .filter(|n| n.is_element() && n.xsd_type() == ElementType::AttributeGroup)
             .map(|node| parse_node(&node, schema))
-            .collect(),
-    }
+            .collect::<ParseResult<_>>()?,
+        groups: schema
+            .children()
+            .filter(|n| n.is_element() && n.xsd_type() == ElementType::Group)
+            .map(|node| parse_node(&node, schema))
+            .collect::<ParseResult<_>>()?,
+        elements: schema
+            .children()
+            .filter(|n| n.is_element() && n.xsd_type() == ElementType::Element)
+            .map(|node| global_element(&node))
+            .collect::<ParseResult<_>>()?,
+        placeholders: vec![],
+    })
 }
 
 #[cfg(test)]
@@ -56,7 +77,7 @@ mod test {
         )
         .unwrap();
 
-        let res = parse_schema(&doc.root_element());
+        let res = parse_schema(&doc.root_element()).unwrap();
         assert_eq!(res.xsd_ns.unwrap().name().unwrap(), "xs");
     }
 
@@ -76,7 +97,7 @@ mod test {
         )
         .unwrap();
 
-        let res = parse_schema(&doc.root_element());
+        let res = parse_schema(&doc.root_element()).unwrap();
         assert_eq!(res.xsd_ns.unwrap().name().unwrap(), "xs");
     }
 }
@@ -0,0 +1,375 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use roxmltree::Document;
+
+use crate::parser::{
+    constants::attribute,
+    error::{ParseError, ParseResult},
+    resolve,
+    schema::parse_schema,
+    types::RsFile,
+    xsd_elements::{ElementType, XsdNode},
+};
+
+// A schema document loaded as a part of a schema set.
+#[derive(Debug, Clone)]
+pub struct SchemaDocument {
+    pub path: PathBuf,
+    pub text: String,
+    pub target_namespace: Option<String>,
+    // Indices of documents referenced with xs:include
+    pub includes: Vec<usize>,
+    // Indices of documents referenced with xs:import
+    pub imports: Vec<usize>,
+    // Locations of xs:include and xs:import which were not found
+    pub unresolved: Vec<String>,
+    // Namespaces of xs:import whose schema was not found
+    pub missing_imports: Vec<String>,
+}
+
+// Loads a schema document together with all schemas it includes or imports.
+//
+// A schemaLocation is resolved relative to the directory of the referencing document.
+// If no such file exists (which is the common case for absolute URLs in ONVIF schemas),
+// the file name from the location is looked up in the directory of the referencing document
+// and in the search paths.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaLoader {
+    search_paths: Vec<PathBuf>,
+}
+
+impl SchemaLoader {
+    pub fn with_search_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.search_paths.push(path.into());
+        self
+    }
+
+    pub fn with_search_paths<P: Into<PathBuf>>(
+        mut self,
+        paths: impl IntoIterator<Item = P>,
+    ) -> Self {
+        self.search_paths.extend(paths.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn load(&self, path: &Path) -> ParseResult<SchemaSet> {
+        let mut documents: Vec<SchemaDocument> = vec![];
+        let mut indices = HashMap::new();
+        let mut pending_imports = vec![];
+
+        let root = canonicalize(path)?;
+        indices.insert(root.clone(), 0);
+        documents.push(read_document(root)?);
+
+        let mut current = 0;
+        while current < documents.len() {
+            let text = documents[current].text.clone();
+            let doc = Document::parse(text.as_str())
+                .map_err(|e| ParseError::from(e).in_file(&documents[current].path))?;
+            let schema = doc.root_element();
+            documents[current].target_namespace =
+                schema.attribute(attribute::TARGET_NAMESPACE).map(|s| s.to_string());
+
+            let base_dir =
+                documents[current].path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+            for node in schema.children().filter(|n| n.is_element()) {
+                let ty = node.xsd_type();
+                if ty != ElementType::Import && ty != ElementType::Include {
+                    continue;
+                }
+                let namespace = node.attribute(attribute::NAMESPACE).map(|s| s.to_string());
+                let missing = |document: &mut SchemaDocument| {
+                    if ty == ElementType::Import {
+                        document.missing_imports.extend(namespace.clone());
+                    }
+                };
+                let location = match node.attribute(attribute::SCHEMA_LOCATION) {
+                    Some(location) => location,
+                    None => {
+                        missing(&mut documents[current]);
+                        continue;
+                    }
+                };
+
+                let found = match self.resolve_location(&base_dir, location) {
+                    Some(found) => found,
+                    None => {
+                        documents[current].unresolved.push(location.to_string());
+                        missing(&mut documents[current]);
+                        continue;
+                    }
+                };
+
+                let index = match indices.get(&found) {
+                    Some(index) => *index,
+                    None => {
+                        indices.insert(found.clone(), documents.len());
+                        documents.push(read_document(found)?);
+                        documents.len() - 1
+                    }
+                };
+
+                if ty == ElementType::Include {
+                    documents[current].includes.push(index);
+                } else {
+                    pending_imports.push((current, index, namespace, location.to_string()));
+                }
+            }
+
+            current += 1;
+        }
+
+        // A file found by its name only might declare a different namespace.
+        for (document, index, namespace, location) in pending_imports {
+            if namespace.is_none() || namespace == documents[index].target_namespace {
+                documents[document].imports.push(index);
+            } else {
+                documents[document].unresolved.push(location);
+                documents[document].missing_imports.extend(namespace);
+            }
+        }
+
+        Ok(SchemaSet { documents })
+    }
+
+    fn resolve_location(&self, base_dir: &Path, location: &str) -> Option<PathBuf> {
+        if !location.contains("://") {
+            let path = base_dir.join(location);
+            if path.is_file() {
+                return path.canonicalize().ok();
+            }
+        }
+
+        let file_name = location.trim_end_matches('/').rsplit('/').next()?;
+        if file_name.is_empty() {
+            return None;
+        }
+
+        std::iter::once(base_dir)
+            .chain(self.search_paths.iter().map(PathBuf::as_path))
+            .flat_map(|dir| {
+                let path = dir.join(file_name);
+                let with_extension = path.extension().is_none().then(|| path.with_extension("xsd"));
+                std::iter::once(path).chain(with_extension)
+            })
+            .find(|path| path.is_file())
+            .and_then(|path| path.canonicalize().ok())
+    }
+}
+
+// A set of schema documents connected with xs:include and xs:import.
+// The first document is the one the set was loaded from.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaSet {
+    documents: Vec<SchemaDocument>,
+}
+
+impl SchemaSet {
+    pub fn load(path: &Path) -> ParseResult<Self> {
+        SchemaLoader::default().load(path)
+    }
+
+    pub fn documents(&self) -> &[SchemaDocument] {
+        self.documents.as_ref()
+    }
+
+    // Parses every document of the set once and resolves base types and attribute groups
+    // across the whole set. Types of included documents are added to the including file,
+    // so the result has the same order as `documents()` and each file is self-contained
+    // within its target namespace.
+    //
+    // Declarations of an imported namespace whose schema was not found are taken as empty,
+    // each such reference is listed in the placeholders of the referencing file.
+    pub fn parse(&self) -> ParseResult<Vec<RsFile>> {
+        let parsed = self
+            .documents
+            .iter()
+            .map(|d| parse_document(d).map_err(|e| e.in_file(&d.path)))
+            .collect::<ParseResult<Vec<RsFile>>>()?;
+
+        let mut files = parsed.clone();
+        for (index, file) in files.iter_mut().enumerate() {
+            for included in self.included_documents(index) {
+                file.types.extend(parsed[included].types.iter().cloned());
+                file.attribute_groups.extend(parsed[included].attribute_groups.iter().cloned());
+                file.groups.extend(parsed[included].groups.iter().cloned());
+                file.elements.extend(parsed[included].elements.iter().cloned());
+            }
+        }
+
+        let mut missing = HashSet::new();
+        for document in self.documents.iter() {
+            missing.extend(document.missing_imports.iter().cloned());
+        }
+        for document in self.documents.iter() {
+            if let Some(namespace) = &document.target_namespace {
+                missing.remove(namespace);
+            }
+        }
+
+        resolve(&mut files, &missing)?;
+        Ok(files)
+    }
+
+    // Transitive closure of xs:include for the document with a given index.
+    fn included_documents(&self, index: usize) -> Vec<usize> {
+        let mut result = vec![];
+        let mut stack = self.documents[index].includes.clone();
+        while let Some(next) = stack.pop() {
+            if next != index && !result.contains(&next) {
+                result.push(next);
+                stack.extend(self.documents[next].includes.iter().copied());
+            }
+        }
+        result
+    }
+}
+
+fn canonicalize(path: &Path) -> ParseResult<PathBuf> {
+    path.canonicalize().map_err(|error| ParseError::Io { path: path.to_path_buf(), error })
+}
+
+fn read_document(path: PathBuf) -> ParseResult<SchemaDocument> {
+    let text =
+        fs::read_to_string(&path).map_err(|error| ParseError::Io { path: path.clone(), error })?;
+
+    Ok(SchemaDocument {
+        path,
+        text,
+        target_namespace: None,
+        includes: vec![],
+        imports: vec![],
+        unresolved: vec![],
+        missing_imports: vec![],
+    })
+}
+
+fn parse_document(document: &SchemaDocument) -> ParseResult<RsFile> {
+    let doc = Document::parse(document.text.as_str())?;
+    let mut file = parse_schema(&doc.root_element())?;
+
+    file.name =
+        document.path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    file.namespace = document.target_namespace.clone();
+    Ok(file)
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::parser::types::{Placeholder, QName, Reference, RsEntity, StructFieldSource};
+
+    fn input_dir(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../input").join(name)
+    }
+
+    fn file_name(document: &SchemaDocument) -> String {
+        document.path.file_name().unwrap().to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_load_onvif() {
+        let set = SchemaLoader::default()
+            .with_search_path(input_dir("xsd_external"))
+            .load(&input_dir("xsd").join("onvif.xsd"))
+            .unwrap();
+
+        let documents = set.documents();
+        assert_eq!(file_name(&documents[0]), "onvif.xsd");
+        assert_eq!(
+            documents[0].target_namespace.as_deref(),
+            Some("http://www.onvif.org/ver10/schema")
+        );
+
+        let includes =
+            documents[0].includes.iter().map(|i| file_name(&documents[*i])).collect::<Vec<_>>();
+        assert_eq!(includes, vec!["common.xsd"]);
+
+        let imports =
+            documents[0].imports.iter().map(|i| file_name(&documents[*i])).collect::<Vec<_>>();
+        assert!(imports.contains(&"xmlmime.xsd".to_string()));
+        assert!(imports.contains(&"soap-envelope.xsd".to_string()));
+        assert!(imports.contains(&"b-2.xsd".to_string()));
+
+        // Every document is loaded only once
+        let mut paths = documents.iter().map(|d| d.path.clone()).collect::<Vec<_>>();
+        paths.sort();
+        paths.dedup();
+        assert_eq!(paths.len(), documents.len());
+    }
+
+    #[test]
+    fn test_unresolved_location() {
+        let set = SchemaSet::load(&input_dir("xsd").join("onvif.xsd")).unwrap();
+        assert!(set.documents()[0]
+            .unresolved
+            .contains(&"http://docs.oasis-open.org/wsn/b-2.xsd".to_string()));
+    }
+
+    #[test]
+    fn test_parse_unresolved_import() {
+        // wsnt:FilterType is declared in b-2.xsd, which is only found in the search paths
+        let set = SchemaSet::load(&input_dir("xsd").join("onvif.xsd")).unwrap();
+        assert!(set.documents()[0]
+            .missing_imports
+            .contains(&"http://docs.oasis-open.org/wsn/b-2".to_string()));
+
+        let files = set.parse().unwrap();
+        let filter_type = Placeholder {
+            kind: Reference::Type,
+            name: QName::new(Some("http://docs.oasis-open.org/wsn/b-2"), "FilterType"),
+            referrer: "EventFilter".to_string(),
+        };
+        assert!(files[0].placeholders.contains(&filter_type));
+
+        let event_filter = files[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                RsEntity::Struct(s) if s.name == "EventFilter" => Some(s),
+                _ => None,
+            })
+            .unwrap();
+        // The placeholder base adds no fields to the declared ones
+        let sources = event_filter.fields.iter().map(|f| &f.source).collect::<Vec<_>>();
+        assert_eq!(sources, vec![&StructFieldSource::AnyAttribute]);
+
+        let set = SchemaLoader::default()
+            .with_search_path(input_dir("xsd_external"))
+            .load(&input_dir("xsd").join("onvif.xsd"))
+            .unwrap();
+        assert!(set.parse().unwrap().iter().all(|f| f.placeholders.is_empty()));
+    }
+
+    #[test]
+    fn test_parse_merges_includes() {
+        let set = SchemaLoader::default()
+            .with_search_path(input_dir("xsd_external"))
+            .load(&input_dir("xsd").join("onvif.xsd"))
+            .unwrap();
+        let files = set.parse().unwrap();
+
+        assert_eq!(files.len(), set.documents().len());
+        assert_eq!(files[0].name, "onvif");
+        // IntRange is defined in common.xsd
+        assert!(files[0].types.iter().any(|t| t.name() == "IntRange"));
+
+        let video_source = files[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                RsEntity::Struct(s) if s.name == "VideoSource" => Some(s),
+                _ => None,
+            })
+            .unwrap();
+        // Inherited from tt:DeviceEntity
+        assert!(video_source.fields.iter().any(|f| f.name == "token"));
+    }
+}
@@ -1,41 +1,52 @@
-use std::cell::RefCell;
-
 use roxmltree::Node;
 
 use crate::parser::{
+    element::element_modifier,
+    error::{ParseError, ParseResult},
     node_parser::parse_node,
-    types::{RsEntity, Struct, StructField, TypeModifier},
-    utils::{enum_to_field, get_documentation, get_parent_name},
-    xsd_elements::{ElementType, XsdNode},
+    types::{RsEntity, Struct, StructField},
+    utils::{choice_to_field, get_documentation, get_parent_name},
+    xsd_elements::{occurs, ElementType, XsdNode},
 };
 
-pub fn parse_sequence(sequence: &Node, parent: &Node) -> RsEntity {
+pub fn parse_sequence(sequence: &Node, parent: &Node) -> ParseResult<RsEntity> {
     let name = get_parent_name(sequence);
-    RsEntity::Struct(Struct {
+    Ok(RsEntity::Struct(Struct {
         name: name.into(),
         comment: get_documentation(parent),
         subtypes: vec![],
-        fields: RefCell::new(elements_to_fields(sequence, name)),
+        fields: elements_to_fields(sequence, name)?,
         ..Default::default()
-    })
+    }))
 }
 
-fn elements_to_fields(sequence: &Node, parent_name: &str) -> Vec<StructField> {
-    sequence
-        .children()
-        .filter(|n| n.is_element() && n.xsd_type() != ElementType::Annotation)
-        .map(|n| match parse_node(&n, sequence) {
-            RsEntity::StructField(mut sf) => {
-                if sf.type_name.ends_with(parent_name) {
-                    sf.type_modifiers.push(TypeModifier::Recursive)
-                }
-                sf
-            }
+fn elements_to_fields(sequence: &Node, parent_name: &str) -> ParseResult<Vec<StructField>> {
+    let mut choices = 0;
+    let mut fields = vec![];
+    for n in
+        sequence.children().filter(|n| n.is_element() && n.xsd_type() != ElementType::Annotation)
+    {
+        match parse_node(&n, sequence)? {
+            RsEntity::StructField(sf) => fields.push(sf),
             RsEntity::Enum(mut en) => {
-                en.name = format!("{}Choice", parent_name);
-                enum_to_field(en)
+                choices += 1;
+                en.name = match choices {
+                    1 => format!("{}Choice", parent_name),
+                    count => format!("{}Choice{}", parent_name, count),
+                };
+                fields.push(choice_to_field(en, &n));
+            }
+            // A nested sequence is inlined
+            RsEntity::Struct(st) => {
+                let occurrence = element_modifier(&n);
+                let occurs = occurs(&n);
+                fields.extend(st.fields.into_iter().map(|mut field| {
+                    field.apply_occurrence(&occurrence, &occurs);
+                    field
+                }));
             }
-            _ => unreachable!("\nError: {:?}\n{:?}", n, parse_node(&n, sequence)),
-        })
-        .collect()
+            _ => return Err(ParseError::unsupported(&n)),
+        }
+    }
+    Ok(fields)
 }
@@ -1,36 +1,40 @@
 use roxmltree::Node;
 
 use crate::parser::{
+    error::{ParseError, ParseResult},
     node_parser::parse_node,
     types::RsEntity,
     utils::get_documentation,
     xsd_elements::{ElementType, XsdNode},
 };
 
-pub fn parse_simple_type(node: &Node, parent: &Node) -> RsEntity {
+pub fn parse_simple_type(node: &Node, parent: &Node) -> ParseResult<RsEntity> {
     let name = node.attr_name();
 
-    assert_eq!(
-        parent.xsd_type() == ElementType::Schema,
-        name.is_some(),
-        "Name required if the simpleType element is a child of the schema element, and not allowed at other times"
-    );
+    if (parent.xsd_type() == ElementType::Schema) != name.is_some() {
+        return Err(ParseError::invalid(
+            node,
+            "Name required if the simpleType element is a child of the schema element, and not allowed at other times",
+        ));
+    }
 
     let content = node
         .children()
-        .filter(|n| n.is_element() && n.xsd_type() != ElementType::Annotation)
-        .last()
-        .expect(
-            "Simple types must be defined in one of the following ways: [Union, List, Restriction]",
-        );
+        .rfind(|n| n.is_element() && n.xsd_type() != ElementType::Annotation)
+        .ok_or_else(|| {
+            ParseError::invalid(
+                node,
+                "Simple types must be defined in one of the following ways: [Union, List, Restriction]",
+            )
+        })?;
 
-    let mut content_type = parse_node(&content, node);
+    let mut content_type = parse_node(&content, node)?;
 
     if let Some(n) = name {
         content_type.set_name(n);
     }
     content_type.set_comment(get_documentation(node));
-    content_type
+    Ok(content_type)
 }
 
 #[cfg(test)]
@@ -62,7 +66,7 @@ mod test {
         let schema = doc.root_element();
         let simple_type = find_child(&schema, "simpleType").unwrap();
 
-        match parse_simple_type(&simple_type, &schema) {
+        match parse_simple_type(&simple_type, &schema).unwrap() {
             RsEntity::TupleStruct(ts) => {
                 assert_eq!(ts.name, "SomeType");
                 assert_eq!(ts.type_name, "xs:SSD");
@@ -101,7 +105,7 @@ mod test {
         let schema = doc.root_element();
         let simple_type = find_child(&schema, "simpleType").unwrap();
 
-        match parse_simple_type(&simple_type, &schema) {
+        match parse_simple_type(&simple_type, &schema).unwrap() {
             RsEntity::TupleStruct(ts) => {
                 assert_eq!(ts.name, "SomeType");
                 assert_eq!(ts.type_name, "SomeTypeEnum");
@@ -137,7 +141,7 @@ mod test {
 
         let schema = doc.root_element();
         let simple_type = find_child(&schema, "simpleType").unwrap();
-        match parse_simple_type(&simple_type, &schema) {
+        match parse_simple_type(&simple_type, &schema).unwrap() {
             RsEntity::TupleStruct(ts) => {
                 assert_eq!(ts.name, "SomeType");
                 assert_eq!(ts.type_name, "xs:string");
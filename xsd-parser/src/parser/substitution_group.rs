@@ -0,0 +1,222 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::parser::{
+    types::{
+        Enum, EnumCase, EnumSource, GlobalElement, QName, RsEntity, RsFile, StructField,
+        StructFieldSource,
+    },
+    xsd_elements::FormType,
+};
+
+// Generates an enum for every head of a substitution group with a variant for each element
+// which can substitute the head (including the head itself, unless it is abstract).
+// References to the head element are replaced with flattened fields of this enum,
+// so any of the substitutable elements is accepted in place of the head.
+pub fn resolve_substitution_groups(files: &mut [RsFile]) {
+    let mut elements: Vec<&GlobalElement> = vec![];
+    for el in files.iter().flat_map(|f| &f.elements) {
+        if !elements.iter().any(|e| e.name == el.name) {
+            elements.push(el);
+        }
+    }
+
+    let mut members: HashMap<&QName, Vec<&GlobalElement>> = HashMap::new();
+    for el in &elements {
+        if let Some(head) = &el.substitution_group {
+            members.entry(head).or_default().push(el);
+        }
+    }
+
+    let groups = members
+        .keys()
+        .filter_map(|head| elements.iter().find(|e| e.name == **head))
+        .map(|head| (head.name.clone(), group_enum(head, &elements, &members)))
+        .collect::<HashMap<QName, Enum>>();
+
+    if groups.is_empty() {
+        return;
+    }
+
+    for file in files.iter_mut() {
+        let mut heads = file
+            .elements
+            .iter()
+            .filter_map(|e| groups.get(&e.name))
+            .map(|en| RsEntity::Enum(en.clone()))
+            .collect::<Vec<RsEntity>>();
+        heads.dedup_by(|a, b| a.name() == b.name());
+
+        let heads_names = groups.keys().collect::<HashSet<&QName>>();
+        for entity in file.types.iter_mut().chain(file.groups.iter_mut()) {
+            replace_head_references(entity, &heads_names);
+        }
+        file.types.append(&mut heads);
+    }
+}
+
+fn group_name(head: &str) -> String {
+    format!("{}Group", head)
+}
+
+fn group_enum(
+    head: &GlobalElement,
+    elements: &[&GlobalElement],
+    members: &HashMap<&QName, Vec<&GlobalElement>>,
+) -> Enum {
+    let mut substitutes = vec![head];
+    let mut index = 0;
+    while index < substitutes.len() {
+        for member in members.get(&substitutes[index].name).into_iter().flatten() {
+            if !substitutes.iter().any(|e| e.name == member.name) {
+                substitutes.push(member);
+            }
+        }
+        index += 1;
+    }
+
+    let cases = substitutes
+        .into_iter()
+        .filter(|e| !e.is_abstract)
+        .map(|e| {
+            let ty = element_type(e, elements);
+            EnumCase {
+                name: e.name.name.clone(),
+                comment: e.comment.clone(),
+                type_name: ty.and_then(|ty| ty.type_name.clone()),
+                type_qname: ty.and_then(|ty| ty.type_qname.clone()),
+                source: EnumSource::Choice,
+                // Members of substitution groups are global elements
+                form: FormType::Qualified,
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    Enum {
+        name: group_name(&head.name.name),
+        cases,
+        comment: head.comment.clone(),
+        type_name: "String".to_string(),
+        source: EnumSource::Choice,
+        ..Default::default()
+    }
+}
+
+// An element without a type has the type of its substitution group head.
+// Returns the element which declares the type.
+fn element_type<'a>(
+    element: &'a GlobalElement,
+    elements: &[&'a GlobalElement],
+) -> Option<&'a GlobalElement> {
+    let mut current = element;
+    for _ in 0..elements.len() {
+        if current.type_name.is_some() {
+            return Some(current);
+        }
+        let head = current.substitution_group.as_ref()?;
+        current = elements.iter().find(|e| e.name == *head)?;
+    }
+    None
+}
+
+fn replace_head_references(entity: &mut RsEntity, heads: &HashSet<&QName>) {
+    match entity {
+        RsEntity::Struct(st) => {
+            for field in st.fields.iter_mut() {
+                replace_head_reference(field, heads);
+                field.subtypes.iter_mut().for_each(|s| replace_head_references(s, heads));
+            }
+            st.subtypes.iter_mut().for_each(|s| replace_head_references(s, heads));
+        }
+        RsEntity::Enum(en) => {
+            en.subtypes.iter_mut().for_each(|s| replace_head_references(s, heads))
+        }
+        RsEntity::TupleStruct(ts) => {
+            ts.subtypes.iter_mut().for_each(|s| replace_head_references(s, heads))
+        }
+        _ => (),
+    }
+}
+
+fn replace_head_reference(field: &mut StructField, heads: &HashSet<&QName>) {
+    // Element references have the same name and type, see element::parse_field_of_sequence
+    let is_reference = matches!(field.source, StructFieldSource::Element)
+        && field.name == field.type_name
+        && field.subtypes.is_empty();
+
+    let head = match field.type_qname.as_ref().filter(|qname| heads.contains(qname)) {
+        Some(head) if is_reference => head,
+        _ => return,
+    };
+    field.type_qname = Some(QName::new(head.namespace.as_deref(), &group_name(&head.name)));
+    field.type_name = group_name(&field.type_name);
+    field.source = StructFieldSource::Choice;
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parser::{
+        parse,
+        types::{RsEntity, StructFieldSource},
+    };
+
+    #[test]
+    fn test_substitution_group_enum() {
+        let file = parse(
+            r#"
+        <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"
+                   xmlns:tns="http://example.com"
+                   targetNamespace="http://example.com">
+            <xs:element name="Shape" type="tns:ShapeType" abstract="true"/>
+            <xs:element name="Circle" type="tns:CircleType" substitutionGroup="tns:Shape"/>
+            <xs:element name="Square" substitutionGroup="tns:Shape"/>
+            <xs:element name="BigCircle" type="tns:BigCircleType" substitutionGroup="tns:Circle"/>
+
+            <xs:complexType name="Drawing">
+                <xs:sequence>
+                    <xs:element ref="tns:Shape"/>
+                </xs:sequence>
+            </xs:complexType>
+        </xs:schema>
+        "#,
+        )
+        .unwrap();
+
+        let group = file
+            .types
+            .iter()
+            .find_map(|t| match t {
+                RsEntity::Enum(en) if en.name == "ShapeGroup" => Some(en),
+                _ => None,
+            })
+            .unwrap();
+        let cases = group
+            .cases
+            .iter()
+            .map(|c| (c.name.as_str(), c.type_name.as_deref().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            cases,
+            vec![
+                ("Circle", "tns:CircleType"),
+                ("Square", "tns:ShapeType"),
+                ("BigCircle", "tns:BigCircleType")
+            ]
+        );
+
+        // Circle is a head of its own group
+        assert!(file.types.iter().any(|t| t.name() == "CircleGroup"));
+
+        let drawing = file
+            .types
+            .iter()
+            .find_map(|t| match t {
+                RsEntity::Struct(st) if st.name == "Drawing" => Some(st),
+                _ => None,
+            })
+            .unwrap();
+        let fields = &drawing.fields;
+        assert_eq!(fields[0].type_name, "tns:ShapeGroup");
+        assert!(matches!(fields[0].source, StructFieldSource::Choice));
+    }
+}
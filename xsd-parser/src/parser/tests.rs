@@ -35,8 +35,8 @@ mod test {
 
         match &result.types[0] {
             RsEntity::Struct(s) => {
-                assert_eq!(s.fields.borrow()[0].name, "token");
-                assert_eq!(s.fields.borrow()[0].type_name, "tt:ReferenceToken");
+                assert_eq!(s.fields[0].name, "token");
+                assert_eq!(s.fields[0].type_name, "tt:ReferenceToken");
                 assert_eq!(s.name, "DeviceEntity");
             }
             _ => unreachable!(),
@@ -44,18 +44,18 @@ mod test {
 
         match &result.types[1] {
             RsEntity::Struct(s) => {
-                assert_eq!(s.fields.borrow().len(), 3);
+                assert_eq!(s.fields.len(), 3);
 
-                assert_eq!(s.fields.borrow()[0].name, "Resolution");
-                assert_eq!(s.fields.borrow()[0].type_name, "tt:VideoResolution");
+                assert_eq!(s.fields[0].name, "Resolution");
+                assert_eq!(s.fields[0].type_name, "tt:VideoResolution");
 
-                assert_eq!(s.fields.borrow()[1].name, "Imaging");
-                assert_eq!(s.fields.borrow()[1].type_name, "tt:ImagingSettings");
-                assert_eq!(s.fields.borrow()[1].type_modifiers[0], TypeModifier::Option);
+                assert_eq!(s.fields[1].name, "Imaging");
+                assert_eq!(s.fields[1].type_name, "tt:ImagingSettings");
+                assert_eq!(s.fields[1].type_modifiers[0], TypeModifier::Option);
 
-                assert_eq!(s.fields.borrow()[2].name, "token");
-                assert_eq!(s.fields.borrow()[2].type_name, "tt:ReferenceToken");
-                assert_eq!(s.fields.borrow()[2].type_modifiers[0], TypeModifier::None);
+                assert_eq!(s.fields[2].name, "token");
+                assert_eq!(s.fields[2].type_name, "tt:ReferenceToken");
+                assert_eq!(s.fields[2].type_modifiers[0], TypeModifier::None);
 
                 assert_eq!(s.name, "VideoSource");
             }
@@ -103,23 +103,274 @@ mod test {
 
         match &result.types[1] {
             RsEntity::Struct(s) => {
-                assert_eq!(s.fields.borrow().len(), 4);
+                assert_eq!(s.fields.len(), 4);
 
-                assert_eq!(s.fields.borrow()[0].name, "InstanceId");
-                assert_eq!(s.fields.borrow()[0].type_name, "xs:unsignedInt");
+                assert_eq!(s.fields[0].name, "InstanceId");
+                assert_eq!(s.fields[0].type_name, "xs:unsignedInt");
 
-                assert_eq!(s.fields.borrow()[1].name, "SequenceId");
-                assert_eq!(s.fields.borrow()[1].type_name, "xs:anyURI");
-                assert_eq!(s.fields.borrow()[1].type_modifiers[0], TypeModifier::Option);
+                assert_eq!(s.fields[1].name, "SequenceId");
+                assert_eq!(s.fields[1].type_name, "xs:anyURI");
+                assert_eq!(s.fields[1].type_modifiers[0], TypeModifier::Option);
 
-                assert_eq!(s.fields.borrow()[2].name, "MessageNumber");
-                assert_eq!(s.fields.borrow()[2].type_name, "xs:unsignedInt");
+                assert_eq!(s.fields[2].name, "MessageNumber");
+                assert_eq!(s.fields[2].type_name, "xs:unsignedInt");
 
-                assert_eq!(s.fields.borrow()[3].name, "any_attribute");
+                assert_eq!(s.fields[3].name, "any_attribute");
 
                 assert_eq!(s.name, "AppSequenceType");
             }
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn test_parse_error_position() {
+        use crate::parser::{error::ParseError, parse};
+
+        let text = r#"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+    <xs:simpleType name="Token">
+        <xs:restriction base="xs:string">
+            <xs:maxLength/>
+        </xs:restriction>
+    </xs:simpleType>
+</xs:schema>
+        "#;
+
+        match parse(text).unwrap_err() {
+            ParseError::InvalidNode { element, pos, reason } => {
+                assert_eq!(element, "maxLength");
+                assert_eq!((pos.row, pos.col), (5, 13));
+                assert_eq!(reason, "Value is required for facets");
+            }
+            err => unreachable!("{}", err),
+        }
+    }
+
+    #[test]
+    fn test_parse_error_invalid_use() {
+        use crate::parser::{error::ParseError, parse};
+
+        let text = r#"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+    <xs:complexType name="Foo">
+        <xs:attribute name="a" type="xs:string" use="always"/>
+    </xs:complexType>
+</xs:schema>
+        "#;
+
+        let err = parse(text).unwrap_err();
+        assert!(matches!(err, ParseError::InvalidNode { .. }));
+        assert_eq!(err.element(), Some("attribute"));
+        assert_eq!(err.pos().map(|p| p.row), Some(4));
+    }
+
+    #[test]
+    fn test_parse_error_invalid_pattern() {
+        use crate::parser::{error::ParseError, parse};
+
+        let text = r#"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+    <xs:simpleType name="Consonant">
+        <xs:restriction base="xs:string">
+            <xs:pattern value="[a-z-[aeiou]]"/>
+        </xs:restriction>
+    </xs:simpleType>
+    <xs:simpleType name="Code">
+        <xs:restriction base="xs:string">
+            <xs:pattern value="[A-Z]{2,1}"/>
+        </xs:restriction>
+    </xs:simpleType>
+</xs:schema>
+        "#;
+
+        let err = parse(text).unwrap_err();
+        assert!(matches!(err, ParseError::InvalidNode { .. }));
+        assert_eq!(err.element(), Some("pattern"));
+        assert_eq!(err.pos().map(|p| p.row), Some(10));
+    }
+
+    #[test]
+    fn test_parse_error_document() {
+        use crate::parser::{error::ParseError, parse};
+
+        assert!(matches!(parse("<xs:schema>").unwrap_err(), ParseError::Document(_)));
+        assert!(matches!(parse("<!-- no schema -->").unwrap_err(), ParseError::Document(_)));
+    }
+
+    #[test]
+    fn test_resolved_references() {
+        use crate::parser::{parse, types::RsEntity};
+
+        // The derived type is declared before its base, which is derived itself
+        let text = r#"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"
+    xmlns:tns="http://example.com"
+    targetNamespace="http://example.com">
+    <xs:complexType name="Derived">
+        <xs:complexContent>
+            <xs:extension base="tns:Middle">
+                <xs:sequence>
+                    <xs:element name="Last" type="xs:string"/>
+                </xs:sequence>
+            </xs:extension>
+        </xs:complexContent>
+    </xs:complexType>
+    <xs:complexType name="Middle">
+        <xs:complexContent>
+            <xs:extension base="tns:Base">
+                <xs:sequence>
+                    <xs:element name="Item" type="tns:Item"/>
+                </xs:sequence>
+            </xs:extension>
+        </xs:complexContent>
+    </xs:complexType>
+    <xs:complexType name="Base">
+        <xs:attribute name="id" type="xs:string"/>
+    </xs:complexType>
+    <xs:simpleType name="Item">
+        <xs:restriction base="xs:string"/>
+    </xs:simpleType>
+</xs:schema>
+        "#;
+
+        let result = parse(text).unwrap();
+        let derived = match &result.types[0] {
+            RsEntity::Struct(s) => s,
+            _ => unreachable!(),
+        };
+        let names = derived.fields.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["Last", "Item", "id"]);
+
+        let item = derived.fields[1].type_qname.as_ref().unwrap();
+        match result.find_type(item) {
+            Some(RsEntity::TupleStruct(ts)) => assert_eq!(ts.name, "Item"),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_unresolved_references() {
+        use crate::parser::{error::ParseError, parse};
+
+        let schema = |content: &str| {
+            format!(
+                r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"
+                    xmlns:tns="http://example.com" targetNamespace="http://example.com">
+                    {}
+                </xs:schema>"#,
+                content
+            )
+        };
+        let unresolved = |content: &str| match parse(&schema(content)).unwrap_err() {
+            ParseError::UnresolvedReference { kind, name, referrer } => (kind, name, referrer),
+            err => panic!("{}", err),
+        };
+
+        let base = r#"<xs:complexType name="Foo">
+                <xs:complexContent><xs:extension base="tns:Missing"/></xs:complexContent>
+            </xs:complexType>"#;
+        assert_eq!(
+            unresolved(base),
+            ("type".into(), "{http://example.com}Missing".into(), "Foo".into())
+        );
+
+        let attribute_group = r#"<xs:complexType name="Foo">
+                <xs:attributeGroup ref="tns:Missing"/>
+            </xs:complexType>"#;
+        assert_eq!(unresolved(attribute_group).0, "attribute group");
+    }
+
+    #[test]
+    fn test_cyclic_references() {
+        use crate::parser::{error::ParseError, parse};
+
+        let text = r#"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"
+    xmlns:tns="http://example.com"
+    targetNamespace="http://example.com">
+    <xs:complexType name="A">
+        <xs:complexContent><xs:extension base="tns:B"/></xs:complexContent>
+    </xs:complexType>
+    <xs:complexType name="B">
+        <xs:complexContent><xs:extension base="tns:A"/></xs:complexContent>
+    </xs:complexType>
+</xs:schema>
+        "#;
+
+        match parse(text).unwrap_err() {
+            ParseError::CyclicReference { kind, names } => {
+                assert_eq!(kind, "type");
+                assert_eq!(
+                    names,
+                    ["{http://example.com}A", "{http://example.com}B", "{http://example.com}A"]
+                );
+            }
+            err => panic!("{}", err),
+        }
+    }
+
+    #[test]
+    fn test_parse_schemas() {
+        use crate::parser::{
+            parse_schemas,
+            types::{Placeholder, QName, Reference, RsEntity},
+        };
+
+        // The types of a WSDL file, which import each other and a schema of another file
+        let text = r#"
+<types xmlns:xs="http://www.w3.org/2001/XMLSchema">
+    <xs:schema xmlns:a="http://example.com/a"
+        xmlns:ext="http://example.com/ext"
+        targetNamespace="http://example.com/a">
+        <xs:import namespace="http://example.com/ext" schemaLocation="ext.xsd"/>
+        <xs:complexType name="Base">
+            <xs:complexContent>
+                <xs:extension base="ext:Entity">
+                    <xs:attribute name="id" type="xs:string"/>
+                </xs:extension>
+            </xs:complexContent>
+        </xs:complexType>
+    </xs:schema>
+    <xs:schema xmlns:a="http://example.com/a"
+        xmlns:b="http://example.com/b"
+        targetNamespace="http://example.com/b">
+        <xs:import namespace="http://example.com/a"/>
+        <xs:element name="Root" type="b:Derived"/>
+        <xs:complexType name="Derived">
+            <xs:complexContent>
+                <xs:extension base="a:Base">
+                    <xs:attribute name="name" type="xs:string"/>
+                </xs:extension>
+            </xs:complexContent>
+        </xs:complexType>
+    </xs:schema>
+</types>
+        "#;
+
+        let doc = roxmltree::Document::parse(text).unwrap();
+        let schemas = doc.root_element().children().filter(|n| n.is_element()).collect::<Vec<_>>();
+        let files = parse_schemas(&schemas).unwrap();
+
+        assert_eq!(
+            files[0].placeholders,
+            vec![Placeholder {
+                kind: Reference::Type,
+                name: QName::new(Some("http://example.com/ext"), "Entity"),
+                referrer: "Base".to_string(),
+            }]
+        );
+        assert!(files[1].placeholders.is_empty());
+
+        let derived = files[1]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                RsEntity::Struct(s) if s.name == "Derived" => Some(s),
+                _ => None,
+            })
+            .unwrap();
+        let names = derived.fields.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["name", "id"]);
+    }
 }
@@ -1,81 +1,286 @@
-use std::{cell::RefCell, collections::HashMap};
+use std::collections::HashMap;
 
-use roxmltree::Namespace;
-
-use crate::parser::{constants::tag, xsd_elements::FacetType};
+use crate::parser::{
+    constants::{namespace, tag},
+    error::{ParseError, ParseResult},
+    xsd_elements::{FacetType, FormType, ProcessContents},
+};
 
 #[derive(Debug, Clone, Default)]
-pub struct RsFile<'input> {
+pub struct RsFile {
     pub name: String,
     pub namespace: Option<String>,
     pub types: Vec<RsEntity>,
     pub attribute_groups: Vec<RsEntity>,
-    pub target_ns: Option<Namespace<'input>>,
-    pub xsd_ns: Option<Namespace<'input>>,
+    pub groups: Vec<RsEntity>,
+    pub elements: Vec<GlobalElement>,
+    pub target_ns: Option<Namespace>,
+    pub xsd_ns: Option<Namespace>,
+    // Namespace declarations in scope of the schema element
+    pub namespaces: Vec<Namespace>,
+    // Declarations of imported schemas which were not found, see schema_set::SchemaSet::parse
+    pub placeholders: Vec<Placeholder>,
+}
+
+impl RsFile {
+    // Type referenced by a resolved name, e.g. the type_qname of a field
+    pub fn find_type(&self, qname: &QName) -> Option<&RsEntity> {
+        if qname.namespace.as_deref() != self.namespace.as_deref() {
+            return None;
+        }
+        self.types.iter().find(|ty| ty.name() == qname.name)
+    }
+}
+
+// A namespace declaration. Unlike roxmltree::Namespace it doesn't borrow the schema
+// document, so parsed schemas can outlive their text.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Namespace {
+    pub prefix: Option<String>,
+    pub uri: String,
+}
+
+impl Namespace {
+    pub fn new(prefix: Option<&str>, uri: &str) -> Self {
+        Self { prefix: prefix.map(|p| p.to_string()), uri: uri.to_string() }
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+}
+
+impl From<&roxmltree::Namespace<'_>> for Namespace {
+    fn from(ns: &roxmltree::Namespace<'_>) -> Self {
+        Self::new(ns.name(), ns.uri())
+    }
+}
+
+// A qualified name resolved against the namespace declarations in scope
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct QName {
+    pub namespace: Option<String>,
+    pub name: String,
+}
+
+impl QName {
+    pub fn new(namespace: Option<&str>, name: &str) -> Self {
+        Self { namespace: namespace.map(|ns| ns.to_string()), name: name.to_string() }
+    }
+
+    // Whether the name is a built-in declaration of XML Schema, e.g. xs:anyType
+    pub fn is_xsd(&self) -> bool {
+        self.namespace.as_deref() == Some(namespace::XSD)
+    }
+}
+
+impl std::fmt::Display for QName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.namespace {
+            Some(ns) => write!(f, "{{{}}}{}", ns, self.name),
+            None => write!(f, "{}", self.name),
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct Struct {
     pub name: String,
+    // Global declaration of the type, kept for the documentation as the name may change
+    pub qname: Option<QName>,
     pub comment: Option<String>,
-    pub fields: RefCell<Vec<StructField>>,
-    pub attribute_groups: RefCell<Vec<Alias>>,
+    pub fields: Vec<StructField>,
+    // Inlined when the types are resolved
+    pub attribute_groups: Vec<Alias>,
     pub subtypes: Vec<RsEntity>,
+    // Whether text is allowed between the child elements
+    pub mixed: bool,
+    // Additional derives of the generated type, set by passes
+    pub derives: Vec<String>,
 }
 
 impl Struct {
-    pub fn get_types_map(&self) -> HashMap<&String, &Self> {
-        let mut map = HashMap::new();
-        map.insert(&self.name, self);
-        for ty in &self.subtypes {
-            if let RsEntity::Struct(st) = ty {
-                map.extend(st.get_types_map());
+    // Global declarations referenced by the struct, which are inlined into it
+    pub fn references(&self) -> Vec<(Reference, &QName)> {
+        let fields = self.fields.iter().filter_map(|f| {
+            let kind = match f.source {
+                StructFieldSource::Group => Reference::Group,
+                _ if f.name.as_str() == tag::BASE => Reference::Type,
+                _ => return None,
+            };
+            f.type_qname.as_ref().map(|qname| (kind, qname))
+        });
+        let attribute_groups = self
+            .attribute_groups
+            .iter()
+            .filter_map(|al| al.original_qname.as_ref())
+            .map(|qname| (Reference::AttributeGroup, qname));
+        fields.chain(attribute_groups).collect()
+    }
+
+    // Inlines the fields of the base type and of the attribute groups, which must be
+    // resolved already
+    pub fn extend(&mut self, resolved: &ResolvedFields) -> ParseResult<()> {
+        let mut fields = vec![];
+        for f in self.fields.iter().filter(|f| f.name.as_str() == tag::BASE) {
+            let base = f.type_qname.as_ref().and_then(|key| resolved.types.get(key));
+            match base {
+                Some(base) => {
+                    let mut base = base.clone();
+                    // Fields redeclared by a restriction replace those of the base. An
+                    // extension keeps both for the naming registry to tell apart.
+                    if f.restricts_base {
+                        base.retain(|b| {
+                            !self
+                                .fields
+                                .iter()
+                                .any(|field| field.name == b.name && field.source == b.source)
+                        });
+                    }
+                    // A simple content restriction narrows the text value of its base
+                    base.iter_mut()
+                        .filter(|field| field.is_text())
+                        .for_each(|field| field.facets.extend(f.facets.iter().cloned()));
+                    fields.append(&mut base);
+                }
+                // Simple content of a simple type is the text value of the element
+                None if f.is_text() => {
+                    fields.push(StructField { name: tag::TEXT.to_string(), ..f.clone() })
+                }
+                // Content of xs:anyType is declared by the derived type
+                None if f.type_qname.as_ref().is_some_and(|q| q.is_xsd()) => (),
+                None => {
+                    return Err(self.unresolved(
+                        Reference::Type,
+                        f.type_qname.as_ref(),
+                        &f.type_name,
+                    ))
+                }
             }
         }
-        map
-    }
 
-    pub fn extend_base(&self, types: &HashMap<&String, &Self>) {
-        self.fields.borrow_mut().iter_mut().for_each(|f| f.extend_base(types));
+        self.fields.retain(|field| field.name.as_str() != tag::BASE);
+        self.fields.append(&mut fields);
 
-        let mut fields = self
-            .fields
-            .borrow()
-            .iter()
-            .filter(|f| f.name.as_str() == tag::BASE)
-            .flat_map(|f| {
-                let key = f.type_name.split(':').last().unwrap().to_string();
-                types.get(&key).map(|s| s.fields.borrow().clone()).unwrap_or_default()
-            })
-            .filter(|f| {
-                //TODO: remove this workaround for fields names clash
-                !self.fields.borrow().iter().any(|field| field.name == f.name)
-            })
-            .collect::<Vec<StructField>>();
-
-        self.fields.borrow_mut().append(&mut fields);
-
-        self.fields.borrow_mut().retain(|field| field.name.as_str() != tag::BASE);
-
-        for subtype in &self.subtypes {
-            if let RsEntity::Struct(s) = subtype {
-                s.extend_base(types);
+        for al in std::mem::take(&mut self.attribute_groups) {
+            match al.original_qname.as_ref().and_then(|key| resolved.attribute_groups.get(key)) {
+                Some(group) => self.fields.extend(group.iter().cloned()),
+                None => {
+                    return Err(self.unresolved(
+                        Reference::AttributeGroup,
+                        al.original_qname.as_ref(),
+                        &al.original,
+                    ))
+                }
             }
         }
+        Ok(())
     }
 
-    pub fn extend_attribute_group(&self, types: &HashMap<&String, &Self>) {
-        let mut fields = self
-            .attribute_groups
-            .borrow()
-            .iter()
-            .flat_map(|f| {
-                let key = f.original.split(':').last().unwrap().to_string();
-                types.get(&key).map(|s| s.fields.borrow().clone()).unwrap_or_default()
-            })
-            .collect::<Vec<StructField>>();
+    // Inlines referenced groups, which must be resolved already
+    pub fn extend_group(&mut self, groups: &HashMap<QName, Vec<StructField>>) -> ParseResult<()> {
+        let mut fields = vec![];
+        for f in std::mem::take(&mut self.fields) {
+            if !f.is_group() {
+                fields.push(f);
+                continue;
+            }
+            let group = match f.type_qname.as_ref().and_then(|key| groups.get(key)) {
+                Some(group) => group,
+                None => {
+                    return Err(self.unresolved(
+                        Reference::Group,
+                        f.type_qname.as_ref(),
+                        &f.type_name,
+                    ))
+                }
+            };
+
+            let occurrence = f.type_modifiers.first().cloned().unwrap_or(TypeModifier::None);
+            fields.extend(group.iter().cloned().map(|mut field| {
+                field.apply_occurrence(&occurrence, &f.occurs);
+                field
+            }));
+        }
+        self.fields = fields;
+        Ok(())
+    }
+
+    fn unresolved(&self, kind: Reference, qname: Option<&QName>, name: &str) -> ParseError {
+        ParseError::UnresolvedReference {
+            kind: kind.to_string(),
+            name: qname.map(|q| q.to_string()).unwrap_or_else(|| name.to_string()),
+            referrer: self.name.clone(),
+        }
+    }
+}
+
+// Kind of a global declaration referenced by a struct. Each kind has its own symbol
+// space, e.g. a group and a type can have the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Reference {
+    Type,
+    AttributeGroup,
+    Group,
+}
+
+impl std::fmt::Display for Reference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Reference::Type => write!(f, "type"),
+            Reference::AttributeGroup => write!(f, "attribute group"),
+            Reference::Group => write!(f, "group"),
+        }
+    }
+}
+
+// A reference to a declaration of an imported namespace whose schema was not found.
+// The declaration is taken as empty, e.g. a base type without fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placeholder {
+    pub kind: Reference,
+    pub name: QName,
+    pub referrer: String,
+}
+
+impl std::fmt::Display for Placeholder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} '{}' referenced by '{}' is declared in an imported schema which was not found, \
+             it is taken as empty",
+            self.kind, self.name, self.referrer
+        )
+    }
+}
 
-        self.fields.borrow_mut().append(&mut fields);
+// Fields of the global declarations once their own references are inlined, by kind
+#[derive(Debug, Default)]
+pub struct ResolvedFields {
+    pub types: HashMap<QName, Vec<StructField>>,
+    pub attribute_groups: HashMap<QName, Vec<StructField>>,
+    pub groups: HashMap<QName, Vec<StructField>>,
+}
+
+impl ResolvedFields {
+    pub fn get(&self, kind: Reference) -> &HashMap<QName, Vec<StructField>> {
+        match kind {
+            Reference::Type => &self.types,
+            Reference::AttributeGroup => &self.attribute_groups,
+            Reference::Group => &self.groups,
+        }
+    }
+
+    pub fn get_mut(&mut self, kind: Reference) -> &mut HashMap<QName, Vec<StructField>> {
+        match kind {
+            Reference::Type => &mut self.types,
+            Reference::AttributeGroup => &mut self.attribute_groups,
+            Reference::Group => &mut self.groups,
+        }
     }
 }
 
@@ -87,25 +292,122 @@ pub struct StructField {
     pub subtypes: Vec<RsEntity>,
     pub source: StructFieldSource,
     pub type_modifiers: Vec<TypeModifier>,
+    pub facets: Vec<Facet>,
+    // Resolved type (or reference) of the field, if it is declared with a QName
+    pub type_qname: Option<QName>,
+    // Whether an element or attribute must be qualified with the namespace
+    pub form: FormType,
+    pub value_constraint: Option<ValueConstraint>,
+    pub occurs: Occurs,
+    // Allowed content of a field holding elements or attributes matched by a wildcard
+    pub wildcard: Option<Wildcard>,
+    // Identifier of the field if it differs from its formatted name, see generator::names
+    pub rust_name: Option<String>,
+    // Rust type replacing the declared type, set by passes, see generator::pass::TypeOverride
+    pub rust_type: Option<String>,
+    // Whether the pseudo-field of the base type comes from xs:restriction, whose
+    // declarations replace those of the base
+    pub restricts_base: bool,
 }
 
 impl StructField {
-    pub fn extend_base(&mut self, types: &HashMap<&String, &Struct>) {
-        for subtype in &mut self.subtypes {
-            if let RsEntity::Struct(st) = subtype {
-                st.extend_base(types);
-            }
+    pub fn is_group(&self) -> bool {
+        matches!(self.source, StructFieldSource::Group)
+    }
+
+    pub fn is_text(&self) -> bool {
+        matches!(self.source, StructFieldSource::Text)
+    }
+
+    // Combines the occurrence of a group reference or a nested sequence with the
+    // occurrence of the inlined field
+    pub fn apply_occurrence(&mut self, occurrence: &TypeModifier, occurs: &Occurs) {
+        self.occurs = self.occurs.combine(occurs);
+        let modifier = match occurrence {
+            TypeModifier::Array => TypeModifier::Array,
+            TypeModifier::Option => TypeModifier::Option,
+            _ => return,
+        };
+
+        if self.type_modifiers.contains(&TypeModifier::Array) {
+            return;
+        }
+        match self
+            .type_modifiers
+            .iter_mut()
+            .find(|m| matches!(m, TypeModifier::None | TypeModifier::Option))
+        {
+            Some(m) => *m = modifier,
+            None => self.type_modifiers.push(modifier),
+        }
+    }
+}
+
+// Allowed number of occurrences of an element, `max` is None if unbounded
+#[derive(Debug, Clone, PartialEq)]
+pub struct Occurs {
+    pub min: usize,
+    pub max: Option<usize>,
+}
+
+impl Default for Occurs {
+    fn default() -> Self {
+        Self { min: 1, max: Some(1) }
+    }
+}
+
+impl Occurs {
+    // Occurrence of an element inside a repeated particle
+    pub fn combine(&self, outer: &Occurs) -> Occurs {
+        Occurs {
+            min: self.min * outer.min,
+            max: self.max.zip(outer.max).map(|(inner, outer)| inner * outer),
         }
     }
 }
 
+// Namespace constraint and processing of xs:any and xs:anyAttribute
+#[derive(Debug, Clone, PartialEq)]
+pub struct Wildcard {
+    // "##any", "##other" or a list of namespaces, "##targetNamespace" and "##local"
+    pub namespace: String,
+    pub process_contents: ProcessContents,
+}
+
+// A default or fixed value of an element or attribute
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueConstraint {
+    Default(String),
+    Fixed(String),
+}
+
+impl ValueConstraint {
+    pub fn value(&self) -> &str {
+        match self {
+            Self::Default(value) | Self::Fixed(value) => value,
+        }
+    }
+
+    pub fn is_fixed(&self) -> bool {
+        matches!(self, Self::Fixed(_))
+    }
+}
+
 #[allow(clippy::upper_case_acronyms)]
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub enum StructFieldSource {
     Attribute,
     Element,
     Base,
     Choice,
+    Group,
+    Text,
+    // Elements matched by xs:any
+    Any,
+    // Attributes matched by xs:anyAttribute
+    AnyAttribute,
+    // Text and child elements of a complex type with mixed content
+    Mixed,
     #[default]
     NA,
 }
@@ -119,21 +421,39 @@ pub struct Facet {
 #[derive(Debug, Clone, Default)]
 pub struct TupleStruct {
     pub name: String,
+    // Global declaration of the type, kept for the documentation as the name may change
+    pub qname: Option<QName>,
     pub comment: Option<String>,
     pub type_name: String,
+    // Resolved type of the value, if it is declared with a QName
+    pub type_qname: Option<QName>,
+    // Rust type replacing the declared type, set by passes, see generator::pass::TypeOverride
+    pub rust_type: Option<String>,
     pub subtypes: Vec<RsEntity>,
     pub type_modifiers: Vec<TypeModifier>,
     pub facets: Vec<Facet>,
+    // Additional derives of the generated type, set by passes
+    pub derives: Vec<String>,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct Enum {
     pub name: String,
+    // Global declaration of the type, kept for the documentation as the name may change
+    pub qname: Option<QName>,
     pub cases: Vec<EnumCase>,
     pub comment: Option<String>,
     pub type_name: String,
     pub subtypes: Vec<RsEntity>,
     pub source: EnumSource,
+    // Additional derives of the generated type, set by passes
+    pub derives: Vec<String>,
+    // Elements read by the other fields of the struct holding a choice, with their form.
+    // None if they are not known, see generator::enum::set_choice_siblings.
+    pub siblings: Option<Vec<(String, FormType)>>,
+    // Elements of the sequence of a mixed type in order, with their occurrence. None if
+    // the content is not a sequence of elements, see generator::struct::merge_mixed_contents.
+    pub sequence: Option<Vec<(String, Occurs)>>,
 }
 
 #[allow(clippy::upper_case_acronyms)]
@@ -153,6 +473,7 @@ pub enum TypeModifier {
     Option,
     Recursive,
     Empty,
+    Nillable,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -161,18 +482,57 @@ pub struct EnumCase {
     pub comment: Option<String>,
     pub value: String,
     pub type_name: Option<String>,
+    // Resolved type (or element) of the case, if it is declared with a QName
+    pub type_qname: Option<QName>,
     pub type_modifiers: Vec<TypeModifier>,
     pub source: EnumSource,
+    // Content of the alternative, for cases of xs:choice
+    pub branch: ChoiceBranch,
+    // Form of the element of an alternative of xs:choice
+    pub form: FormType,
+    // Allowed content of an alternative matched by xs:any
+    pub wildcard: Option<Wildcard>,
+    // Identifier of the variant if it differs from its formatted name, see generator::names
+    pub rust_name: Option<String>,
+    // Rust type replacing the declared type, set by passes, see generator::pass::TypeOverride
+    pub rust_type: Option<String>,
+}
+
+// Content of an alternative of xs:choice. Nested sequences and choices are
+// generated as types named after the case.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum ChoiceBranch {
+    #[default]
+    Element,
+    Sequence,
+    Choice,
+    Any,
+    // Text of mixed content
+    Text,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct Alias {
     pub name: String,
     pub original: String,
+    pub original_qname: Option<QName>,
+    // Rust type replacing the original type, set by passes, see generator::pass::TypeOverride
+    pub rust_type: Option<String>,
     pub comment: Option<String>,
     pub subtypes: Vec<RsEntity>,
 }
 
+// A global element declaration, used to resolve substitution groups
+#[derive(Debug, Clone, Default)]
+pub struct GlobalElement {
+    pub name: QName,
+    pub type_name: Option<String>,
+    pub type_qname: Option<QName>,
+    pub substitution_group: Option<QName>,
+    pub is_abstract: bool,
+    pub comment: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Import {
     pub name: String,
@@ -192,6 +552,25 @@ pub enum RsEntity {
 }
 
 impl RsEntity {
+    // Calls `f` for the structs of the entity, including nested types. A struct is
+    // visited before its nested types, so they include the fields it adds.
+    pub fn visit_structs(&mut self, f: &mut dyn FnMut(&mut Struct)) {
+        match self {
+            RsEntity::Struct(st) => {
+                f(st);
+                for field in st.fields.iter_mut() {
+                    field.subtypes.iter_mut().for_each(|s| s.visit_structs(f));
+                }
+                st.subtypes.iter_mut().for_each(|s| s.visit_structs(f));
+            }
+            RsEntity::Enum(en) => en.subtypes.iter_mut().for_each(|s| s.visit_structs(f)),
+            RsEntity::TupleStruct(ts) => ts.subtypes.iter_mut().for_each(|s| s.visit_structs(f)),
+            RsEntity::Alias(al) => al.subtypes.iter_mut().for_each(|s| s.visit_structs(f)),
+            RsEntity::StructField(sf) => sf.subtypes.iter_mut().for_each(|s| s.visit_structs(f)),
+            RsEntity::EnumCase(_) | RsEntity::Import(_) => (),
+        }
+    }
+
     pub fn name(&self) -> &str {
         use RsEntity::*;
         match self {
@@ -218,6 +597,16 @@ impl RsEntity {
         }
     }
 
+    pub fn set_qname(&mut self, qname: QName) {
+        use RsEntity::*;
+        match self {
+            Struct(s) => s.qname = Some(qname),
+            TupleStruct(tp) => tp.qname = Some(qname),
+            Enum(e) => e.qname = Some(qname),
+            EnumCase(_) | Alias(_) | StructField(_) | Import(_) => (),
+        }
+    }
+
     pub fn set_comment(&mut self, comment: Option<String>) {
         use RsEntity::*;
         match self {
@@ -1,25 +1,27 @@
-use std::cell::RefCell;
-
 use roxmltree::Node;
 
 use crate::parser::{
     constants::attribute,
+    error::ParseResult,
     node_parser::parse_node,
     types::{Enum, EnumCase, EnumSource, RsEntity, Struct},
     utils::{attributes_to_fields, enum_to_field, get_documentation, get_parent_name},
     xsd_elements::{ElementType, XsdNode},
 };
 
-pub fn parse_union(union: &Node) -> RsEntity {
-    let mut cases =
-        union.attribute(attribute::MEMBER_TYPES).map(create_enum_cases).unwrap_or_default();
+pub fn parse_union(union: &Node) -> ParseResult<RsEntity> {
+    let mut cases = union
+        .attribute(attribute::MEMBER_TYPES)
+        .map(|mt| create_enum_cases(union, mt))
+        .transpose()?
+        .unwrap_or_default();
 
     let subtypes = union
         .children()
         .filter(|e| e.is_element() && e.xsd_type() == ElementType::SimpleType)
         .enumerate()
         .map(|st| enum_subtype_from_node(&st.1, union, st.0))
-        .collect::<Vec<RsEntity>>();
+        .collect::<ParseResult<Vec<RsEntity>>>()?;
 
     cases.append(
         &mut subtypes
@@ -43,49 +45,57 @@ pub fn parse_union(union: &Node) -> RsEntity {
         ..Default::default()
     };
 
-    let mut fields = attributes_to_fields(union);
+    let mut fields = attributes_to_fields(union)?;
 
     if fields.is_empty() {
-        RsEntity::Enum(union_enum)
+        Ok(RsEntity::Enum(union_enum))
     } else {
         union_enum.name = format!("{}Choice", get_parent_name(union));
         fields.push(enum_to_field(union_enum));
-        RsEntity::Struct(Struct { fields: RefCell::new(fields), ..Default::default() })
+        Ok(RsEntity::Struct(Struct { fields, ..Default::default() }))
     }
 }
 
-fn create_enum_cases(member_types: &str) -> Vec<EnumCase> {
+fn create_enum_cases(union: &Node, member_types: &str) -> ParseResult<Vec<EnumCase>> {
     member_types
         .split(' ')
         .filter(|s| !s.is_empty())
-        .map(|mt| EnumCase {
-            name: mt.to_string(),
-            type_name: Some(mt.to_string()),
-            source: EnumSource::Union,
-            ..Default::default()
+        .map(|mt| {
+            Ok(EnumCase {
+                name: mt.to_string(),
+                type_name: Some(mt.to_string()),
+                type_qname: Some(union.resolve_qname(mt)?),
+                source: EnumSource::Union,
+                ..Default::default()
+            })
         })
         .collect()
 }
 
-fn enum_subtype_from_node(node: &Node, parent: &Node, index: usize) -> RsEntity {
-    let mut entity = parse_node(node, parent);
+fn enum_subtype_from_node(node: &Node, parent: &Node, index: usize) -> ParseResult<RsEntity> {
+    let mut entity = parse_node(node, parent)?;
     entity.set_name(format!("EnumCaseType_{}", index).as_str());
-    entity
+    Ok(entity)
 }
 
 #[cfg(test)]
 mod test {
     use crate::parser::{
-        types::RsEntity,
+        types::{QName, RsEntity},
         union::{create_enum_cases, parse_union},
         utils::find_child,
     };
 
     #[test]
     fn test_create_enum() {
-        let cases = create_enum_cases("Type1 Type2  Type3");
+        let doc = roxmltree::Document::parse(
+            r#"<xs:union xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:tns="http://example.com"/>"#,
+        )
+        .unwrap();
+        let cases = create_enum_cases(&doc.root_element(), "Type1 tns:Type2  Type3").unwrap();
         assert_eq!(cases.len(), 3);
         assert_eq!(cases[0].name, "Type1");
+        assert_eq!(cases[1].type_qname, Some(QName::new(Some("http://example.com"), "Type2")));
     }
 
     #[test]
@@ -104,7 +114,7 @@ mod test {
         let simple_type = find_child(&doc.root_element(), "simpleType").unwrap();
         let union = find_child(&simple_type, "union").unwrap();
 
-        let result = parse_union(&union);
+        let result = parse_union(&union).unwrap();
 
         match result {
             RsEntity::Enum(en) => {
@@ -143,7 +153,7 @@ mod test {
         let simple_type = find_child(&doc.root_element(), "simpleType").unwrap();
         let union = find_child(&simple_type, "union").unwrap();
 
-        let result = parse_union(&union);
+        let result = parse_union(&union).unwrap();
         match result {
             RsEntity::Enum(en) => {
                 assert_eq!(en.cases.len(), 5);
@@ -193,13 +203,13 @@ mod test {
         let simple_type = find_child(&doc.root_element(), "simpleType").unwrap();
         let union = find_child(&simple_type, "union").unwrap();
 
-        let result = parse_union(&union);
+        let result = parse_union(&union).unwrap();
         let subtype = match &result {
             RsEntity::Struct(st) => {
                 assert!(st.name.is_empty());
                 assert_eq!(st.subtypes.len(), 0);
-                assert_eq!(st.fields.borrow().len(), 3);
-                let ty = &st.fields.borrow()[2];
+                assert_eq!(st.fields.len(), 3);
+                let ty = &st.fields[2];
                 ty.subtypes[0].clone()
             }
             _ => unreachable!("Test Failed!"),
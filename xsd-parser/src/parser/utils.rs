@@ -4,9 +4,11 @@ use roxmltree::{Namespace, Node};
 
 use crate::parser::{
     constants::attribute,
+    element::element_modifier,
+    error::{ParseError, ParseResult},
     node_parser::parse_node,
-    types::{Alias, Enum, RsEntity, StructField, StructFieldSource},
-    xsd_elements::{ElementType, XsdNode},
+    types::{Alias, Enum, RsEntity, StructField, StructFieldSource, TypeModifier},
+    xsd_elements::{occurs, ElementType, FormType, XsdNode},
 };
 
 pub fn target_namespace<'a, 'input>(node: &Node<'a, 'input>) -> Option<&'a Namespace<'input>> {
@@ -42,38 +44,61 @@ pub fn get_parent_name<'a>(node: &Node<'a, '_>) -> &'a str {
     }
 }
 
-pub fn get_base<'a>(node: &Node<'a, '_>) -> &'a str {
-    node.attribute(attribute::BASE).expect("The base value is required")
+pub fn get_base<'a>(node: &Node<'a, '_>) -> ParseResult<&'a str> {
+    node.attribute(attribute::BASE)
+        .ok_or_else(|| ParseError::invalid(node, "The base value is required"))
 }
 
-pub fn attributes_to_fields(node: &Node) -> Vec<StructField> {
+pub fn attributes_to_fields(node: &Node) -> ParseResult<Vec<StructField>> {
     node.children()
         .filter(|n| {
             n.xsd_type() == ElementType::Attribute || n.xsd_type() == ElementType::AnyAttribute
         })
-        .map(|n| match parse_node(&n, node) {
-            RsEntity::StructField(sf) => sf,
-            _ => unreachable!("Invalid attribute parsing: {:?}", n),
+        .map(|n| match parse_node(&n, node)? {
+            RsEntity::StructField(sf) => Ok(sf),
+            _ => Err(ParseError::invalid(&n, "Invalid attribute parsing")),
         })
         .collect()
 }
 
-pub fn attribute_groups_to_aliases(node: &Node) -> Vec<Alias> {
+pub fn attribute_groups_to_aliases(node: &Node) -> ParseResult<Vec<Alias>> {
     node.children()
         .filter(|n| n.xsd_type() == ElementType::AttributeGroup)
-        .map(|n| match parse_node(&n, node) {
-            RsEntity::Alias(a) => a,
-            _ => unreachable!("Invalid attribute group parsing: {:?}", n),
+        .map(|n| match parse_node(&n, node)? {
+            RsEntity::Alias(a) => Ok(a),
+            _ => Err(ParseError::invalid(&n, "Invalid attribute group parsing")),
         })
         .collect()
 }
 
+// A choice is a flattened field, which repeats if the choice has maxOccurs > 1
+pub fn choice_to_field(en: Enum, choice: &Node) -> StructField {
+    StructField {
+        name: en.name.clone(),
+        type_name: en.name.clone(),
+        source: StructFieldSource::Choice,
+        type_modifiers: choice_modifiers(choice),
+        occurs: occurs(choice),
+        subtypes: vec![RsEntity::Enum(en)],
+        ..Default::default()
+    }
+}
+
+// An absent choice is the __Unknown__ variant of the enum, so it is never optional
+pub fn choice_modifiers(choice: &Node) -> Vec<TypeModifier> {
+    match element_modifier(choice) {
+        TypeModifier::Array => vec![TypeModifier::Array],
+        _ => vec![],
+    }
+}
+
 pub fn enum_to_field(en: Enum) -> StructField {
     StructField {
         name: en.name.clone(),
         type_name: en.name.clone(),
         subtypes: vec![RsEntity::Enum(en)],
         source: StructFieldSource::Element,
+        form: FormType::Qualified,
         ..Default::default()
     }
 }
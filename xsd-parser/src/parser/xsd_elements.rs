@@ -1,4 +1,8 @@
-use crate::parser::constants::attribute;
+use crate::parser::{
+    constants::{attribute, namespace},
+    error::{ParseError, ParseResult},
+    types::{Occurs, QName, ValueConstraint},
+};
 
 #[derive(Debug, PartialEq)]
 pub enum ElementType {
@@ -60,6 +64,30 @@ pub enum WhiteSpace {
     Collapse,
 }
 
+// The facet as declared in the schema, e.g. maxInclusive = 1920
+impl std::fmt::Display for FacetType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use FacetType::*;
+        let (name, value) = match self {
+            Enumeration(v) => ("enumeration", v.as_str()),
+            FractionDigits(v) => ("fractionDigits", v.as_str()),
+            Length(v) => ("length", v.as_str()),
+            MaxExclusive(v) => ("maxExclusive", v.as_str()),
+            MaxInclusive(v) => ("maxInclusive", v.as_str()),
+            MaxLength(v) => ("maxLength", v.as_str()),
+            MinExclusive(v) => ("minExclusive", v.as_str()),
+            MinInclusive(v) => ("minInclusive", v.as_str()),
+            MinLength(v) => ("minLength", v.as_str()),
+            Pattern(v) => ("pattern", v.as_str()),
+            TotalDigits(v) => ("totalDigits", v.as_str()),
+            WhiteSpace(self::WhiteSpace::Preserve) => ("whiteSpace", "preserve"),
+            WhiteSpace(self::WhiteSpace::Replace) => ("whiteSpace", "replace"),
+            WhiteSpace(self::WhiteSpace::Collapse) => ("whiteSpace", "collapse"),
+        };
+        write!(f, "{} = {}", name, value)
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum ExtensionType {
     ComplexContent,
@@ -78,8 +106,10 @@ pub trait XsdNode {
     fn attr_name(&self) -> Option<&str>;
     fn attr_type(&self) -> Option<&str>;
     fn attr_ref(&self) -> Option<&str>;
-    fn attr_use(&self) -> UseType;
+    fn attr_use(&self) -> ParseResult<UseType>;
     fn attr_value(&self) -> Option<&str>;
+    fn resolve_qname(&self, qname: &str) -> ParseResult<QName>;
+    fn attr_qname(&self, name: &str) -> ParseResult<Option<QName>>;
 }
 
 impl<'a> XsdNode for roxmltree::Node<'a, '_> {
@@ -138,25 +168,25 @@ impl<'a> XsdNode for roxmltree::Node<'a, '_> {
             "union" => Union,
             "unique" => Unique,
 
-            "enumeration" => Facet(FacetType::Enumeration(get_string_value(self))),
-            "fractionDigits" => Facet(FacetType::FractionDigits(get_string_value(self))),
-            "length" => Facet(FacetType::Length(get_string_value(self))),
-            "maxExclusive" => Facet(FacetType::MaxExclusive(get_string_value(self))),
-            "maxInclusive" => Facet(FacetType::MaxInclusive(get_string_value(self))),
-            "maxLength" => Facet(FacetType::MaxLength(get_string_value(self))),
-            "minExclusive" => Facet(FacetType::MinExclusive(get_string_value(self))),
-            "minInclusive" => Facet(FacetType::MinInclusive(get_string_value(self))),
-            "minLength" => Facet(FacetType::MinLength(get_string_value(self))),
-            "pattern" => Facet(FacetType::Pattern(get_string_value(self))),
-            "totalDigits" => Facet(FacetType::TotalDigits(get_string_value(self))),
+            "enumeration" => facet(self, FacetType::Enumeration),
+            "fractionDigits" => facet(self, FacetType::FractionDigits),
+            "length" => facet(self, FacetType::Length),
+            "maxExclusive" => facet(self, FacetType::MaxExclusive),
+            "maxInclusive" => facet(self, FacetType::MaxInclusive),
+            "maxLength" => facet(self, FacetType::MaxLength),
+            "minExclusive" => facet(self, FacetType::MinExclusive),
+            "minInclusive" => facet(self, FacetType::MinInclusive),
+            "minLength" => facet(self, FacetType::MinLength),
+            "pattern" => facet(self, FacetType::Pattern),
+            "totalDigits" => facet(self, FacetType::TotalDigits),
             "whiteSpace" => match self.attr_value() {
                 Some(val) => match val {
                     "preserve" => Facet(FacetType::WhiteSpace(WhiteSpace::Preserve)),
                     "replace" => Facet(FacetType::WhiteSpace(WhiteSpace::Replace)),
                     "collapse" => Facet(FacetType::WhiteSpace(WhiteSpace::Collapse)),
-                    x => unreachable!("Invalid WhiteSpace value: {}.\n {:?}", x, self),
+                    x => XsdError(format!("Invalid WhiteSpace value: {}", x)),
                 },
-                None => unreachable!("Value is required for facets"),
+                None => XsdError("Value is required for facets".to_string()),
             },
 
             _ => UnknownElement(self.tag_name().name().to_string()),
@@ -175,26 +205,47 @@ impl<'a> XsdNode for roxmltree::Node<'a, '_> {
         self.attribute(attribute::REF)
     }
 
-    fn attr_use(&self) -> UseType {
+    fn attr_use(&self) -> ParseResult<UseType> {
         match self.attribute(attribute::USE).unwrap_or("optional") {
-            "optional" => UseType::Optional,
-            "prohibited" => UseType::Prohibited,
-            "required" => UseType::Required,
-            _ => unreachable!(
-                "If 'use' specified, this attribute must have one of the following values [optional, prohibited, required]"
-            )
+            "optional" => Ok(UseType::Optional),
+            "prohibited" => Ok(UseType::Prohibited),
+            "required" => Ok(UseType::Required),
+            _ => Err(ParseError::invalid(
+                self,
+                "If 'use' specified, this attribute must have one of the following values [optional, prohibited, required]",
+            )),
         }
     }
 
     fn attr_value(&self) -> Option<&str> {
         self.attribute(attribute::VALUE)
     }
+
+    // An unprefixed QName belongs to the default namespace (or to no namespace)
+    fn resolve_qname(&self, qname: &str) -> ParseResult<QName> {
+        match qname.split_once(':') {
+            Some(("xml", name)) => Ok(QName::new(Some(namespace::XML), name)),
+            Some((prefix, name)) => match self.lookup_namespace_uri(Some(prefix)) {
+                Some(uri) => Ok(QName::new(Some(uri), name)),
+                None => Err(ParseError::invalid(
+                    self,
+                    format!("Undeclared namespace prefix '{}' in '{}'", prefix, qname),
+                )),
+            },
+            None => Ok(QName::new(self.lookup_namespace_uri(None), qname)),
+        }
+    }
+
+    fn attr_qname(&self, name: &str) -> ParseResult<Option<QName>> {
+        self.attribute(name).map(|qname| self.resolve_qname(qname)).transpose()
+    }
 }
 
-fn get_string_value(node: &roxmltree::Node) -> String {
-    node.attr_value()
-        .map(|s| s.to_string())
-        .unwrap_or_else(|| panic!("Value is required. {:?}", node))
+fn facet(node: &roxmltree::Node, facet_type: fn(String) -> FacetType) -> ElementType {
+    match node.attr_value() {
+        Some(val) => ElementType::Facet(facet_type(val.to_string())),
+        None => ElementType::XsdError("Value is required for facets".to_string()),
+    }
 }
 
 pub enum UseType {
@@ -203,6 +254,67 @@ pub enum UseType {
     Optional,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum FormType {
+    Qualified,
+    #[default]
+    Unqualified,
+}
+
+// How the content matched by a wildcard is validated, from the strictest
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub enum ProcessContents {
+    #[default]
+    Strict,
+    Lax,
+    Skip,
+}
+
+pub fn process_contents(node: &roxmltree::Node) -> ProcessContents {
+    match node.attribute(attribute::PROCESS_CONTENTS) {
+        Some("lax") => ProcessContents::Lax,
+        Some("skip") => ProcessContents::Skip,
+        _ => ProcessContents::Strict,
+    }
+}
+
+// Effective form of a local element: its own form attribute or elementFormDefault of the schema
+pub fn element_form(node: &roxmltree::Node) -> FormType {
+    form(node, attribute::ELEMENT_FORM_DEFAULT)
+}
+
+// Effective form of a local attribute: its own form attribute or attributeFormDefault of the schema
+pub fn attribute_form(node: &roxmltree::Node) -> FormType {
+    form(node, attribute::ATTRIBUTE_FORM_DEFAULT)
+}
+
+fn form(node: &roxmltree::Node, default_attribute: &str) -> FormType {
+    // Declarations referenced with 'ref' are global, so they are always qualified
+    if node.has_attribute(attribute::REF) {
+        return FormType::Qualified;
+    }
+
+    let value = node.attribute(attribute::FORM).or_else(|| {
+        node.ancestors()
+            .find(|n| n.is_element() && n.xsd_type() == ElementType::Schema)
+            .and_then(|schema| schema.attribute(default_attribute))
+    });
+
+    match value {
+        Some("qualified") => FormType::Qualified,
+        _ => FormType::Unqualified,
+    }
+}
+
+pub fn value_constraint(node: &roxmltree::Node) -> Option<ValueConstraint> {
+    node.attribute(attribute::FIXED).map(|value| ValueConstraint::Fixed(value.to_string())).or_else(
+        || {
+            node.attribute(attribute::DEFAULT)
+                .map(|value| ValueConstraint::Default(value.to_string()))
+        },
+    )
+}
+
 pub type MinOccurs = usize;
 pub enum MaxOccurs {
     Bounded(usize),
@@ -223,3 +335,92 @@ pub fn max_occurs(node: &roxmltree::Node) -> MaxOccurs {
         None => MaxOccurs::None,
     }
 }
+
+pub fn occurs(node: &roxmltree::Node) -> Occurs {
+    Occurs {
+        min: min_occurs(node),
+        max: match max_occurs(node) {
+            MaxOccurs::Bounded(max) => Some(max),
+            MaxOccurs::Unbounded => None,
+            MaxOccurs::None => Some(1),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parser::{
+        types::{Occurs, QName},
+        utils::find_child,
+        xsd_elements::{attribute_form, element_form, occurs, ElementType, FormType, XsdNode},
+    };
+
+    #[test]
+    fn test_occurs() {
+        let doc = roxmltree::Document::parse(
+            r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+                <xs:element name="One"/>
+                <xs:element name="Bounded" minOccurs="2" maxOccurs="5"/>
+                <xs:element name="Unbounded" minOccurs="0" maxOccurs="unbounded"/>
+            </xs:schema>"#,
+        )
+        .unwrap();
+        let elements = doc.root_element().children().filter(|n| n.is_element()).collect::<Vec<_>>();
+
+        assert_eq!(occurs(&elements[0]), Occurs { min: 1, max: Some(1) });
+        assert_eq!(occurs(&elements[1]), Occurs { min: 2, max: Some(5) });
+        assert_eq!(occurs(&elements[2]), Occurs { min: 0, max: None });
+    }
+
+    #[test]
+    fn test_resolve_qname() {
+        let doc = roxmltree::Document::parse(
+            r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"
+                          xmlns="http://default.com"
+                          xmlns:tns="http://example.com"/>"#,
+        )
+        .unwrap();
+        let schema = doc.root_element();
+
+        let qname = |qname: &str| schema.resolve_qname(qname).unwrap();
+        assert_eq!(qname("tns:Foo"), QName::new(Some("http://example.com"), "Foo"));
+        assert_eq!(qname("Foo"), QName::new(Some("http://default.com"), "Foo"));
+        assert_eq!(
+            qname("xml:lang"),
+            QName::new(Some("http://www.w3.org/XML/1998/namespace"), "lang")
+        );
+
+        let err = schema.resolve_qname("unknown:Foo").unwrap_err();
+        assert!(err.to_string().contains("Undeclared namespace prefix 'unknown' in 'unknown:Foo'"));
+    }
+
+    #[test]
+    fn test_form() {
+        let doc = roxmltree::Document::parse(
+            r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"
+                          elementFormDefault="qualified">
+                <xs:complexType name="Foo">
+                    <xs:sequence>
+                        <xs:element name="a" type="xs:int"/>
+                        <xs:element name="b" type="xs:int" form="unqualified"/>
+                    </xs:sequence>
+                    <xs:attribute name="c" type="xs:int"/>
+                    <xs:attribute ref="xml:lang"/>
+                </xs:complexType>
+            </xs:schema>"#,
+        )
+        .unwrap();
+        let complex_type = find_child(&doc.root_element(), "complexType").unwrap();
+        let sequence = find_child(&complex_type, "sequence").unwrap();
+        let elements = sequence.children().filter(|n| n.is_element()).collect::<Vec<_>>();
+        let attributes = complex_type
+            .children()
+            .filter(|n| n.is_element() && n.xsd_type() == ElementType::Attribute)
+            .collect::<Vec<_>>();
+
+        assert_eq!(element_form(&elements[0]), FormType::Qualified);
+        assert_eq!(element_form(&elements[1]), FormType::Unqualified);
+        assert_eq!(attribute_form(&attributes[0]), FormType::Unqualified);
+        assert_eq!(attribute_form(&attributes[1]), FormType::Qualified);
+    }
+}
@@ -1,19 +1,34 @@
+/// XSD: `tns:FooType`
 #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
 #[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
 pub struct FooType {
+    /// XSD: element `Once` of type `xs:int`
     #[yaserde(prefix = "tns", rename = "Once")]
     pub once: i32,
 
+    /// XSD: element `Optional` of type `xs:int`
     #[yaserde(prefix = "tns", rename = "Optional")]
     pub optional: Option<i32>,
 
+    /// XSD: element `OnceSpecify` of type `xs:int`
     #[yaserde(prefix = "tns", rename = "OnceSpecify")]
     pub once_specify: i32,
 
+    /// XSD: element `TwiceOrMore` of type `xs:int`
     #[yaserde(prefix = "tns", rename = "TwiceOrMore")]
     pub twice_or_more: Vec<i32>,
 }
 
-impl Validate for FooType {}
+impl Validate for FooType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        errors.check(&format!("{}/TwiceOrMore", path), || {
+            #[allow(clippy::len_zero)]
+            if self.twice_or_more.len() < 2 {
+                return Err(xs::ValidationError::new(xs::FacetKind::MinOccurs, "twice_or_more", ">= 2", self.twice_or_more.len()));
+            }
+            Ok(())
+        });
+    }
+}
 
 // pub type Foo = FooType;
@@ -1,6 +1,99 @@
+/// XSD: `tns:FooType`
 #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
 #[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
 pub struct FooType {
+    /// XSD: element `Name` of type `xs:string`
     #[yaserde(prefix = "tns", rename = "Name")]
     pub name: String,
+
+    /// XSD: any element of namespace `##any`, processContents `lax`
+    /// Elements are kept as raw XML.
+    #[yaserde(flatten)]
+    pub any: xs::AnyElements,
 }
+
+impl Validate for FooType {}
+
+/// XSD: `tns:BarType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct BarType {
+    /// XSD: any element of namespace `##other`, processContents `skip`
+    /// Elements are kept as raw XML.
+    #[yaserde(flatten)]
+    pub any: xs::AnyElements,
+}
+
+impl Validate for BarType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        errors.check(path, || {
+            #[allow(clippy::len_zero)]
+            if self.any.len() < 1 {
+                return Err(xs::ValidationError::new(xs::FacetKind::MinOccurs, "any", ">= 1", self.any.len()));
+            }
+            if self.any.len() > 2 {
+                return Err(xs::ValidationError::new(xs::FacetKind::MaxOccurs, "any", "<= 2", self.any.len()));
+            }
+            self.any.validate_namespace("##other", Some("http://example.com"))?;
+            Ok(())
+        });
+    }
+}
+
+/// XSD: `tns:BazType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct BazType {
+    /// XSD: any element of namespace `##targetNamespace`, processContents `strict`
+    /// Elements without a global declaration in the generated schemas fail validation.
+    #[yaserde(flatten)]
+    pub any: xs::AnyElements,
+}
+
+impl Validate for BazType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        errors.check(path, || {
+            #[allow(clippy::len_zero)]
+            if self.any.len() < 1 {
+                return Err(xs::ValidationError::new(xs::FacetKind::MinOccurs, "any", ">= 1", self.any.len()));
+            }
+            if self.any.len() > 1 {
+                return Err(xs::ValidationError::new(xs::FacetKind::MaxOccurs, "any", "<= 1", self.any.len()));
+            }
+            self.any.validate_namespace("##targetNamespace", Some("http://example.com"))?;
+            self.any.validate_declared(&[(Some("http://example.com"), "Foo"), (Some("http://example.com"), "Bar"), (Some("http://example.com"), "Baz")])?;
+            Ok(())
+        });
+    }
+}
+
+/// XSD: `tns:QuxType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct QuxType {
+    /// XSD: any element of namespace `##targetNamespace http://ext.com`, processContents `lax`
+    /// Elements are kept as raw XML.
+    #[yaserde(flatten)]
+    pub any: xs::AnyElements,
+
+    /// XSD: element `Name` of type `xs:string`
+    #[yaserde(prefix = "tns", rename = "Name")]
+    pub name: String,
+}
+
+impl Validate for QuxType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        errors.check(path, || {
+            #[allow(clippy::len_zero)]
+            if self.any.len() < 2 {
+                return Err(xs::ValidationError::new(xs::FacetKind::MinOccurs, "any", ">= 2", self.any.len()));
+            }
+            self.any.validate_namespace("##targetNamespace http://ext.com", Some("http://example.com"))?;
+            Ok(())
+        });
+    }
+}
+
+// pub type Foo = FooType;
+// pub type Bar = BarType;
+// pub type Baz = BazType;
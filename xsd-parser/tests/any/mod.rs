@@ -1,18 +1,85 @@
 use super::utils;
 
-#[test]
-fn deserialization_works() {
-    mod expected {
-        use yaserde_derive::{YaDeserialize, YaSerialize};
+mod expected {
+    use xsd_parser::generator::validator::Validate;
+    use xsd_types::types as xs;
+    use yaserde_derive::{YaDeserialize, YaSerialize};
 
-        include!("expected.rs");
-    }
+    include!("expected.rs");
+}
 
+#[test]
+fn deserialization_works() {
     let ser = include_str!("example.xml");
 
     let de: expected::FooType = yaserde::de::from_str(ser).unwrap();
 
-    assert_eq!(de, expected::FooType { name: "abcd".to_string() });
+    assert_eq!(de.name, "abcd");
+    assert_eq!(de.any.len(), 2);
+    assert_eq!(de.any.0[0].name.local_name, "AnyElement");
+    assert_eq!(de.any.0[1].name.namespace.as_deref(), Some("http://ext.com"));
+    assert_eq!(de.any.0[1].attributes[0].value, "1");
+    assert_eq!(de.any.0[1].text(), "text");
+}
+
+#[test]
+fn serialization_round_trip() {
+    let de: expected::FooType = yaserde::de::from_str(include_str!("example.xml")).unwrap();
+
+    let ser = yaserde::ser::to_string(&de).unwrap();
+    println!("{}", ser);
+    assert!(ser.contains("<AnyElement />"));
+    assert!(ser
+        .contains(r#"<ext:Extension xmlns:ext="http://ext.com" ext:id="1">text</ext:Extension>"#));
+
+    let round_trip: expected::FooType = yaserde::de::from_str(&ser).unwrap();
+    assert_eq!(round_trip, de);
+}
+
+#[test]
+fn validation_checks_wildcard() {
+    use xsd_parser::generator::validator::Validate;
+
+    let parse = |xml: &str| -> expected::BarType { yaserde::de::from_str(xml).unwrap() };
+
+    let bar = parse(
+        r#"<tns:Bar xmlns:tns="http://example.com"><o:A xmlns:o="http://other.com"/></tns:Bar>"#,
+    );
+    assert!(bar.validate().is_ok());
+
+    let empty = parse(r#"<tns:Bar xmlns:tns="http://example.com"></tns:Bar>"#);
+    assert!(empty.validate().is_err());
+
+    let same_namespace = parse(r#"<tns:Bar xmlns:tns="http://example.com"><tns:A/></tns:Bar>"#);
+    assert!(same_namespace.validate().is_err());
+}
+
+#[test]
+fn validation_checks_strict_wildcard() {
+    use xsd_parser::generator::validator::Validate;
+
+    let parse = |xml: &str| -> expected::BazType { yaserde::de::from_str(xml).unwrap() };
+
+    let declared = parse(r#"<tns:Baz xmlns:tns="http://example.com"><tns:Bar/></tns:Baz>"#);
+    assert!(declared.validate().is_ok());
+
+    let undeclared = parse(r#"<tns:Baz xmlns:tns="http://example.com"><tns:A/></tns:Baz>"#);
+    let errors = undeclared.validate().unwrap_err();
+    assert_eq!(errors.0[0].kind, xsd_types::types::FacetKind::ProcessContents);
+}
+
+#[test]
+fn merged_wildcards_allow_both_contents() {
+    use xsd_parser::generator::validator::Validate;
+
+    let qux: expected::QuxType = yaserde::de::from_str(
+        r#"<tns:Qux xmlns:tns="http://example.com" xmlns:ext="http://ext.com">
+            <tns:Foo/><tns:Name>abcd</tns:Name><ext:A/><ext:B/>
+        </tns:Qux>"#,
+    )
+    .unwrap();
+    assert_eq!(qux.any.len(), 3);
+    assert!(qux.validate().is_ok());
 }
 
 #[test]
@@ -21,7 +88,6 @@ fn generator_does_not_panic() {
 }
 
 #[test]
-#[ignore]
 fn generator_output_has_correct_ast() {
     utils::ast_test(include_str!("input.xsd"), include_str!("expected.rs"));
 }
@@ -0,0 +1,53 @@
+/// XSD: `tns:FooType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct FooType {
+    /// XSD: element `Name` of type `xs:string`
+    #[yaserde(prefix = "tns", rename = "Name")]
+    pub name: String,
+
+    /// XSD: attribute `token` of type `xs:string`
+    #[yaserde(attribute, rename = "token")]
+    pub token: String,
+
+    /// XSD: any attribute of namespace `##any`, processContents `lax`
+    /// Attributes are kept as raw XML.
+    #[yaserde(flatten)]
+    pub any_attribute: xs::AnyAttributes<FooType>,
+}
+
+impl xs::DeclaredAttributes for FooType {
+    const NAMES: &'static [(Option<&'static str>, &'static str)] = &[(None, "token")];
+}
+
+impl Validate for FooType {}
+
+/// XSD: `tns:BarType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct BarType {
+    /// XSD: attribute `id` of type `xs:int`
+    #[yaserde(attribute, rename = "id")]
+    pub id: Option<i32>,
+
+    /// XSD: any attribute of namespace `##other`, processContents `strict`
+    /// Attributes are kept as raw XML, only their namespace is validated.
+    #[yaserde(flatten)]
+    pub any_attribute: xs::AnyAttributes<BarType>,
+}
+
+impl xs::DeclaredAttributes for BarType {
+    const NAMES: &'static [(Option<&'static str>, &'static str)] = &[(None, "id")];
+}
+
+impl Validate for BarType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        errors.check(path, || {
+            self.any_attribute.validate_namespace("##other", Some("http://example.com"))?;
+            Ok(())
+        });
+    }
+}
+
+// pub type Foo = FooType;
+// pub type Bar = BarType;
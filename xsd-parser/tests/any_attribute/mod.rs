@@ -0,0 +1,70 @@
+use super::utils;
+
+mod expected {
+    use xsd_parser::generator::validator::Validate;
+    use xsd_types::types as xs;
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    include!("expected.rs");
+}
+
+#[test]
+fn deserialization_works() {
+    let ser = include_str!("example.xml");
+
+    let de: expected::FooType = yaserde::de::from_str(ser).unwrap();
+
+    assert_eq!(de.name, "foo");
+    assert_eq!(de.token, "abc");
+    assert_eq!(de.any_attribute.len(), 1);
+    assert_eq!(de.any_attribute.get(Some("http://vendor.com"), "model"), Some("X1"));
+}
+
+#[test]
+fn serialization_round_trip() {
+    let de: expected::FooType = yaserde::de::from_str(include_str!("example.xml")).unwrap();
+
+    let ser = yaserde::ser::to_string(&de).unwrap();
+    println!("{}", ser);
+    assert!(ser.contains(r#"v:model="X1""#));
+    assert!(ser.contains(r#"xmlns:v="http://vendor.com""#));
+
+    let round_trip: expected::FooType = yaserde::de::from_str(&ser).unwrap();
+    assert_eq!(round_trip, de);
+}
+
+#[test]
+fn validation_checks_namespace() {
+    use xsd_parser::generator::validator::Validate;
+
+    let parse = |xml: &str| -> expected::BarType { yaserde::de::from_str(xml).unwrap() };
+
+    let bar = parse(
+        r#"<tns:Bar xmlns:tns="http://example.com" xmlns:v="http://vendor.com" id="1" v:a="b"/>"#,
+    );
+    assert_eq!(bar.id, Some(1));
+    assert!(bar.validate().is_ok());
+
+    let local = parse(r#"<tns:Bar xmlns:tns="http://example.com" id="1" a="b"/>"#);
+    assert!(local.validate().is_err());
+}
+
+#[test]
+fn vendor_attribute_with_declared_local_name_is_kept() {
+    let bar: expected::BarType = yaserde::de::from_str(
+        r#"<tns:Bar xmlns:tns="http://example.com" xmlns:v="http://vendor.com" id="1" v:id="2"/>"#,
+    )
+    .unwrap();
+    assert_eq!(bar.any_attribute.len(), 1);
+    assert_eq!(bar.any_attribute.get(Some("http://vendor.com"), "id"), Some("2"));
+}
+
+#[test]
+fn generator_does_not_panic() {
+    println!("{}", utils::generate(include_str!("input.xsd")))
+}
+
+#[test]
+fn generator_output_has_correct_ast() {
+    utils::ast_test(include_str!("input.xsd"), include_str!("expected.rs"));
+}
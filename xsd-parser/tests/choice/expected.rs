@@ -4,10 +4,12 @@ pub struct BarType(pub String);
 #[derive(Default, Clone, PartialEq, Debug, UtilsTupleIo, UtilsDefaultSerde)]
 pub struct BazType(pub i32);
 
-#[derive(PartialEq, Debug, Clone, YaSerialize, YaDeserialize)]
-#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+#[derive(PartialEq, Debug, Clone, UtilsChoiceSerDe)]
+#[choice(namespace = "tns: http://example.com", siblings = "")]
 pub enum FooTypeChoice {
+    #[choice(element = "tns:Bar")]
     Bar(BarType),
+    #[choice(element = "tns:Baz")]
     Baz(BazType),
     __Unknown__(String),
 }
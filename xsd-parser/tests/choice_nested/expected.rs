@@ -0,0 +1,216 @@
+/// XSD: `tns:ColorType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct ColorType {
+    /// XSD: attribute `shade` of type `xs:int`
+    #[yaserde(attribute, rename = "shade")]
+    pub shade: Option<i32>,
+}
+
+impl Validate for ColorType {}
+
+/// XSD: `tns:ItemsType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct ItemsType {
+    /// XSD: element `Title` of type `xs:string`
+    #[yaserde(prefix = "tns", rename = "Title")]
+    pub title: String,
+
+    #[yaserde(flatten)]
+    pub items_type_choice: xs::Choices<items_type::ItemsTypeChoice>,
+}
+
+impl Validate for ItemsType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        errors.check(path, || {
+            #[allow(clippy::len_zero)]
+            if self.items_type_choice.len() < 1 {
+                return Err(xs::ValidationError::new(xs::FacetKind::MinOccurs, "items_type_choice", ">= 1", self.items_type_choice.len()));
+            }
+            Ok(())
+        });
+        self.items_type_choice.validate_at(path, errors);
+    }
+}
+
+pub mod items_type {
+    use super::*;
+
+    #[derive(PartialEq, Debug, Clone, UtilsChoiceSerDe)]
+    #[choice(namespace = "tns: http://example.com", siblings = "tns:Title")]
+    pub enum ItemsTypeChoice {
+        /// XSD: element `Note` of type `xs:string`
+        #[choice(element = "tns:Note")]
+        Note(String),
+        /// XSD: element `Count` of type `xs:int`
+        #[choice(element = "tns:Count")]
+        Count(i32),
+        #[choice(sequence = "tns:Key tns:Value?")]
+        KeySequence(items_type_choice::KeySequence),
+        #[choice(choice)]
+        RedChoice(items_type_choice::RedChoice),
+        /// XSD: any element of namespace `##other`, processContents `lax`
+        /// Elements are kept as raw XML.
+        #[choice(any = "##other", target_namespace = "http://example.com")]
+        Any(xs::AnyElement),
+        __Unknown__(String),
+    }
+
+    impl Default for ItemsTypeChoice {
+        fn default() -> ItemsTypeChoice {
+            Self::__Unknown__("No valid variants".into())
+        }
+    }
+
+    impl Validate for ItemsTypeChoice {
+        fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+            match self {
+                Self::KeySequence(value) => value.validate_at(path, errors),
+                Self::RedChoice(value) => value.validate_at(path, errors),
+                _ => (),
+            }
+        }
+    }
+
+    pub mod items_type_choice {
+        use super::*;
+
+        #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+        #[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+        pub struct KeySequence {
+            /// XSD: element `Key` of type `xs:string`
+            #[yaserde(prefix = "tns", rename = "Key")]
+            pub key: String,
+
+            /// XSD: element `Value` of type `xs:string`
+            #[yaserde(prefix = "tns", rename = "Value")]
+            pub value: Option<String>,
+        }
+
+        impl Validate for KeySequence {}
+
+        #[derive(PartialEq, Debug, Clone, UtilsChoiceSerDe)]
+        #[choice(namespace = "tns: http://example.com")]
+        pub enum RedChoice {
+            /// XSD: element `Red` of type `tns:ColorType`
+            #[choice(element = "tns:Red")]
+            Red(ColorType),
+            /// XSD: element `Blue` of type `tns:ColorType`
+            #[choice(element = "tns:Blue")]
+            Blue(ColorType),
+            __Unknown__(String),
+        }
+
+        impl Default for RedChoice {
+            fn default() -> RedChoice {
+                Self::__Unknown__("No valid variants".into())
+            }
+        }
+
+        impl Validate for RedChoice {
+            fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+                match self {
+                    Self::Red(value) => value.validate_at(&format!("{}/Red", path), errors),
+                    Self::Blue(value) => value.validate_at(&format!("{}/Blue", path), errors),
+                    _ => (),
+                }
+            }
+        }
+    }
+}
+
+/// XSD: `tns:PairsType`
+#[derive(PartialEq, Debug, Clone, UtilsChoiceSerDe)]
+#[choice(namespace = "tns: http://example.com", siblings = "")]
+pub enum PairsTypeChoice {
+    #[choice(sequence = "tns:Key tns:Value")]
+    KeySequence(pairs_type_choice::KeySequence),
+    #[choice(sequence = "tns:Key tns:Count{2}")]
+    KeySequence2(pairs_type_choice::KeySequence2),
+    __Unknown__(String),
+}
+
+impl Default for PairsTypeChoice {
+    fn default() -> PairsTypeChoice {
+        Self::__Unknown__("No valid variants".into())
+    }
+}
+
+impl Validate for PairsTypeChoice {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        match self {
+            Self::KeySequence(value) => value.validate_at(path, errors),
+            Self::KeySequence2(value) => value.validate_at(path, errors),
+            _ => (),
+        }
+    }
+}
+
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct PairsType {
+    #[yaserde(flatten)]
+    pub pairs_type_choice: xs::Choices<PairsTypeChoice>,
+}
+
+impl Validate for PairsType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        errors.check(path, || {
+            #[allow(clippy::len_zero)]
+            if self.pairs_type_choice.len() < 1 {
+                return Err(xs::ValidationError::new(xs::FacetKind::MinOccurs, "pairs_type_choice", ">= 1", self.pairs_type_choice.len()));
+            }
+            Ok(())
+        });
+        self.pairs_type_choice.validate_at(path, errors);
+    }
+}
+
+pub mod pairs_type_choice {
+    use super::*;
+
+    #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+    #[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+    pub struct KeySequence {
+        /// XSD: element `Key` of type `xs:string`
+        #[yaserde(prefix = "tns", rename = "Key")]
+        pub key: String,
+
+        /// XSD: element `Value` of type `xs:string`
+        #[yaserde(prefix = "tns", rename = "Value")]
+        pub value: String,
+    }
+
+    impl Validate for KeySequence {}
+
+    #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+    #[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+    pub struct KeySequence2 {
+        /// XSD: element `Key` of type `xs:string`
+        #[yaserde(prefix = "tns", rename = "Key")]
+        pub key: String,
+
+        /// XSD: element `Count` of type `xs:int`
+        #[yaserde(prefix = "tns", rename = "Count")]
+        pub count: Vec<i32>,
+    }
+
+    impl Validate for KeySequence2 {
+        fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+            errors.check(&format!("{}/Count", path), || {
+                #[allow(clippy::len_zero)]
+                if self.count.len() < 2 {
+                    return Err(xs::ValidationError::new(xs::FacetKind::MinOccurs, "count", ">= 2", self.count.len()));
+                }
+                if self.count.len() > 2 {
+                    return Err(xs::ValidationError::new(xs::FacetKind::MaxOccurs, "count", "<= 2", self.count.len()));
+                }
+                Ok(())
+            });
+        }
+    }
+}
+
+// pub type Items = ItemsType;
+// pub type Pairs = PairsType;
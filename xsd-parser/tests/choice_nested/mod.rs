@@ -0,0 +1,128 @@
+use super::utils;
+
+mod expected {
+    use xsd_macro_utils::UtilsChoiceSerDe;
+    use xsd_parser::generator::validator::Validate;
+    use xsd_types::types as xs;
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    include!("expected.rs");
+}
+
+use expected::items_type::{items_type_choice, ItemsTypeChoice};
+
+fn example() -> expected::ItemsType {
+    expected::ItemsType {
+        title: "Inventory".to_string(),
+        items_type_choice: vec![
+            ItemsTypeChoice::Count(2),
+            ItemsTypeChoice::KeySequence(items_type_choice::KeySequence {
+                key: "size".to_string(),
+                value: Some("XL".to_string()),
+            }),
+            ItemsTypeChoice::Note("fragile".to_string()),
+            ItemsTypeChoice::KeySequence(items_type_choice::KeySequence {
+                key: "color".to_string(),
+                value: None,
+            }),
+            ItemsTypeChoice::RedChoice(items_type_choice::RedChoice::Red(expected::ColorType {
+                shade: Some(3),
+            })),
+        ]
+        .into(),
+    }
+}
+
+#[test]
+fn deserialization_works() {
+    let de: expected::ItemsType = yaserde::de::from_str(include_str!("example.xml")).unwrap();
+
+    assert_eq!(de, example());
+}
+
+#[test]
+fn serialization_keeps_document_order() {
+    let ser = yaserde::ser::to_string(&example()).unwrap();
+    println!("{}", ser);
+
+    let names = ["Title", "Count", "Key", "Value", "Note", "Key", "Red"];
+    let positions = names
+        .iter()
+        .map(|name| ser.match_indices(&format!("<tns:{}", name)).map(|(i, _)| i).collect())
+        .collect::<Vec<Vec<usize>>>();
+    assert_eq!(positions.iter().map(|p| p.len()).collect::<Vec<_>>(), vec![1, 1, 2, 1, 1, 2, 1]);
+    assert!(positions[2][0] < positions[3][0] && positions[3][0] < positions[4][0]);
+    assert!(positions[4][0] < positions[5][1] && positions[5][1] < positions[6][0]);
+
+    let round_trip: expected::ItemsType = yaserde::de::from_str(&ser).unwrap();
+    assert_eq!(round_trip, example());
+}
+
+#[test]
+fn deserialization_matches_namespaces() {
+    let xml = r#"<tns:Items xmlns:tns="http://example.com" xmlns:v="http://vendor.com">
+        <tns:Title>Inventory</tns:Title>
+        <v:Note>vendor</v:Note>
+        <tns:Note>fragile</tns:Note>
+    </tns:Items>"#;
+    let de: expected::ItemsType = yaserde::de::from_str(xml).unwrap();
+    assert!(
+        matches!(&de.items_type_choice[0], ItemsTypeChoice::Any(e) if e.name.local_name == "Note")
+    );
+    assert_eq!(de.items_type_choice[1], ItemsTypeChoice::Note("fragile".to_string()));
+
+    // Elements of the target namespace which start no alternative are rejected
+    let xml = r#"<tns:Items xmlns:tns="http://example.com">
+        <tns:Title>Inventory</tns:Title>
+        <tns:Weight>2</tns:Weight>
+    </tns:Items>"#;
+    assert!(yaserde::de::from_str::<expected::ItemsType>(xml).is_err());
+}
+
+#[test]
+fn deserialization_requires_sequence_steps() {
+    let xml = r#"<tns:Pairs xmlns:tns="http://example.com">
+        <tns:Key>a</tns:Key>
+        <tns:Count>1</tns:Count>
+        <tns:Count>2</tns:Count>
+        <tns:Key>b</tns:Key>
+        <tns:Value>c</tns:Value>
+    </tns:Pairs>"#;
+    let de: expected::PairsType = yaserde::de::from_str(xml).unwrap();
+    assert_eq!(
+        de.pairs_type_choice.0,
+        vec![
+            expected::PairsTypeChoice::KeySequence2(expected::pairs_type_choice::KeySequence2 {
+                key: "a".to_string(),
+                count: vec![1, 2],
+            }),
+            expected::PairsTypeChoice::KeySequence(expected::pairs_type_choice::KeySequence {
+                key: "b".to_string(),
+                value: "c".to_string(),
+            }),
+        ]
+    );
+
+    // A key without its value or counts matches no alternative
+    let xml = r#"<tns:Pairs xmlns:tns="http://example.com"><tns:Key>a</tns:Key></tns:Pairs>"#;
+    assert!(yaserde::de::from_str::<expected::PairsType>(xml).is_err());
+}
+
+#[test]
+fn validation_checks_occurrences() {
+    use xsd_parser::generator::validator::Validate;
+
+    assert!(example().validate().is_ok());
+    let empty = expected::ItemsType { title: "Inventory".to_string(), ..Default::default() };
+    assert!(empty.validate().is_err());
+}
+
+#[test]
+fn generator_does_not_panic() {
+    println!("{}", utils::generate(include_str!("input.xsd")))
+}
+
+#[test]
+fn generator_output_has_correct_ast() {
+    utils::ast_test(include_str!("input.xsd"), include_str!("expected.rs"));
+}
@@ -1,9 +1,12 @@
+/// XSD: `tns:FooType`
 #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
 #[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
 pub struct FooType {
+    /// XSD: element `Min` of type `xs:int`
     #[yaserde(prefix = "tns", rename = "Min")]
     pub min: i32,
 
+    /// XSD: element `Max` of type `xs:int`
     #[yaserde(prefix = "tns", rename = "Max")]
     pub max: i32,
 }
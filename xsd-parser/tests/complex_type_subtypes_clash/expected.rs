@@ -1,11 +1,17 @@
+/// XSD: `tns:FooType`
 #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
 #[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
 pub struct FooType {
+    /// XSD: element `Extension`
     #[yaserde(prefix = "tns", rename = "Extension")]
     pub extension: foo_type::ExtensionType,
 }
 
-impl Validate for FooType {}
+impl Validate for FooType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        self.extension.validate_at(&format!("{}/Extension", path), errors);
+    }
+}
 
 pub mod foo_type {
     use super::*;
@@ -17,14 +23,20 @@ pub mod foo_type {
     impl Validate for ExtensionType {}
 }
 
+/// XSD: `tns:BarType`
 #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
 #[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
 pub struct BarType {
+    /// XSD: element `Extension`
     #[yaserde(prefix = "tns", rename = "Extension")]
     pub extension: bar_type::ExtensionType,
 }
 
-impl Validate for BarType {}
+impl Validate for BarType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        self.extension.validate_at(&format!("{}/Extension", path), errors);
+    }
+}
 
 pub mod bar_type {
     use super::*;
@@ -0,0 +1,94 @@
+/// XSD: `tns:ProtocolType`
+#[derive(PartialEq, Debug, Clone, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub enum ProtocolType {
+    #[yaserde(rename = "HTTP")]
+    Http,
+    #[yaserde(rename = "RTSP")]
+    Rtsp,
+    __Unknown__(String),
+}
+
+impl Default for ProtocolType {
+    fn default() -> ProtocolType {
+        Self::__Unknown__("No valid variants".into())
+    }
+}
+
+impl Validate for ProtocolType {}
+
+/// XSD: `tns:FooType`
+#[derive(Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct FooType {
+    /// XSD: element `Name` of type `xs:string`
+    #[yaserde(prefix = "tns", rename = "Name")]
+    pub name: String,
+
+    /// XSD: element `Tags` of type `xs:string`
+    #[yaserde(prefix = "tns", rename = "Tags")]
+    pub tags: Vec<String>,
+
+    /// XSD: attribute `Port` of type `xs:int`
+    #[yaserde(attribute, default = "default_foo_type_port", rename = "Port")]
+    pub port: Option<i32>,
+
+    /// XSD: attribute `Protocol` of type `tns:ProtocolType`
+    #[yaserde(attribute, default = "default_foo_type_protocol", rename = "Protocol")]
+    pub protocol: Option<ProtocolType>,
+
+    /// XSD: attribute `Enabled` of type `xs:boolean`
+    #[yaserde(attribute, default = "default_foo_type_enabled", rename = "Enabled")]
+    pub enabled: Option<bool>,
+
+    /// XSD: attribute `Version` of type `xs:string`
+    #[yaserde(attribute, default = "default_foo_type_version", rename = "Version")]
+    pub version: Option<String>,
+}
+
+impl Default for FooType {
+    fn default() -> Self {
+        Self {
+            name: default_foo_type_name(),
+            tags: Default::default(),
+            port: default_foo_type_port(),
+            protocol: default_foo_type_protocol(),
+            enabled: default_foo_type_enabled(),
+            version: default_foo_type_version(),
+        }
+    }
+}
+
+fn default_foo_type_name() -> String {
+    "unnamed".to_string()
+}
+
+fn default_foo_type_port() -> Option<i32> {
+    Some(80)
+}
+
+fn default_foo_type_protocol() -> Option<ProtocolType> {
+    Some(xs::from_text::<ProtocolType>("RTSP").unwrap())
+}
+
+fn default_foo_type_enabled() -> Option<bool> {
+    Some(true)
+}
+
+fn default_foo_type_version() -> Option<String> {
+    Some("1.0".to_string())
+}
+
+impl Validate for FooType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        self.protocol.validate_at(&format!("{}/@Protocol", path), errors);
+        errors.check(&format!("{}/@Version", path), || {
+            if self.version.is_some() && self.version != default_foo_type_version() {
+                return Err(xs::ValidationError::new(xs::FacetKind::Fixed, "version", format!("== {:?}", default_foo_type_version()), format!("{:?}", self.version)));
+            }
+            Ok(())
+        });
+    }
+}
+
+// pub type Foo = FooType;
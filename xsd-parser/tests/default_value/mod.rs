@@ -0,0 +1,115 @@
+use super::utils;
+
+mod expected {
+    use xsd_parser::generator::validator::Validate;
+    use xsd_types::types as xs;
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    include!("expected.rs");
+}
+
+#[test]
+fn deserialization_works() {
+    let ser = include_str!("example.xml");
+
+    let de: expected::FooType = yaserde::de::from_str(ser).unwrap();
+
+    assert_eq!(
+        de,
+        expected::FooType {
+            name: "foo".to_string(),
+            tags: vec![],
+            port: Some(8080),
+            protocol: Some(expected::ProtocolType::Rtsp),
+            enabled: Some(true),
+            version: Some("1.0".to_string()),
+        }
+    );
+}
+
+#[test]
+fn default_uses_schema_values() {
+    let foo = expected::FooType::default();
+
+    assert_eq!(foo.name, "unnamed");
+    assert_eq!(foo.port, Some(80));
+    assert_eq!(foo.protocol, Some(expected::ProtocolType::Rtsp));
+    assert_eq!(foo.enabled, Some(true));
+    assert_eq!(foo.version, Some("1.0".to_string()));
+}
+
+#[test]
+fn validation_rejects_changed_fixed_value() {
+    use xsd_parser::generator::validator::Validate;
+
+    let mut foo = expected::FooType::default();
+    assert!(foo.validate().is_ok());
+
+    foo.version = None;
+    assert!(foo.validate().is_ok());
+
+    foo.version = Some("2.0".to_string());
+    assert!(foo.validate().is_err());
+}
+
+#[test]
+fn generator_does_not_panic() {
+    println!("{}", utils::generate(include_str!("input.xsd")))
+}
+
+#[test]
+fn generator_output_has_correct_ast() {
+    utils::ast_test(include_str!("input.xsd"), include_str!("expected.rs"));
+}
+
+#[test]
+fn generator_reports_invalid_values() {
+    use xsd_parser::{
+        generator::{builder::GeneratorBuilder, error::GenerateError},
+        parser::parse,
+    };
+
+    let schema = parse(
+        r#"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"
+           xmlns:tns="http://example.com"
+           targetNamespace="http://example.com">
+    <xs:simpleType name="ProtocolType">
+        <xs:restriction base="xs:string">
+            <xs:enumeration value="RTSP"/>
+        </xs:restriction>
+    </xs:simpleType>
+    <xs:simpleType name="PortType">
+        <xs:restriction base="xs:unsignedShort"/>
+    </xs:simpleType>
+    <xs:complexType name="FooType">
+        <xs:attribute name="enabled" type="xs:boolean" default="yes"/>
+        <xs:attribute name="protocol" type="tns:ProtocolType" default="HTTP"/>
+        <xs:attribute name="port" type="tns:PortType" default="80000"/>
+        <xs:attribute name="count" type="xs:int" default="3"/>
+    </xs:complexType>
+</xs:schema>
+        "#,
+    )
+    .unwrap();
+
+    let gen = GeneratorBuilder::default().build();
+    let code = gen.generate_rs_file(&schema);
+    // Fields with invalid values take the default value of their type
+    assert!(code.contains("count: default_foo_type_count(),"));
+    assert!(code.contains("enabled: Default::default(),"));
+
+    let invalid = |field: &str, reason: &str| GenerateError::InvalidValue {
+        owner: "FooType".to_string(),
+        field: field.to_string(),
+        reason: reason.to_string(),
+    };
+    assert_eq!(
+        gen.take_errors(),
+        vec![
+            invalid("enabled", r#""yes" is not a valid bool, expected true, false, 1 or 0"#),
+            invalid("protocol", r#""HTTP" is not one of the values of ProtocolType"#),
+            invalid("port", r#""80000" is not a valid u16"#),
+        ]
+    );
+}
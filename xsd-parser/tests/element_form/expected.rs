@@ -0,0 +1,25 @@
+/// XSD: `tns:FooType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct FooType {
+    /// XSD: element `a` of type `xs:int`
+    #[yaserde(rename = "a")]
+    pub a: i32,
+
+    /// XSD: element `b` of type `xs:string`
+    #[yaserde(prefix = "tns", rename = "b")]
+    pub b: String,
+
+    /// XSD: attribute `c` of type `xs:string`
+    #[yaserde(attribute, prefix = "tns", rename = "c")]
+    pub c: String,
+
+    /// XSD: attribute `d` of type `xs:string`
+    #[yaserde(attribute, rename = "d")]
+    pub d: String,
+}
+
+
+impl Validate for FooType {}
+
+// pub type Foo = FooType;
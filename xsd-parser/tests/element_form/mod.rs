@@ -0,0 +1,47 @@
+use super::utils;
+
+mod expected {
+    use xsd_parser::generator::validator::Validate;
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    include!("expected.rs");
+}
+
+fn foo() -> expected::FooType {
+    expected::FooType {
+        a: 1,
+        b: "string".to_string(),
+        c: "qualified".to_string(),
+        d: "unqualified".to_string(),
+    }
+}
+
+#[test]
+fn deserialization_works() {
+    let ser = include_str!("example.xml");
+
+    let de: expected::FooType = yaserde::de::from_str(ser).unwrap();
+
+    assert_eq!(de, foo());
+}
+
+#[test]
+fn serialization_works() {
+    let ser = yaserde::ser::to_string(&foo()).unwrap();
+    println!("{}", ser);
+
+    assert!(ser.contains("<a>1</a>"));
+    assert!(ser.contains("<tns:b>string</tns:b>"));
+    assert!(ser.contains("tns:c=\"qualified\""));
+    assert!(ser.contains(" d=\"unqualified\""));
+}
+
+#[test]
+fn generator_does_not_panic() {
+    println!("{}", utils::generate(include_str!("input.xsd")))
+}
+
+#[test]
+fn generator_output_has_correct_ast() {
+    utils::ast_test(include_str!("input.xsd"), include_str!("expected.rs"));
+}
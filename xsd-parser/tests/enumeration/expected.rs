@@ -1,3 +1,4 @@
+/// XSD: `tns:FooType`
 #[derive(PartialEq, Debug, Clone, YaSerialize, YaDeserialize)]
 #[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
 pub enum FooType {
@@ -18,8 +19,19 @@ impl Default for FooType {
 impl Validate for FooType {}
 
 
+/// XSD: `tns:FooType2`
+/// Facets: `enumeration = xs:OFF`, `enumeration = ON`, `enumeration = AUTO`
 #[derive(Default, Clone, PartialEq, Debug, UtilsTupleIo, UtilsDefaultSerde)]
 pub struct FooType2(pub String);
 
-impl Validate for FooType2 {}
+impl Validate for FooType2 {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        errors.check(path, || {
+            if !["xs:OFF", "ON", "AUTO"].contains(&self.0.as_str()) {
+                return Err(xs::ValidationError::new(xs::FacetKind::Enumeration, "0", format!("is one of {:?}", ["xs:OFF", "ON", "AUTO"]), format!("{:?}", self.0)));
+            }
+            Ok(())
+        });
+    }
+}
 
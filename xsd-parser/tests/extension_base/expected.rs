@@ -1,24 +1,31 @@
+/// XSD: `tns:BarType`
 #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
 #[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
 pub struct BarType {
+    /// XSD: element `b` of type `xs:int`
     #[yaserde(prefix = "tns", rename = "b")]
     pub b: i32,
 
+    /// XSD: element `c` of type `xs:string`
     #[yaserde(prefix = "tns", rename = "c")]
     pub c: String,
 }
 
 impl Validate for BarType {}
 
+/// XSD: `tns:FooType`
 #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
 #[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
 pub struct FooType {
+    /// XSD: element `a` of type `xs:float`
     #[yaserde(prefix = "tns", rename = "a")]
     pub a: f64,
 
+    /// XSD: element `b` of type `xs:int`
     #[yaserde(prefix = "tns", rename = "b")]
     pub b: i32,
 
+    /// XSD: element `c` of type `xs:string`
     #[yaserde(prefix = "tns", rename = "c")]
     pub c: String,
 }
@@ -1,23 +1,32 @@
+/// XSD: `tns:BarType`
 #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
 #[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
 pub struct BarType {
+    /// XSD: element `aa` of type `xs:int`
     #[yaserde(prefix = "tns", rename = "aa")]
     pub aa: i32,
 
+    /// XSD: element `bb` of type `xs:string`
     #[yaserde(prefix = "tns", rename = "bb")]
     pub bb: String,
 }
 
 impl Validate for BarType {}
 
+/// XSD: `tns:FooType`
 #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
 #[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
 pub struct FooType {
+    /// XSD: element `Messages`
     #[yaserde(prefix = "tns", rename = "Messages")]
     pub messages: foo_type::MessagesType,
 }
 
-impl Validate for FooType {}
+impl Validate for FooType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        self.messages.validate_at(&format!("{}/Messages", path), errors);
+    }
+}
 
 pub mod foo_type {
     use super::*;
@@ -25,12 +34,15 @@ pub mod foo_type {
     #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
     #[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
     pub struct MessagesType {
+        /// XSD: element `a` of type `xs:string`
         #[yaserde(prefix = "tns", rename = "a")]
         pub a: String,
 
+        /// XSD: element `aa` of type `xs:int`
         #[yaserde(prefix = "tns", rename = "aa")]
         pub aa: i32,
 
+        /// XSD: element `bb` of type `xs:string`
         #[yaserde(prefix = "tns", rename = "bb")]
         pub bb: String,
     }
@@ -15,8 +15,22 @@ fn deserialization_works() {
 }
 
 #[test]
-fn generator_does_not_panic() {
-    println!("{}", utils::generate(include_str!("input.xsd")))
+fn parser_reports_unresolved_base() {
+    use xsd_parser::parser::{error::ParseError, parse};
+
+    // The base type is declared in input2.xsd, which is only loaded with the schema set
+    let err = parse(include_str!("input.xsd")).unwrap_err();
+    assert!(matches!(err, ParseError::UnresolvedReference { .. }), "{}", err);
+}
+
+#[test]
+fn generator_resolves_imported_base() {
+    let code = utils::generate_file(&utils::test_file("extension_base_two_files", "input.xsd"));
+    println!("{}", code);
+
+    assert!(code.contains("pub a: f64"));
+    assert!(code.contains("pub b: i32"));
+    assert!(code.contains("pub c: String"));
 }
 
 #[test]
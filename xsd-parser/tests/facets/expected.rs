@@ -0,0 +1,139 @@
+/// XSD: `tns:Address`
+/// Facets: `pattern = (\d{1,3}\.){3}\d{1,3}`, `pattern = [a-z]+(\.[a-z]+)*`
+#[derive(Default, Clone, PartialEq, Debug, UtilsTupleIo, UtilsDefaultSerde)]
+pub struct Address (pub String);
+
+impl Validate for Address {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        errors.check(path, || {
+            if !xs::matches_pattern(&self.0, &["(\\d{1,3}\\.){3}\\d{1,3}", "[a-z]+(\\.[a-z]+)*"])? {
+                return Err(xs::ValidationError::new(xs::FacetKind::Pattern, "0", format!("matches one of {:?}", ["(\\d{1,3}\\.){3}\\d{1,3}", "[a-z]+(\\.[a-z]+)*"]), format!("{:?}", self.0)));
+            }
+            Ok(())
+        });
+    }
+}
+
+/// XSD: `tns:Identifier`
+/// Facets: `pattern = [\i-[:]][\c-[:]]*`
+#[derive(Default, Clone, PartialEq, Debug, UtilsTupleIo, UtilsDefaultSerde)]
+pub struct Identifier (pub String);
+
+impl Validate for Identifier {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        errors.check(path, || {
+            if !xs::matches_pattern(&self.0, &["[\\i-[:]][\\c-[:]]*"])? {
+                return Err(xs::ValidationError::new(xs::FacetKind::Pattern, "0", format!("matches one of {:?}", ["[\\i-[:]][\\c-[:]]*"]), format!("{:?}", self.0)));
+            }
+            Ok(())
+        });
+    }
+}
+
+/// XSD: `tns:Price`
+/// Facets: `totalDigits = 5`, `fractionDigits = 2`
+#[derive(Default, Clone, PartialEq, Debug, UtilsTupleIo, UtilsDefaultSerde)]
+pub struct Price (pub xs::Decimal);
+
+impl Validate for Price {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        errors.check(path, || {
+            if xs::total_digits(&self.0.to_string()).unwrap_or(usize::MAX) > 5 {
+                return Err(xs::ValidationError::new(xs::FacetKind::TotalDigits, "0", "has at most 5 digits", self.0.to_string()));
+            }
+            if xs::fraction_digits(&self.0.to_string()).unwrap_or(usize::MAX) > 2 {
+                return Err(xs::ValidationError::new(xs::FacetKind::FractionDigits, "0", "has at most 2 fraction digits", self.0.to_string()));
+            }
+            Ok(())
+        });
+    }
+}
+
+/// XSD: `tns:Size`
+/// Facets: `enumeration = extra small`, `enumeration = extra large`
+#[derive(Default, Clone, PartialEq, Debug, UtilsTupleIo, UtilsDefaultSerde)]
+pub struct Size (pub String);
+
+impl Validate for Size {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        errors.check(path, || {
+            if !["extra small", "extra large"].contains(&self.0.as_str()) {
+                return Err(xs::ValidationError::new(xs::FacetKind::Enumeration, "0", format!("is one of {:?}", ["extra small", "extra large"]), format!("{:?}", self.0)));
+            }
+            Ok(())
+        });
+    }
+}
+
+/// XSD: `tns:Ratio`
+/// Facets: `enumeration = 0.5`, `enumeration = 1.5`
+#[derive(Default, Clone, PartialEq, Debug, UtilsTupleIo, UtilsDefaultSerde)]
+pub struct Ratio (pub f64);
+
+impl Validate for Ratio {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        errors.check(path, || {
+            if !["0.5", "1.5"].iter().any(|value| value.parse::<f64>().is_ok_and(|value| value == self.0)) {
+                return Err(xs::ValidationError::new(xs::FacetKind::Enumeration, "0", format!("is one of {:?}", ["0.5", "1.5"]), format!("{:?}", self.0)));
+            }
+            Ok(())
+        });
+    }
+}
+
+/// XSD: `tns:Label`
+/// Facets: `whiteSpace = collapse`, `maxLength = 7`
+#[derive(Default, Clone, PartialEq, Debug, UtilsTupleIo, UtilsDefaultSerde)]
+pub struct Label (pub String);
+
+impl Validate for Label {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        errors.check(path, || {
+            let normalized = xs::collapse_white_space(&self.0);
+            if normalized.len() > 7 {
+                return Err(xs::ValidationError::new(xs::FacetKind::MaxLength, "0", "<= 7", normalized.len()));
+            }
+            Ok(())
+        });
+    }
+}
+
+/// XSD: `tns:Amount`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct Amount {
+    /// XSD: attribute `currency` of type `xs:string`
+    #[yaserde(attribute, rename = "currency")]
+    pub currency: Option<String>,
+
+    /// Facets: `pattern = \d+`
+    #[yaserde(text)]
+    pub value: String,
+}
+
+impl Validate for Amount {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        errors.check(path, || {
+            self.value.parse::<i32>().map_err(|_| xs::ValidationError::new(xs::FacetKind::Type, "value", "is a valid i32", format!("{:?}", self.value)))?;
+            if !xs::matches_pattern(&self.value, &["\\d+"])? {
+                return Err(xs::ValidationError::new(xs::FacetKind::Pattern, "value", format!("matches one of {:?}", ["\\d+"]), format!("{:?}", self.value)));
+            }
+            Ok(())
+        });
+    }
+}
+
+/// XSD: `tns:AmountBase`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct AmountBase {
+    /// XSD: attribute `currency` of type `xs:string`
+    #[yaserde(attribute, rename = "currency")]
+    pub currency: Option<String>,
+
+    #[yaserde(text)]
+    pub value: String,
+}
+
+impl Validate for AmountBase {}
+
@@ -0,0 +1,68 @@
+use super::utils;
+
+mod expected {
+    use std::str::FromStr;
+
+    use xsd_macro_utils::*;
+    use xsd_parser::generator::validator::Validate;
+    use xsd_types::types as xs;
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    include!("expected.rs");
+}
+
+#[test]
+fn deserialization_works() {
+    let ser = include_str!("example.xml");
+
+    let de: expected::Amount = yaserde::de::from_str(ser).unwrap();
+
+    assert_eq!(de, expected::Amount { currency: Some("EUR".to_string()), value: "42".to_string() });
+}
+
+#[test]
+fn validation_works() {
+    use std::str::FromStr;
+
+    use xsd_parser::generator::validator::Validate;
+
+    // Patterns of a type are alternatives
+    assert!(expected::Address("192.168.0.1".to_string()).validate().is_ok());
+    assert!(expected::Address("example.com".to_string()).validate().is_ok());
+    assert!(expected::Address("192.168.0.1/24".to_string()).validate().is_err());
+
+    // XSD escapes and character class subtraction
+    assert!(expected::Identifier("xs.id-1".to_string()).validate().is_ok());
+    assert!(expected::Identifier("xs:id".to_string()).validate().is_err());
+    assert!(expected::Identifier("1id".to_string()).validate().is_err());
+
+    assert!(expected::Price::from_str("123.45").unwrap().validate().is_ok());
+    assert!(expected::Price::from_str("12345.6").unwrap().validate().is_err());
+    assert!(expected::Price::from_str("1.234").unwrap().validate().is_err());
+
+    assert!(expected::Size("extra small".to_string()).validate().is_ok());
+    assert!(expected::Size("medium".to_string()).validate().is_err());
+
+    assert!(expected::Ratio(1.5).validate().is_ok());
+    assert!(expected::Ratio(2.5).validate().is_err());
+
+    // Other facets are checked against the value with collapsed white space
+    assert!(expected::Label("a label".to_string()).validate().is_ok());
+    assert!(expected::Label(" a \t label\n".to_string()).validate().is_ok());
+    assert!(expected::Label("a long label".to_string()).validate().is_err());
+
+    let amount = |value: &str| expected::Amount { currency: None, value: value.to_string() };
+    assert!(amount("42").validate().is_ok());
+    assert!(amount("-42").validate().is_err());
+    assert!(amount("large").validate().is_err());
+}
+
+#[test]
+fn generator_does_not_panic() {
+    println!("{}", utils::generate(include_str!("input.xsd")))
+}
+
+#[test]
+fn generator_output_has_correct_ast() {
+    utils::ast_test(include_str!("input.xsd"), include_str!("expected.rs"));
+}
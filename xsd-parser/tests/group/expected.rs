@@ -0,0 +1,40 @@
+/// XSD: `tns:FooType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct FooType {
+    /// XSD: element `Id` of type `xs:int`
+    #[yaserde(prefix = "tns", rename = "Id")]
+    pub id: i32,
+
+    /// XSD: element `Name` of type `xs:string`
+    #[yaserde(prefix = "tns", rename = "Name")]
+    pub name: Option<String>,
+
+    /// XSD: element `Value` of type `xs:int`
+    #[yaserde(prefix = "tns", rename = "Value")]
+    pub value: i32,
+
+    /// XSD: element `Tag` of type `xs:string`
+    #[yaserde(prefix = "tns", rename = "Tag")]
+    pub tag: Vec<String>,
+}
+
+impl Validate for FooType {}
+
+/// XSD: `tns:BarType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct BarType {
+    /// XSD: element `Id` of type `xs:int`
+    #[yaserde(prefix = "tns", rename = "Id")]
+    pub id: i32,
+
+    /// XSD: element `Name` of type `xs:string`
+    #[yaserde(prefix = "tns", rename = "Name")]
+    pub name: Option<String>,
+}
+
+impl Validate for BarType {}
+
+// pub type Foo = FooType;
+
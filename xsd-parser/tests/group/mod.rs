@@ -0,0 +1,75 @@
+use super::utils;
+
+#[test]
+fn deserialization_works() {
+    mod expected {
+        use xsd_parser::generator::validator::Validate;
+        use yaserde_derive::{YaDeserialize, YaSerialize};
+
+        include!("expected.rs");
+    }
+
+    let ser = include_str!("example.xml");
+
+    let de: expected::FooType = yaserde::de::from_str(ser).unwrap();
+
+    assert_eq!(
+        de,
+        expected::FooType {
+            id: 1,
+            name: Some("foo".to_string()),
+            value: 42,
+            tag: vec!["a".to_string(), "b".to_string()]
+        }
+    );
+}
+
+#[test]
+fn generator_does_not_panic() {
+    println!("{}", utils::generate(include_str!("input.xsd")))
+}
+
+#[test]
+fn generator_output_has_correct_ast() {
+    utils::ast_test(include_str!("input.xsd"), include_str!("expected.rs"));
+}
+
+#[test]
+fn parser_reports_unresolved_groups() {
+    use xsd_parser::parser::{error::ParseError, parse};
+
+    let schema = |content: &str| {
+        format!(
+            r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"
+                xmlns:tns="http://example.com" targetNamespace="http://example.com">
+                {}
+            </xs:schema>"#,
+            content
+        )
+    };
+
+    let missing = r#"<xs:complexType name="FooType">
+            <xs:sequence><xs:group ref="tns:Missing"/></xs:sequence>
+        </xs:complexType>"#;
+    match parse(&schema(missing)).unwrap_err() {
+        ParseError::UnresolvedReference { kind, name, referrer } => {
+            assert_eq!((kind.as_str(), referrer.as_str()), ("group", "FooType"));
+            assert_eq!(name, "{http://example.com}Missing");
+        }
+        err => panic!("{}", err),
+    }
+
+    let cyclic = r#"<xs:group name="Header">
+            <xs:sequence><xs:group ref="tns:Body"/></xs:sequence>
+        </xs:group>
+        <xs:group name="Body">
+            <xs:sequence><xs:group ref="tns:Header"/></xs:sequence>
+        </xs:group>"#;
+    match parse(&schema(cyclic)).unwrap_err() {
+        ParseError::CyclicReference { kind, names } => {
+            assert_eq!(kind, "group");
+            assert_eq!(names.len(), 3);
+        }
+        err => panic!("{}", err),
+    }
+}
@@ -0,0 +1,12 @@
+use xsd_parser::parser::{json::to_json, parse};
+
+// The exported JSON is a stable format: a change of this output must come with a new
+// FORMAT_VERSION, see xsd_parser::parser::json.
+#[test]
+fn json_matches_snapshot() {
+    let files = [parse(include_str!("input.xsd")).unwrap()];
+
+    let json = to_json(&files).unwrap();
+    println!("{}", json);
+    assert_eq!(json.trim_end(), include_str!("expected.json").trim_end());
+}
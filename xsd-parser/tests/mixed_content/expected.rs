@@ -0,0 +1,113 @@
+/// XSD: `tns:ParagraphType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct ParagraphType {
+    /// XSD: attribute `lang` of type `xs:string`
+    #[yaserde(attribute, rename = "lang")]
+    pub lang: Option<String>,
+
+    #[yaserde(flatten)]
+    pub content: xs::Mixed<paragraph_type::ParagraphTypeContent>,
+}
+
+impl Validate for ParagraphType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        self.content.validate_at(path, errors);
+    }
+}
+
+pub mod paragraph_type {
+    use super::*;
+
+    #[derive(PartialEq, Debug, Clone, UtilsChoiceSerDe)]
+    #[choice(namespace = "tns: http://example.com", siblings = "")]
+    pub enum ParagraphTypeContent {
+        #[choice(text)]
+        Text(String),
+        /// XSD: element `Bold` of type `xs:string`
+        #[choice(element = "tns:Bold")]
+        Bold(String),
+        /// XSD: element `Link` of type `tns:LinkType`
+        #[choice(element = "tns:Link")]
+        Link(LinkType),
+        __Unknown__(String),
+    }
+
+    impl Default for ParagraphTypeContent {
+        fn default() -> ParagraphTypeContent {
+            Self::__Unknown__("No valid variants".into())
+        }
+    }
+
+    impl Validate for ParagraphTypeContent {
+        fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+            if let Self::Link(value) = self {
+                value.validate_at(&format!("{}/Link", path), errors);
+            }
+        }
+    }
+}
+
+/// XSD: `tns:LinkType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct LinkType {
+    /// XSD: attribute `href` of type `xs:string`
+    #[yaserde(attribute, rename = "href")]
+    pub href: String,
+
+    #[yaserde(text)]
+    pub value: String,
+}
+
+impl Validate for LinkType {}
+
+/// XSD: `tns:NoteType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct NoteType {
+    #[yaserde(flatten)]
+    pub content: xs::Mixed<note_type::NoteTypeContent>,
+}
+
+impl Validate for NoteType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        errors.check(path, || {
+            self.content.validate_sequence(&[
+                xs::MixedStep { name: "Title", min: 1, max: Some(1) },
+                xs::MixedStep { name: "Ref", min: 0, max: Some(2) }
+            ])?;
+            Ok(())
+        });
+        self.content.validate_at(path, errors);
+    }
+}
+
+pub mod note_type {
+    use super::*;
+
+    #[derive(PartialEq, Debug, Clone, UtilsChoiceSerDe)]
+    #[choice(namespace = "tns: http://example.com", siblings = "")]
+    pub enum NoteTypeContent {
+        #[choice(text)]
+        Text(String),
+        /// XSD: element `Title` of type `xs:string`
+        #[choice(element = "tns:Title")]
+        Title(String),
+        /// XSD: element `Ref` of type `xs:string`
+        #[choice(element = "tns:Ref")]
+        Ref(String),
+        __Unknown__(String),
+    }
+
+    impl Default for NoteTypeContent {
+        fn default() -> NoteTypeContent {
+            Self::__Unknown__("No valid variants".into())
+        }
+    }
+
+    impl Validate for NoteTypeContent {}
+}
+
+// pub type Paragraph = ParagraphType;
+// pub type Note = NoteType;
@@ -0,0 +1,111 @@
+use super::utils;
+
+mod expected {
+    use xsd_macro_utils::UtilsChoiceSerDe;
+    use xsd_parser::generator::validator::Validate;
+    use xsd_types::types as xs;
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    include!("expected.rs");
+}
+
+use expected::paragraph_type::ParagraphTypeContent;
+
+#[test]
+fn deserialization_works() {
+    let ser = include_str!("example.xml");
+
+    let de: expected::ParagraphType = yaserde::de::from_str(ser).unwrap();
+
+    assert_eq!(de.lang.as_deref(), Some("en"));
+    assert_eq!(
+        de.content.0,
+        vec![
+            ParagraphTypeContent::Text("See".into()),
+            ParagraphTypeContent::Link(expected::LinkType {
+                href: "http://example.com/docs".into(),
+                value: "the docs".into(),
+            }),
+            ParagraphTypeContent::Text(",".into()),
+            ParagraphTypeContent::Bold("now".into()),
+            ParagraphTypeContent::Text("!".into()),
+        ]
+    );
+    assert_eq!(de.content.text(), "See,!");
+}
+
+#[test]
+fn serialization_round_trip() {
+    let de: expected::ParagraphType = yaserde::de::from_str(include_str!("example.xml")).unwrap();
+
+    let ser = yaserde::ser::to_string(&de).unwrap();
+    println!("{}", ser);
+    assert!(ser.contains(r#"lang="en">See<tns:Link href="http://example.com/docs">"#));
+    assert!(ser.contains("</tns:Link>,<tns:Bold>now</tns:Bold>!</"));
+
+    let round_trip: expected::ParagraphType = yaserde::de::from_str(&ser).unwrap();
+    assert_eq!(round_trip, de);
+}
+
+#[test]
+fn deserialization_rejects_undeclared_elements() {
+    let ser = r#"<tns:Paragraph xmlns:tns="http://example.com">See<tns:Italic>it</tns:Italic></tns:Paragraph>"#;
+
+    assert!(yaserde::de::from_str::<expected::ParagraphType>(ser).is_err());
+}
+
+#[test]
+fn validation_checks_sequence_order_and_occurs() {
+    use expected::note_type::NoteTypeContent;
+    use xsd_parser::generator::validator::Validate;
+    use xsd_types::types::FacetKind;
+
+    let note = |xml: &str| -> expected::NoteType {
+        let xml = format!(r#"<tns:Note xmlns:tns="http://example.com">{}</tns:Note>"#, xml);
+        yaserde::de::from_str(&xml).unwrap()
+    };
+    let error_kind = |note: expected::NoteType| note.validate().unwrap_err()[0].kind;
+
+    let valid = note("See <tns:Title>A</tns:Title> and <tns:Ref>1</tns:Ref><tns:Ref>2</tns:Ref>.");
+    assert_eq!(
+        valid.content.0,
+        vec![
+            NoteTypeContent::Text("See".into()),
+            NoteTypeContent::Title("A".into()),
+            NoteTypeContent::Text("and".into()),
+            NoteTypeContent::Ref("1".into()),
+            NoteTypeContent::Ref("2".into()),
+            NoteTypeContent::Text(".".into()),
+        ]
+    );
+    assert!(valid.validate().is_ok());
+    assert!(note("<tns:Title>A</tns:Title>").validate().is_ok());
+
+    assert_eq!(error_kind(note("text <tns:Ref>1</tns:Ref>")), FacetKind::MinOccurs);
+    assert_eq!(error_kind(note("<tns:Ref>1</tns:Ref><tns:Title>A</tns:Title>")), FacetKind::Other);
+    assert_eq!(
+        error_kind(note("<tns:Title>A</tns:Title><tns:Ref>1</tns:Ref><tns:Title>B</tns:Title>")),
+        FacetKind::MaxOccurs
+    );
+    assert_eq!(
+        error_kind(note(
+            "<tns:Title>A</tns:Title><tns:Ref>1</tns:Ref><tns:Ref>2</tns:Ref><tns:Ref>3</tns:Ref>"
+        )),
+        FacetKind::MaxOccurs
+    );
+
+    let errors = note("<tns:Ref>1</tns:Ref><tns:Title>A</tns:Title>").validate().unwrap_err();
+    assert_eq!(errors[0].path, "");
+    assert_eq!(errors[0].expected, "in the order Title, Ref");
+    assert_eq!(errors[0].actual, "child element 2");
+}
+
+#[test]
+fn generator_does_not_panic() {
+    println!("{}", utils::generate(include_str!("input.xsd")))
+}
+
+#[test]
+fn generator_output_has_correct_ast() {
+    utils::ast_test(include_str!("input.xsd"), include_str!("expected.rs"));
+}
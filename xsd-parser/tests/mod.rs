@@ -1,18 +1,38 @@
+// Not every type of expected.rs is used in (de)serialization tests and yaserde_derive expands
+// to impl blocks inside test functions.
+#![allow(dead_code, non_local_definitions)]
+
 #[macro_use]
 mod utils;
 mod all;
 mod any;
+mod any_attribute;
 mod choice;
+mod choice_nested;
 mod complex_type;
 mod complex_type_subtypes_clash;
+mod default_value;
+mod element_form;
 mod enumeration;
 mod extension_base;
 mod extension_base_multilayer;
 mod extension_base_two_files;
+mod facets;
+mod group;
+mod json_export;
+mod mixed_content;
+mod name_clash;
+mod nested_validation;
+mod nillable;
+mod occurs;
+mod qualified_names;
+mod recursion;
 mod ref_to_attribute;
 mod rename_only_where_needed;
 mod restriction_any_type;
+mod simple_content_restriction;
 mod simple_type;
+mod substitution_group;
 mod tuple_with_integer;
 mod tuple_with_string;
 mod tuple_with_vec;
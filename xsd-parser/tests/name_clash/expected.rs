@@ -0,0 +1,76 @@
+/// XSD: `tns:BaseType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct BaseType {
+    /// XSD: attribute `id` of type `xs:string`
+    #[yaserde(attribute, rename = "id")]
+    pub id: Option<String>,
+}
+
+impl Validate for BaseType {}
+
+/// XSD: `tns:FooType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct FooType {
+    /// XSD: element `Id` of type `tns:IdType`
+    #[yaserde(prefix = "tns", rename = "Id")]
+    pub id: IdType,
+
+    /// XSD: element `Mode` of type `tns:mode2`
+    #[yaserde(prefix = "tns", rename = "Mode")]
+    pub mode: Mode2,
+
+    /// XSD: element `OtherMode` of type `tns:Mode`
+    #[yaserde(prefix = "tns", rename = "OtherMode")]
+    pub other_mode: Mode,
+
+    /// XSD: attribute `id` of type `xs:string`
+    #[yaserde(attribute, rename = "id")]
+    pub id_2: Option<String>,
+}
+
+impl Validate for FooType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        self.id.validate_at(&format!("{}/Id", path), errors);
+        self.mode.validate_at(&format!("{}/Mode", path), errors);
+        self.other_mode.validate_at(&format!("{}/OtherMode", path), errors);
+    }
+}
+
+/// XSD: `tns:IdType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct IdType {
+    /// XSD: element `Value` of type `xs:string`
+    #[yaserde(prefix = "tns", rename = "Value")]
+    pub value: String,
+}
+
+impl Validate for IdType {}
+
+/// XSD: `tns:Mode`
+#[derive(PartialEq, Debug, Clone, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub enum Mode {
+    #[yaserde(rename = "on")]
+    On,
+    #[yaserde(rename = "On")]
+    On2,
+    __Unknown__(String),
+}
+
+impl Default for Mode {
+    fn default() -> Mode {
+        Self::__Unknown__("No valid variants".into())
+    }
+}
+
+impl Validate for Mode {}
+
+/// XSD: `tns:mode`
+#[derive(Default, Clone, PartialEq, Debug, UtilsTupleIo, UtilsDefaultSerde)]
+pub struct Mode2 (pub i32);
+
+impl Validate for Mode2 {}
+// pub type Foo = FooType;
@@ -0,0 +1,160 @@
+/// XSD: `tns:Width`
+/// Facets: `minInclusive = 1`, `maxInclusive = 1920`
+#[derive(Default, Clone, PartialEq, Debug, UtilsTupleIo, UtilsDefaultSerde)]
+pub struct Width (pub i32);
+
+impl Validate for Width {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        errors.check(path, || {
+            if self.0 < "1".parse::<i32>().unwrap() {
+                return Err(xs::ValidationError::new(xs::FacetKind::MinInclusive, "0", ">= 1", self.0.to_string()));
+            }
+            if self.0 > "1920".parse::<i32>().unwrap() {
+                return Err(xs::ValidationError::new(xs::FacetKind::MaxInclusive, "0", "<= 1920", self.0.to_string()));
+            }
+            Ok(())
+        });
+    }
+}
+
+/// XSD: `tns:Token`
+/// Facets: `maxLength = 8`
+#[derive(Default, Clone, PartialEq, Debug, UtilsTupleIo, UtilsDefaultSerde)]
+pub struct Token (pub String);
+
+impl Validate for Token {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        errors.check(path, || {
+            if self.0.len() > 8 {
+                return Err(xs::ValidationError::new(xs::FacetKind::MaxLength, "0", "<= 8", self.0.len()));
+            }
+            Ok(())
+        });
+    }
+}
+
+/// XSD: `tns:Resolution`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct Resolution {
+    /// XSD: element `Width` of type `tns:Width`
+    #[yaserde(prefix = "tns", rename = "Width")]
+    pub width: Width,
+
+    /// XSD: element `Height` of type `xs:int`
+    #[yaserde(prefix = "tns", rename = "Height")]
+    pub height: i32,
+}
+
+impl Validate for Resolution {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        self.width.validate_at(&format!("{}/Width", path), errors);
+    }
+}
+
+/// XSD: `tns:VideoEncoderConfiguration`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct VideoEncoderConfiguration {
+    /// XSD: element `Resolution` of type `tns:Resolution`
+    #[yaserde(prefix = "tns", rename = "Resolution")]
+    pub resolution: Option<Resolution>,
+
+    /// XSD: attribute `token` of type `tns:Token`
+    #[yaserde(attribute, rename = "token")]
+    pub token: Option<Token>,
+}
+
+impl Validate for VideoEncoderConfiguration {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        self.resolution.validate_at(&format!("{}/Resolution", path), errors);
+        self.token.validate_at(&format!("{}/@token", path), errors);
+    }
+}
+
+/// XSD: `tns:Source`
+#[derive(PartialEq, Debug, Clone, UtilsChoiceSerDe)]
+#[choice(namespace = "tns: http://example.com", siblings = "")]
+pub enum SourceChoice {
+    /// XSD: element `Fixed` of type `tns:Resolution`
+    #[choice(element = "tns:Fixed")]
+    Fixed(Resolution),
+    /// XSD: element `Label` of type `tns:Token`
+    #[choice(element = "tns:Label")]
+    Label(Token),
+    __Unknown__(String),
+}
+
+impl Default for SourceChoice {
+    fn default() -> SourceChoice {
+        Self::__Unknown__("No valid variants".into())
+    }
+}
+
+impl Validate for SourceChoice {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        match self {
+            Self::Fixed(value) => value.validate_at(&format!("{}/Fixed", path), errors),
+            Self::Label(value) => value.validate_at(&format!("{}/Label", path), errors),
+            _ => (),
+        }
+    }
+}
+
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct Source {
+    #[yaserde(flatten)]
+    pub source_choice: SourceChoice,
+}
+
+impl Validate for Source {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        self.source_choice.validate_at(path, errors);
+    }
+}
+
+/// XSD: `tns:Profile`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct Profile {
+    /// XSD: element `VideoEncoderConfiguration` of type `tns:VideoEncoderConfiguration`
+    #[yaserde(prefix = "tns", rename = "VideoEncoderConfiguration")]
+    pub video_encoder_configuration: Vec<VideoEncoderConfiguration>,
+
+    /// XSD: element `Source` of type `tns:Source`
+    #[yaserde(prefix = "tns", rename = "Source")]
+    pub source: Source,
+}
+
+impl Validate for Profile {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        errors.check(&format!("{}/VideoEncoderConfiguration", path), || {
+            #[allow(clippy::len_zero)]
+            if self.video_encoder_configuration.len() < 1 {
+                return Err(xs::ValidationError::new(xs::FacetKind::MinOccurs, "video_encoder_configuration", ">= 1", self.video_encoder_configuration.len()));
+            }
+            if self.video_encoder_configuration.len() > 2 {
+                return Err(xs::ValidationError::new(xs::FacetKind::MaxOccurs, "video_encoder_configuration", "<= 2", self.video_encoder_configuration.len()));
+            }
+            Ok(())
+        });
+        self.video_encoder_configuration.validate_at(&format!("{}/VideoEncoderConfiguration", path), errors);
+        self.source.validate_at(&format!("{}/Source", path), errors);
+    }
+}
+
+/// XSD: `tns:GetProfileResponse`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct GetProfileResponse {
+    /// XSD: element `Profile` of type `tns:Profile`
+    #[yaserde(prefix = "tns", rename = "Profile")]
+    pub profile: Profile,
+}
+
+impl Validate for GetProfileResponse {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        self.profile.validate_at(&format!("{}/Profile", path), errors);
+    }
+}
@@ -0,0 +1,74 @@
+use super::utils;
+
+mod expected {
+    use std::str::FromStr;
+
+    use xsd_macro_utils::*;
+    use xsd_parser::generator::validator::Validate;
+    use xsd_types::types as xs;
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    include!("expected.rs");
+}
+
+#[test]
+fn deserialization_works() {
+    let ser = include_str!("example.xml");
+
+    let de: expected::GetProfileResponse = yaserde::de::from_str(ser).unwrap();
+
+    let configuration = &de.profile.video_encoder_configuration[0];
+    assert_eq!(configuration.token, Some(expected::Token("encoder".to_string())));
+    assert_eq!(configuration.resolution.as_ref().unwrap().width, expected::Width(1280));
+    assert_eq!(
+        de.profile.source.source_choice,
+        expected::SourceChoice::Label(expected::Token("main".to_string()))
+    );
+}
+
+#[test]
+fn validation_works() {
+    use xsd_parser::generator::validator::{FacetKind, Validate};
+
+    let ser = include_str!("example.xml");
+    let mut de: expected::GetProfileResponse = yaserde::de::from_str(ser).unwrap();
+    assert!(de.validate().is_ok());
+
+    // Errors of nested values are listed with the paths of their elements, repeated
+    // elements are numbered from 1
+    let configuration = &mut de.profile.video_encoder_configuration[0];
+    configuration.resolution.as_mut().unwrap().width = expected::Width(4096);
+    configuration.token = Some(expected::Token("encoder-1".to_string()));
+    de.profile.source.source_choice = expected::SourceChoice::Fixed(expected::Resolution {
+        width: expected::Width(0),
+        height: 0,
+    });
+    de.profile.video_encoder_configuration.extend(vec![Default::default(); 2]);
+
+    let errors = de.validate().unwrap_err();
+    let paths = errors.iter().map(|e| e.path.as_str()).collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        [
+            "/Profile/VideoEncoderConfiguration",
+            "/Profile/VideoEncoderConfiguration[1]/Resolution/Width",
+            "/Profile/VideoEncoderConfiguration[1]/@token",
+            "/Profile/Source/Fixed/Width",
+        ]
+    );
+    assert_eq!(errors[1].kind, FacetKind::MaxInclusive);
+    assert_eq!((errors[1].expected.as_str(), errors[1].actual.as_str()), ("<= 1920", "4096"));
+    assert_eq!(errors[2].kind, FacetKind::MaxLength);
+    assert_eq!((errors[2].expected.as_str(), errors[2].actual.as_str()), ("<= 8", "9"));
+    assert!(errors.to_string().contains("/Profile/Source/Fixed/Width: MinInclusive"));
+}
+
+#[test]
+fn generator_does_not_panic() {
+    println!("{}", utils::generate(include_str!("input.xsd")))
+}
+
+#[test]
+fn generator_output_has_correct_ast() {
+    utils::ast_test(include_str!("input.xsd"), include_str!("expected.rs"));
+}
@@ -0,0 +1,35 @@
+/// XSD: `tns:BarType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct BarType {
+    /// XSD: element `Name` of type `xs:string`
+    #[yaserde(prefix = "tns", rename = "Name")]
+    pub name: String,
+}
+
+impl Validate for BarType {}
+
+/// XSD: `tns:FooType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct FooType {
+    /// XSD: element `Count` of type `xs:int`
+    #[yaserde(prefix = "tns", rename = "Count")]
+    pub count: xs::NillableText<i32>,
+
+    /// XSD: element `Comment` of type `xs:string`
+    #[yaserde(prefix = "tns", rename = "Comment")]
+    pub comment: Option<xs::NillableText<String>>,
+
+    /// XSD: element `Bar` of type `tns:BarType`
+    #[yaserde(prefix = "tns", rename = "Bar")]
+    pub bar: xs::Nillable<BarType>,
+}
+
+impl Validate for FooType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        self.bar.validate_at(&format!("{}/Bar", path), errors);
+    }
+}
+
+// pub type Foo = FooType;
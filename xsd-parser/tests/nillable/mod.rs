@@ -0,0 +1,53 @@
+use super::utils;
+use xsd_types::types as xs;
+
+mod expected {
+    use xsd_parser::generator::validator::Validate;
+    use xsd_types::types as xs;
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    include!("expected.rs");
+}
+
+#[test]
+fn deserialization_works() {
+    let ser = include_str!("example.xml");
+
+    let de: expected::FooType = yaserde::de::from_str(ser).unwrap();
+
+    assert_eq!(
+        de,
+        expected::FooType {
+            count: xs::NillableText::Nil,
+            comment: Some(xs::NillableText::Value("text".to_string())),
+            bar: xs::Nillable::Nil,
+        }
+    );
+}
+
+#[test]
+fn serialization_round_trip() {
+    let foo = expected::FooType {
+        count: xs::NillableText::Value(3),
+        comment: Some(xs::NillableText::Nil),
+        bar: xs::Nillable::Value(expected::BarType { name: "bar".to_string() }),
+    };
+
+    let ser = yaserde::ser::to_string(&foo).unwrap();
+    println!("{}", ser);
+    assert!(ser.contains("<tns:Count>3</tns:Count>"));
+    assert!(ser.contains("xsi:nil=\"true\""));
+
+    let de: expected::FooType = yaserde::de::from_str(&ser).unwrap();
+    assert_eq!(de, foo);
+}
+
+#[test]
+fn generator_does_not_panic() {
+    println!("{}", utils::generate(include_str!("input.xsd")))
+}
+
+#[test]
+fn generator_output_has_correct_ast() {
+    utils::ast_test(include_str!("input.xsd"), include_str!("expected.rs"));
+}
@@ -0,0 +1,50 @@
+/// XSD: `tns:FooType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct FooType {
+    /// XSD: element `Name` of type `xs:string`
+    #[yaserde(prefix = "tns", rename = "Name")]
+    pub name: Vec<String>,
+
+    /// XSD: element `Tag` of type `xs:string`
+    #[yaserde(prefix = "tns", rename = "Tag")]
+    pub tag: Vec<String>,
+
+    /// XSD: element `Value` of type `xs:int`
+    #[yaserde(prefix = "tns", rename = "Value")]
+    pub value: Vec<i32>,
+
+    /// XSD: element `Point` of type `xs:int`
+    #[yaserde(prefix = "tns", rename = "Point")]
+    pub point: Vec<i32>,
+}
+
+impl Validate for FooType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        errors.check(&format!("{}/Name", path), || {
+            #[allow(clippy::len_zero)]
+            if self.name.len() < 2 {
+                return Err(xs::ValidationError::new(xs::FacetKind::MinOccurs, "name", ">= 2", self.name.len()));
+            }
+            if self.name.len() > 3 {
+                return Err(xs::ValidationError::new(xs::FacetKind::MaxOccurs, "name", "<= 3", self.name.len()));
+            }
+            Ok(())
+        });
+        errors.check(&format!("{}/Value", path), || {
+            #[allow(clippy::len_zero)]
+            if self.value.len() < 1 {
+                return Err(xs::ValidationError::new(xs::FacetKind::MinOccurs, "value", ">= 1", self.value.len()));
+            }
+            Ok(())
+        });
+        errors.check(&format!("{}/Point", path), || {
+            if self.point.len() > 4 {
+                return Err(xs::ValidationError::new(xs::FacetKind::MaxOccurs, "point", "<= 4", self.point.len()));
+            }
+            Ok(())
+        });
+    }
+}
+
+// pub type Foo = FooType;
@@ -0,0 +1,63 @@
+use super::utils;
+
+mod expected {
+    use xsd_parser::generator::validator::Validate;
+    use xsd_types::types as xs;
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    include!("expected.rs");
+}
+
+#[test]
+fn deserialization_works() {
+    use xsd_parser::generator::validator::Validate;
+
+    let ser = include_str!("example.xml");
+
+    let de: expected::FooType = yaserde::de::from_str(ser).unwrap();
+
+    assert_eq!(
+        de,
+        expected::FooType {
+            name: vec!["a".to_string(), "b".to_string()],
+            tag: vec![],
+            value: vec![1],
+            point: vec![2],
+        }
+    );
+    assert!(de.validate().is_ok());
+}
+
+#[test]
+fn validation_checks_bounds() {
+    use xsd_parser::generator::validator::Validate;
+
+    let valid = expected::FooType {
+        name: vec!["a".to_string(), "b".to_string()],
+        value: vec![1],
+        ..Default::default()
+    };
+    assert!(valid.validate().is_ok());
+
+    let too_few = expected::FooType { name: vec!["a".to_string()], ..valid.clone() };
+    assert!(too_few.validate().is_err());
+
+    let too_many = expected::FooType { name: vec!["a".to_string(); 4], ..valid.clone() };
+    assert!(too_many.validate().is_err());
+
+    let empty = expected::FooType { value: vec![], ..valid.clone() };
+    assert!(empty.validate().is_err());
+
+    let group_too_many = expected::FooType { point: vec![0; 5], ..valid };
+    assert!(group_too_many.validate().is_err());
+}
+
+#[test]
+fn generator_does_not_panic() {
+    println!("{}", utils::generate(include_str!("input.xsd")))
+}
+
+#[test]
+fn generator_output_has_correct_ast() {
+    utils::ast_test(include_str!("input.xsd"), include_str!("expected.rs"));
+}
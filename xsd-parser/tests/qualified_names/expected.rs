@@ -0,0 +1,9 @@
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct FooType {
+    #[yaserde(prefix = "tns", rename = "b")]
+    pub b: String,
+
+    #[yaserde(prefix = "tns", rename = "a")]
+    pub a: i32,
+}
@@ -0,0 +1,28 @@
+use super::utils;
+
+#[test]
+fn deserialization_works() {
+    mod expected {
+        use yaserde_derive::{YaDeserialize, YaSerialize};
+        include!("expected.rs");
+    }
+
+    let ser = include_str!("example.xml");
+
+    let de: expected::FooType = yaserde::de::from_str(ser).unwrap();
+
+    assert_eq!(de, expected::FooType { b: "string".to_string(), a: 3 });
+}
+
+#[test]
+fn generator_resolves_qualified_names() {
+    let code = utils::generate_file(&utils::test_file("qualified_names", "input.xsd"));
+    println!("{}", code);
+
+    let (foo, bar) = code.split_once("pub struct BarType").unwrap();
+    // ex:BaseType is the local BaseType, xsd:int is a built-in type
+    assert!(foo.contains("pub a: i32"));
+    // other:BaseType is the BaseType of the imported namespace
+    assert!(bar.contains("pub c: f64"));
+    assert!(!bar.contains("pub a: i32"));
+}
@@ -0,0 +1,85 @@
+/// XSD: `tns:NodeType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct NodeType {
+    /// XSD: element `Name` of type `xs:string`
+    #[yaserde(prefix = "tns", rename = "Name")]
+    pub name: String,
+
+    /// XSD: element `Next` of type `tns:NodeType`
+    #[yaserde(prefix = "tns", rename = "Next")]
+    pub next: Option<xs::Boxed<NodeType>>,
+
+    /// XSD: element `Children` of type `tns:NodeType`
+    #[yaserde(prefix = "tns", rename = "Children")]
+    pub children: Vec<NodeType>,
+}
+
+impl Validate for NodeType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        self.next.validate_at(&format!("{}/Next", path), errors);
+        self.children.validate_at(&format!("{}/Children", path), errors);
+    }
+}
+
+/// XSD: `tns:FolderType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct FolderType {
+    /// XSD: element `Name` of type `xs:string`
+    #[yaserde(prefix = "tns", rename = "Name")]
+    pub name: String,
+
+    /// XSD: element `Entry` of type `tns:EntryType`
+    #[yaserde(prefix = "tns", rename = "Entry")]
+    pub entry: Option<EntryType>,
+}
+
+impl Validate for FolderType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        self.entry.validate_at(&format!("{}/Entry", path), errors);
+    }
+}
+
+/// XSD: `tns:EntryType`
+#[derive(PartialEq, Debug, Clone, UtilsChoiceSerDe)]
+#[choice(namespace = "tns: http://example.com", siblings = "")]
+pub enum EntryTypeChoice {
+    /// XSD: element `File` of type `xs:string`
+    #[choice(element = "tns:File")]
+    File(String),
+    /// XSD: element `Folder` of type `tns:FolderType`
+    #[choice(element = "tns:Folder")]
+    Folder(xs::Boxed<FolderType>),
+    __Unknown__(String),
+}
+
+impl Default for EntryTypeChoice {
+    fn default() -> EntryTypeChoice {
+        Self::__Unknown__("No valid variants".into())
+    }
+}
+
+impl Validate for EntryTypeChoice {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        if let Self::Folder(value) = self {
+            value.validate_at(&format!("{}/Folder", path), errors);
+        }
+    }
+}
+
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct EntryType {
+    #[yaserde(flatten)]
+    pub entry_type_choice: EntryTypeChoice,
+}
+
+impl Validate for EntryType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        self.entry_type_choice.validate_at(path, errors);
+    }
+}
+
+// pub type Node = NodeType;
+// pub type Folder = FolderType;
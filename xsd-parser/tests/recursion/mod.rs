@@ -0,0 +1,86 @@
+use super::utils;
+
+mod expected {
+    use xsd_macro_utils::UtilsChoiceSerDe;
+    use xsd_parser::generator::validator::Validate;
+    use xsd_types::types as xs;
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    include!("expected.rs");
+}
+
+#[test]
+fn deserialization_works() {
+    use expected::{EntryType, EntryTypeChoice, FolderType};
+
+    let ser = include_str!("example.xml");
+
+    let de: FolderType = yaserde::de::from_str(ser).unwrap();
+
+    let docs = FolderType {
+        name: "docs".to_string(),
+        entry: Some(EntryType {
+            entry_type_choice: EntryTypeChoice::File("readme.txt".to_string()),
+        }),
+    };
+    assert_eq!(
+        de,
+        FolderType {
+            name: "root".to_string(),
+            entry: Some(EntryType { entry_type_choice: EntryTypeChoice::Folder(docs.into()) }),
+        }
+    );
+}
+
+#[test]
+fn serialization_round_trip() {
+    let de: expected::FolderType = yaserde::de::from_str(include_str!("example.xml")).unwrap();
+
+    let ser = yaserde::ser::to_string(&de).unwrap();
+    let again: expected::FolderType = yaserde::de::from_str(&ser).unwrap();
+    assert_eq!(again, de);
+}
+
+#[test]
+fn direct_recursion_works() {
+    // Derived deserializers take an element named like one of the fields of its own
+    // type (here `Next`) for that field, so a directly recursive type is only written
+    let third = expected::NodeType { name: "third".to_string(), ..Default::default() };
+    let second = expected::NodeType {
+        name: "second".to_string(),
+        next: Some(third.into()),
+        ..Default::default()
+    };
+    let first = expected::NodeType {
+        name: "first".to_string(),
+        next: Some(second.into()),
+        ..Default::default()
+    };
+
+    let ser = yaserde::ser::to_string(&first).unwrap();
+    assert!(ser.contains(
+        "<tns:Name>first</tns:Name><tns:Next><tns:Name>second</tns:Name>\
+         <tns:Next><tns:Name>third</tns:Name></tns:Next></tns:Next>"
+    ));
+}
+
+#[test]
+fn repeated_fields_are_not_boxed() {
+    let node = expected::NodeType {
+        name: "root".to_string(),
+        children: vec![expected::NodeType { name: "child".to_string(), ..Default::default() }],
+        ..Default::default()
+    };
+
+    assert_eq!(node.children[0].name, "child");
+}
+
+#[test]
+fn generator_does_not_panic() {
+    println!("{}", utils::generate(include_str!("input.xsd")))
+}
+
+#[test]
+fn generator_output_has_correct_ast() {
+    utils::ast_test(include_str!("input.xsd"), include_str!("expected.rs"));
+}
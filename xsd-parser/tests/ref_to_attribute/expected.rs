@@ -1,12 +1,20 @@
+/// XSD: `tns:id`
 #[derive(Default, Clone, PartialEq, Debug, UtilsTupleIo, UtilsDefaultSerde)]
 pub struct Id(pub String);
 
 impl Validate for Id {}
+
+/// XSD: `tns:FooType`
 #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
 #[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
 pub struct FooType {
+    /// XSD: attribute `tns:id`
     #[yaserde(attribute, prefix = "tns", rename = "id")]
     pub id: Option<Id>,
 }
 
-impl Validate for FooType {}
+impl Validate for FooType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        self.id.validate_at(&format!("{}/@id", path), errors);
+    }
+}
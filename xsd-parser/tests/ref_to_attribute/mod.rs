@@ -7,6 +7,7 @@ fn deserialization_works() {
 
         use xsd_macro_utils::*;
         use xsd_parser::generator::validator::Validate;
+        use xsd_types::types as xs;
         use yaserde_derive::{YaDeserialize, YaSerialize};
 
         include!("expected.rs");
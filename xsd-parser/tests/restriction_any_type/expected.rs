@@ -1,15 +1,36 @@
 // pub type AppSequence = AppSequenceType;
+/// XSD: `tns:AppSequenceType`
 #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
 #[yaserde(prefix = "tns", namespace = "tns: http://schemas.xmlsoap.org/ws/2005/04/discovery")]
 pub struct AppSequenceType {
+    /// XSD: attribute `InstanceId` of type `xs:unsignedInt`
     #[yaserde(attribute, rename = "InstanceId")]
     pub instance_id: u32,
 
+    /// XSD: attribute `SequenceId` of type `xs:anyURI`
     #[yaserde(attribute, rename = "SequenceId")]
     pub sequence_id: Option<String>,
 
+    /// XSD: attribute `MessageNumber` of type `xs:unsignedInt`
     #[yaserde(attribute, rename = "MessageNumber")]
     pub message_number: u32,
+
+    /// XSD: any attribute of namespace `##other`, processContents `lax`
+    /// Attributes are kept as raw XML.
+    #[yaserde(flatten)]
+    pub any_attribute: xs::AnyAttributes<AppSequenceType>,
+}
+
+impl xs::DeclaredAttributes for AppSequenceType {
+    const NAMES: &'static [(Option<&'static str>, &'static str)] = &[(None, "InstanceId"), (None, "SequenceId"), (None, "MessageNumber")];
+}
+
+impl Validate for AppSequenceType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        errors.check(path, || {
+            self.any_attribute.validate_namespace("##other", Some("http://schemas.xmlsoap.org/ws/2005/04/discovery"))?;
+            Ok(())
+        });
+    }
 }
 
-impl Validate for AppSequenceType {}
@@ -1,14 +1,15 @@
 use super::utils;
 
-#[test]
-fn deserialization_works() {
-    mod expected {
-        use xsd_parser::generator::validator::Validate;
-        use yaserde_derive::{YaDeserialize, YaSerialize};
+mod expected {
+    use xsd_parser::generator::validator::Validate;
+    use xsd_types::types as xs;
+    use yaserde_derive::{YaDeserialize, YaSerialize};
 
-        include!("expected.rs");
-    }
+    include!("expected.rs");
+}
 
+#[test]
+fn deserialization_works() {
     let ser = include_str!("example.xml");
 
     let de: expected::AppSequenceType = yaserde::de::from_str(ser).unwrap();
@@ -19,6 +20,7 @@ fn deserialization_works() {
             instance_id: 7,
             sequence_id: Some("http://www.company.org/cum/sonoras".to_string()),
             message_number: 7,
+            any_attribute: Default::default(),
         }
     );
 }
@@ -0,0 +1,48 @@
+/// XSD: `tns:SizeType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct SizeType {
+    /// XSD: attribute `unit` of type `xs:string`
+    #[yaserde(attribute, rename = "unit")]
+    pub unit: Option<String>,
+
+    /// XSD: attribute `comment` of type `xs:string`
+    #[yaserde(attribute, rename = "comment")]
+    pub comment: Option<String>,
+
+    #[yaserde(text)]
+    pub value: String,
+}
+
+impl Validate for SizeType {}
+
+/// XSD: `tns:FooType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct FooType {
+    /// XSD: attribute `unit` of type `xs:string`
+    #[yaserde(attribute, rename = "unit")]
+    pub unit: String,
+
+    /// Facets: `minInclusive = 1`, `maxInclusive = 100`
+    #[yaserde(text)]
+    pub value: String,
+}
+
+impl Validate for FooType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        errors.check(path, || {
+            let value = self.value.parse::<i32>().map_err(|_| xs::ValidationError::new(xs::FacetKind::Type, "value", "is a valid i32", format!("{:?}", self.value)))?;
+            if value < "1".parse::<i32>().unwrap() {
+                return Err(xs::ValidationError::new(xs::FacetKind::MinInclusive, "value", ">= 1", value.to_string()));
+            }
+            if value > "100".parse::<i32>().unwrap() {
+                return Err(xs::ValidationError::new(xs::FacetKind::MaxInclusive, "value", "<= 100", value.to_string()));
+            }
+            Ok(())
+        });
+    }
+}
+
+// pub type Foo = FooType;
+
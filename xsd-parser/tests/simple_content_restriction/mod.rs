@@ -0,0 +1,42 @@
+use super::utils;
+
+mod expected {
+    use xsd_parser::generator::validator::Validate;
+    use xsd_types::types as xs;
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    include!("expected.rs");
+}
+
+#[test]
+fn deserialization_works() {
+    let ser = include_str!("example.xml");
+
+    let de: expected::FooType = yaserde::de::from_str(ser).unwrap();
+
+    assert_eq!(de, expected::FooType { unit: "cm".to_string(), value: "42".to_string() });
+}
+
+#[test]
+fn validation_works() {
+    use xsd_parser::generator::validator::Validate;
+
+    let foo = expected::FooType { unit: "cm".to_string(), value: "42".to_string() };
+    assert!(foo.validate().is_ok());
+
+    let foo = expected::FooType { unit: "cm".to_string(), value: "101".to_string() };
+    assert!(foo.validate().is_err());
+
+    let foo = expected::FooType { unit: "cm".to_string(), value: "large".to_string() };
+    assert!(foo.validate().is_err());
+}
+
+#[test]
+fn generator_does_not_panic() {
+    println!("{}", utils::generate(include_str!("input.xsd")))
+}
+
+#[test]
+fn generator_output_has_correct_ast() {
+    utils::ast_test(include_str!("input.xsd"), include_str!("expected.rs"));
+}
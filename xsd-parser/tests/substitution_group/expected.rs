@@ -0,0 +1,79 @@
+/// XSD: `tns:CircleType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct CircleType {
+    /// XSD: element `Radius` of type `xs:int`
+    #[yaserde(prefix = "tns", rename = "Radius")]
+    pub radius: i32,
+}
+
+impl Validate for CircleType {}
+
+/// XSD: `tns:SquareType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct SquareType {
+    /// XSD: element `Side` of type `xs:int`
+    #[yaserde(prefix = "tns", rename = "Side")]
+    pub side: i32,
+}
+
+impl Validate for SquareType {}
+
+/// XSD: `tns:Shape`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct Shape {}
+
+impl Validate for Shape {}
+
+// pub type Circle = CircleType;
+// pub type Square = SquareType;
+
+/// XSD: `tns:FooType`
+#[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+#[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
+pub struct FooType {
+    /// XSD: element `Name` of type `xs:string`
+    #[yaserde(prefix = "tns", rename = "Name")]
+    pub name: String,
+
+    #[yaserde(flatten)]
+    pub shape: ShapeGroup,
+}
+
+impl Validate for FooType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        self.shape.validate_at(path, errors);
+    }
+}
+
+// pub type Foo = FooType;
+
+#[derive(PartialEq, Debug, Clone, UtilsChoiceSerDe)]
+#[choice(namespace = "tns: http://example.com")]
+pub enum ShapeGroup {
+    /// XSD: element `Circle` of type `tns:CircleType`
+    #[choice(element = "tns:Circle")]
+    Circle(CircleType),
+    /// XSD: element `Square` of type `tns:SquareType`
+    #[choice(element = "tns:Square")]
+    Square(SquareType),
+    __Unknown__(String),
+}
+
+impl Default for ShapeGroup {
+    fn default() -> ShapeGroup {
+        Self::__Unknown__("No valid variants".into())
+    }
+}
+
+impl Validate for ShapeGroup {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        match self {
+            Self::Circle(value) => value.validate_at(&format!("{}/Circle", path), errors),
+            Self::Square(value) => value.validate_at(&format!("{}/Square", path), errors),
+            _ => (),
+        }
+    }
+}
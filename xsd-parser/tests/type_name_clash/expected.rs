@@ -1,6 +1,8 @@
+/// XSD: `tns:BarType`
 #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
 #[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
 pub struct BarType {
+    /// XSD: attribute `a` of type `xs:string`
     #[yaserde(attribute, rename = "a")]
     pub a: Option<String>,
 }
@@ -8,14 +10,20 @@ pub struct BarType {
 impl Validate for BarType {}
 
 
+/// XSD: `tns:FooType`
 #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
 #[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
 pub struct FooType {
+    /// XSD: element `Bar`
     #[yaserde(prefix = "tns", rename = "Bar")]
     pub bar: foo_type::BarType,
 }
 
-impl Validate for FooType {}
+impl Validate for FooType {
+    fn validate_at(&self, path: &str, errors: &mut xs::ValidationErrors) {
+        self.bar.validate_at(&format!("{}/Bar", path), errors);
+    }
+}
 
 pub mod foo_type {
     use super::*;
@@ -23,9 +31,11 @@ pub mod foo_type {
     #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
     #[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
     pub struct BarType {
+        /// XSD: attribute `b` of type `xs:string`
         #[yaserde(attribute, rename = "b")]
         pub b: Option<String>,
 
+        /// XSD: attribute `a` of type `xs:string`
         #[yaserde(attribute, rename = "a")]
         pub a: Option<String>,
 
@@ -4,6 +4,7 @@ use super::utils;
 fn deserialization_works() {
     mod expected {
         use xsd_parser::generator::validator::Validate;
+        use xsd_types::types as xs;
         use yaserde_derive::{YaDeserialize, YaSerialize};
 
         include!("expected.rs");
@@ -1,3 +1,4 @@
+/// XSD: `tns:FooType`
 #[derive(PartialEq, Debug, UtilsUnionSerDe)]
 pub enum FooType {
     Int(i32),
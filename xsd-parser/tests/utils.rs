@@ -1,4 +1,9 @@
-use xsd_parser::{generator::builder::GeneratorBuilder, parser::parse};
+use std::path::{Path, PathBuf};
+
+use xsd_parser::{
+    generator::builder::GeneratorBuilder,
+    parser::{parse, schema_set::SchemaSet},
+};
 
 pub fn generate(input: &str) -> String {
     let f = parse(input).unwrap();
@@ -6,6 +11,20 @@ pub fn generate(input: &str) -> String {
     gen.generate_rs_file(&f)
 }
 
+/// Path of a file in the test case directory, e.g. `test_file("any", "input.xsd")`.
+pub fn test_file(case: &str, name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join(case).join(name)
+}
+
+/// Loads a schema together with its imports and includes
+/// and generates code for the root document.
+pub fn generate_file(path: &Path) -> String {
+    let set = SchemaSet::load(path).unwrap();
+    let files = set.parse().unwrap();
+    let gen = GeneratorBuilder::default().build();
+    gen.generate_rs_file(&files[0])
+}
+
 /// Checks if AST of two code fragments are equivalent.
 /// Here we compare only AST, so anything not related
 /// to AST is ignored, like:
@@ -1,6 +1,8 @@
+/// XSD: `tns:FooType`
 #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
 #[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
 pub struct FooType {
+    /// XSD: element `Text` of type `xsd:string`
     #[yaserde(prefix = "tns", rename = "Text")]
     pub text: String,
 }
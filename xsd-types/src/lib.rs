@@ -1,3 +1,6 @@
+// yaserde_derive expands to impl blocks inside test functions.
+#![cfg_attr(test, allow(non_local_definitions))]
+
 pub mod types;
 pub mod utils;
 
@@ -0,0 +1,636 @@
+use std::{
+    borrow::Cow,
+    fmt,
+    io::{Read, Write},
+    marker::PhantomData,
+};
+
+use xml::{
+    attribute::OwnedAttribute,
+    name::OwnedName,
+    namespace::{Namespace, NS_NO_PREFIX, NS_XMLNS_PREFIX, NS_XML_PREFIX},
+    reader, writer,
+};
+use yaserde::{de, ser, YaDeserialize, YaSerialize};
+
+use crate::types::{FacetKind, ValidationError};
+
+// An element which is not described by the schema, e.g. the content of xs:any.
+// It is kept as is, so it can be sent back unchanged.
+#[derive(Clone, PartialEq, Debug)]
+pub struct AnyElement {
+    pub name: OwnedName,
+    pub attributes: Vec<OwnedAttribute>,
+    // Namespace declarations written with the element
+    pub namespace: Namespace,
+    pub children: Vec<AnyNode>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum AnyNode {
+    Element(AnyElement),
+    Text(String),
+}
+
+impl AnyElement {
+    pub fn new(name: OwnedName) -> Self {
+        Self { name, attributes: vec![], namespace: Namespace::empty(), children: vec![] }
+    }
+
+    pub fn child_elements(&self) -> impl Iterator<Item = &AnyElement> {
+        self.children.iter().filter_map(|child| match child {
+            AnyNode::Element(element) => Some(element),
+            AnyNode::Text(_) => None,
+        })
+    }
+
+    pub fn text(&self) -> String {
+        self.children
+            .iter()
+            .filter_map(|child| match child {
+                AnyNode::Text(text) => Some(text.as_str()),
+                AnyNode::Element(_) => None,
+            })
+            .collect()
+    }
+
+    // Converts the element to a type generated from its declaration
+    pub fn to_value<T: YaDeserialize>(&self) -> Result<T, String> {
+        yaserde::de::from_str(&yaserde::ser::to_string(self)?)
+    }
+
+    pub fn from_value<T: YaSerialize>(value: &T) -> Result<Self, String> {
+        yaserde::de::from_str(&yaserde::ser::to_string(value)?)
+    }
+
+    // Reads the element at the current position of the reader. The end element
+    // is left to the caller, like derived deserializers do.
+    fn read<R: Read>(
+        reader: &mut de::Deserializer<R>,
+        parent_namespace: &Namespace,
+    ) -> Result<Self, String> {
+        let (name, attributes, namespace) = match reader.next_event()? {
+            reader::XmlEvent::StartElement { name, attributes, namespace } => {
+                (name, attributes, namespace)
+            }
+            event => return Err(format!("Start element not found, got {:?}", event)),
+        };
+
+        let mut element = AnyElement::new(name);
+        element.attributes = attributes;
+        element.namespace = declared_namespace(&namespace, parent_namespace);
+
+        loop {
+            match reader.peek()? {
+                reader::XmlEvent::StartElement { .. } => {
+                    let child = AnyElement::read(reader, &namespace)?;
+                    reader.next_event()?;
+                    element.children.push(AnyNode::Element(child));
+                }
+                reader::XmlEvent::Characters(text) => {
+                    element.children.push(AnyNode::Text(text.clone()));
+                    reader.next_event()?;
+                }
+                reader::XmlEvent::EndElement { .. } => break,
+                reader::XmlEvent::EndDocument => return Err("Unexpected end of document".into()),
+                _ => {
+                    reader.next_event()?;
+                }
+            }
+        }
+
+        Ok(element)
+    }
+
+    fn write<W: Write>(&self, writer: &mut ser::Serializer<W>) -> Result<(), String> {
+        let attributes = self.attributes.iter().map(|a| a.borrow()).collect::<Vec<_>>();
+        writer
+            .write(writer::XmlEvent::StartElement {
+                name: self.name.borrow(),
+                attributes: Cow::Owned(attributes),
+                namespace: Cow::Borrowed(&self.namespace),
+            })
+            .map_err(|e| e.to_string())?;
+
+        for child in &self.children {
+            match child {
+                AnyNode::Element(element) => element.write(writer)?,
+                AnyNode::Text(text) => {
+                    writer.write(writer::XmlEvent::characters(text)).map_err(|e| e.to_string())?
+                }
+            }
+        }
+
+        writer.write(writer::XmlEvent::end_element()).map_err(|e| e.to_string())
+    }
+}
+
+// Namespace bindings which are not inherited from the parent element
+fn declared_namespace(namespace: &Namespace, parent: &Namespace) -> Namespace {
+    let mut declared = Namespace::empty();
+    for (prefix, uri) in namespace {
+        if prefix == NS_XML_PREFIX || prefix == NS_XMLNS_PREFIX {
+            continue;
+        }
+        if prefix == NS_NO_PREFIX && uri.is_empty() && parent.get(prefix).is_none() {
+            continue;
+        }
+        if parent.get(prefix) != Some(uri) {
+            declared.put(prefix, uri);
+        }
+    }
+    declared
+}
+
+impl YaSerialize for AnyElement {
+    fn serialize<W: Write>(&self, writer: &mut ser::Serializer<W>) -> Result<(), String> {
+        self.write(writer)
+    }
+
+    fn serialize_attributes(
+        &self,
+        attributes: Vec<OwnedAttribute>,
+        namespace: Namespace,
+    ) -> Result<(Vec<OwnedAttribute>, Namespace), String> {
+        Ok((attributes, namespace))
+    }
+}
+
+impl YaDeserialize for AnyElement {
+    fn deserialize<R: Read>(reader: &mut de::Deserializer<R>) -> Result<Self, String> {
+        AnyElement::read(reader, &Namespace::empty())
+    }
+}
+
+// Child elements of a struct which were not matched by any of its fields,
+// e.g. the content of xs:any. Generated structs read them as a flattened field.
+#[derive(Default, Clone, PartialEq, Debug)]
+pub struct AnyElements(pub Vec<AnyElement>);
+
+impl AnyElements {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &AnyElement> {
+        self.0.iter()
+    }
+
+    // Checks the namespace constraint of xs:any, e.g. "##other" or a list of URIs
+    pub fn validate_namespace(
+        &self,
+        constraint: &str,
+        target_namespace: Option<&str>,
+    ) -> Result<(), ValidationError> {
+        for element in self.iter() {
+            let namespace = element.name.namespace.as_deref().filter(|ns| !ns.is_empty());
+            if !namespace_allowed(constraint, namespace, target_namespace) {
+                return Err(ValidationError::new(
+                    FacetKind::Namespace,
+                    &element.name.local_name,
+                    format!("is allowed by {:?}", constraint),
+                    format!("{:?}", namespace.unwrap_or("")),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    // Checks processContents="strict" of xs:any: each element must have a global
+    // declaration, given by its namespace and local name
+    pub fn validate_declared(
+        &self,
+        declared: &[(Option<&str>, &str)],
+    ) -> Result<(), ValidationError> {
+        for element in self.iter() {
+            let namespace = element.name.namespace.as_deref().filter(|ns| !ns.is_empty());
+            if !declared.contains(&(namespace, element.name.local_name.as_str())) {
+                return Err(ValidationError::new(
+                    FacetKind::ProcessContents,
+                    &element.name.local_name,
+                    "is declared",
+                    "none",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Namespace constraint of xs:any and xs:anyAttribute
+pub fn namespace_allowed(
+    constraint: &str,
+    namespace: Option<&str>,
+    target_namespace: Option<&str>,
+) -> bool {
+    match constraint.trim() {
+        "##any" => true,
+        "##other" => namespace.is_some() && namespace != target_namespace,
+        list => list.split_whitespace().any(|item| match item {
+            "##local" => namespace.is_none(),
+            "##targetNamespace" => namespace == target_namespace,
+            uri => namespace == Some(uri),
+        }),
+    }
+}
+
+impl From<Vec<AnyElement>> for AnyElements {
+    fn from(elements: Vec<AnyElement>) -> Self {
+        Self(elements)
+    }
+}
+
+impl YaSerialize for AnyElements {
+    fn serialize<W: Write>(&self, writer: &mut ser::Serializer<W>) -> Result<(), String> {
+        self.iter().try_for_each(|element| element.write(writer))
+    }
+
+    fn serialize_attributes(
+        &self,
+        attributes: Vec<OwnedAttribute>,
+        namespace: Namespace,
+    ) -> Result<(Vec<OwnedAttribute>, Namespace), String> {
+        Ok((attributes, namespace))
+    }
+}
+
+// Reads the children of the element at the current position of the reader.
+// Flattened fields get the parent element with the children which weren't
+// matched by other fields.
+impl YaDeserialize for AnyElements {
+    fn deserialize<R: Read>(reader: &mut de::Deserializer<R>) -> Result<Self, String> {
+        let elements = read_children(reader)?
+            .into_iter()
+            .filter_map(|node| match node {
+                AnyNode::Element(element) => Some(element),
+                AnyNode::Text(_) => None,
+            })
+            .collect();
+        Ok(Self(elements))
+    }
+}
+
+// Reads the element at the current position of the reader and returns its content
+pub(crate) fn read_children<R: Read>(
+    reader: &mut de::Deserializer<R>,
+) -> Result<Vec<AnyNode>, String> {
+    let namespace = match reader.next_event()? {
+        reader::XmlEvent::StartElement { namespace, .. } => namespace,
+        event => return Err(format!("Start element not found, got {:?}", event)),
+    };
+
+    let mut nodes = vec![];
+    loop {
+        match reader.peek()? {
+            reader::XmlEvent::StartElement { .. } => {
+                let mut element = AnyElement::read(reader, &namespace)?;
+                reader.next_event()?;
+                // Only keep the bindings which the element uses
+                element.namespace = used_namespace(&element, &namespace);
+                nodes.push(AnyNode::Element(element));
+            }
+            reader::XmlEvent::Characters(text) => {
+                nodes.push(AnyNode::Text(text.clone()));
+                reader.next_event()?;
+            }
+            reader::XmlEvent::EndElement { .. } | reader::XmlEvent::EndDocument => break,
+            _ => {
+                reader.next_event()?;
+            }
+        }
+    }
+    Ok(nodes)
+}
+
+// Inherited bindings used by the names of an element, its attributes and its children
+fn used_namespace(element: &AnyElement, in_scope: &Namespace) -> Namespace {
+    fn collect(
+        element: &AnyElement,
+        declared: &Namespace,
+        in_scope: &Namespace,
+        used: &mut Namespace,
+    ) {
+        let mut declared = declared.clone();
+        declared.extend(&element.namespace);
+
+        let prefixes = std::iter::once(&element.name)
+            .chain(element.attributes.iter().map(|a| &a.name))
+            .map(|name| name.prefix.as_deref().unwrap_or(NS_NO_PREFIX));
+        for prefix in prefixes {
+            if declared.get(prefix).is_some() {
+                continue;
+            }
+            if let Some(uri) = in_scope.get(prefix).filter(|uri| !uri.is_empty()) {
+                used.put(prefix, uri);
+            }
+        }
+        element.child_elements().for_each(|child| collect(child, &declared, in_scope, used));
+    }
+
+    let mut used = element.namespace.clone();
+    collect(element, &Namespace::empty(), in_scope, &mut used);
+    used
+}
+
+// Namespaces and local names of the attributes declared by a struct with xs:anyAttribute,
+// the namespace is None for unqualified ones. These attributes are read by their own
+// fields, so `AnyAttributes` skips them.
+pub trait DeclaredAttributes {
+    const NAMES: &'static [(Option<&'static str>, &'static str)];
+}
+
+// Attributes of an element which are not declared by its type, e.g. vendor
+// attributes matched by xs:anyAttribute, in document order.
+// Generated structs read them as a flattened field.
+pub struct AnyAttributes<T> {
+    pub attributes: Vec<OwnedAttribute>,
+    // Declarations of the prefixes used by the attributes
+    pub namespace: Namespace,
+    owner: PhantomData<fn() -> T>,
+}
+
+impl<T> AnyAttributes<T> {
+    pub fn new(attributes: Vec<OwnedAttribute>) -> Self {
+        Self { attributes, namespace: Namespace::empty(), owner: PhantomData }
+    }
+
+    pub fn len(&self) -> usize {
+        self.attributes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &OwnedAttribute> {
+        self.attributes.iter()
+    }
+
+    // Value of an attribute by its local name and namespace
+    pub fn get(&self, namespace: Option<&str>, local_name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|a| a.name.local_name == local_name && a.name.namespace.as_deref() == namespace)
+            .map(|a| a.value.as_str())
+    }
+
+    // Sets the value of an attribute, replacing the one with the same qualified name
+    pub fn insert(&mut self, name: OwnedName, value: impl Into<String>) {
+        let value = value.into();
+        if let (Some(prefix), Some(uri)) = (&name.prefix, &name.namespace) {
+            self.namespace.put(prefix.as_str(), uri.as_str());
+        }
+        match self
+            .attributes
+            .iter_mut()
+            .find(|a| a.name.local_name == name.local_name && a.name.namespace == name.namespace)
+        {
+            Some(attribute) => attribute.value = value,
+            None => self.attributes.push(OwnedAttribute::new(name, value)),
+        }
+    }
+
+    pub fn validate_namespace(
+        &self,
+        constraint: &str,
+        target_namespace: Option<&str>,
+    ) -> Result<(), ValidationError> {
+        for attribute in self.iter() {
+            let namespace = attribute.name.namespace.as_deref().filter(|ns| !ns.is_empty());
+            if !namespace_allowed(constraint, namespace, target_namespace) {
+                return Err(ValidationError::new(
+                    FacetKind::Namespace,
+                    &attribute.name.local_name,
+                    format!("is allowed by {:?}", constraint),
+                    format!("{:?}", namespace.unwrap_or("")),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T> Default for AnyAttributes<T> {
+    fn default() -> Self {
+        Self::new(vec![])
+    }
+}
+
+impl<T> Clone for AnyAttributes<T> {
+    fn clone(&self) -> Self {
+        Self {
+            attributes: self.attributes.clone(),
+            namespace: self.namespace.clone(),
+            owner: PhantomData,
+        }
+    }
+}
+
+impl<T> PartialEq for AnyAttributes<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.attributes == other.attributes
+    }
+}
+
+impl<T> fmt::Debug for AnyAttributes<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnyAttributes").field("attributes", &self.attributes).finish()
+    }
+}
+
+impl<T> YaSerialize for AnyAttributes<T> {
+    // The attributes are written by the parent element, see `serialize_attributes`
+    fn serialize<W: Write>(&self, _writer: &mut ser::Serializer<W>) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn serialize_attributes(
+        &self,
+        mut attributes: Vec<OwnedAttribute>,
+        mut namespace: Namespace,
+    ) -> Result<(Vec<OwnedAttribute>, Namespace), String> {
+        attributes.extend(self.attributes.iter().cloned());
+        namespace.extend(&self.namespace);
+        Ok((attributes, namespace))
+    }
+}
+
+// Flattened fields get the parent element, so its undeclared attributes are taken
+impl<T: DeclaredAttributes> YaDeserialize for AnyAttributes<T> {
+    fn deserialize<R: Read>(reader: &mut de::Deserializer<R>) -> Result<Self, String> {
+        let (attributes, in_scope) = match reader.next_event()? {
+            reader::XmlEvent::StartElement { attributes, namespace, .. } => (attributes, namespace),
+            event => return Err(format!("Start element not found, got {:?}", event)),
+        };
+
+        let mut result = Self::default();
+        for attribute in attributes {
+            let namespace = attribute.name.namespace.as_deref().filter(|ns| !ns.is_empty());
+            if T::NAMES.contains(&(namespace, attribute.name.local_name.as_str())) {
+                continue;
+            }
+            if let Some(prefix) = attribute.name.prefix.as_deref() {
+                if let Some(uri) = in_scope.get(prefix) {
+                    result.namespace.put(prefix, uri);
+                }
+            }
+            result.attributes.push(attribute);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::xml_eq::assert_xml_eq;
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+    #[yaserde(prefix = "t", namespace = "t: http://example.com")]
+    pub struct Message {
+        #[yaserde(prefix = "t", rename = "Name")]
+        pub name: String,
+
+        #[yaserde(flatten)]
+        pub any: AnyElements,
+    }
+
+    #[test]
+    fn any_elements_round_trip() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+        <t:Message xmlns:t="http://example.com" xmlns:ext="http://ext.com">
+            <t:Name>foo</t:Name>
+            <ext:Extension ext:id="1"><ext:Item>text</ext:Item><Plain/></ext:Extension>
+        </t:Message>"#;
+
+        let message: Message = yaserde::de::from_str(xml).unwrap();
+        assert_eq!(message.name, "foo");
+        assert_eq!(message.any.len(), 1);
+
+        let extension = &message.any.0[0];
+        assert_eq!(extension.name.local_name, "Extension");
+        assert_eq!(extension.name.namespace.as_deref(), Some("http://ext.com"));
+        assert_eq!(extension.attributes[0].value, "1");
+        let children = extension.child_elements().collect::<Vec<_>>();
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0].text(), "text");
+
+        // Namespaces are declared on the elements which use them
+        let expected = r#"<?xml version="1.0" encoding="utf-8"?>
+        <t:Message xmlns:t="http://example.com">
+            <t:Name>foo</t:Name>
+            <ext:Extension xmlns:ext="http://ext.com" ext:id="1"><ext:Item>text</ext:Item><Plain/></ext:Extension>
+        </t:Message>"#;
+        let ser = yaserde::ser::to_string(&message).unwrap();
+        assert_xml_eq(&ser, expected);
+    }
+
+    #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+    #[yaserde(prefix = "t", namespace = "t: http://example.com")]
+    pub struct Envelope {
+        #[yaserde(prefix = "t", rename = "Message")]
+        pub message: Message,
+
+        #[yaserde(prefix = "t", rename = "Count")]
+        pub count: i32,
+    }
+
+    #[test]
+    fn any_elements_of_child_struct() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+        <t:Envelope xmlns:t="http://example.com">
+            <t:Message>
+                <t:Name>foo</t:Name>
+                <Extra a="b"><Inner/></Extra>
+            </t:Message>
+            <t:Count>2</t:Count>
+        </t:Envelope>"#;
+
+        let envelope: Envelope = yaserde::de::from_str(xml).unwrap();
+        assert_eq!(envelope.count, 2);
+        assert_eq!(envelope.message.name, "foo");
+        assert_eq!(envelope.message.any.len(), 1);
+        assert_eq!(envelope.message.any.0[0].name.local_name, "Extra");
+
+        let ser = yaserde::ser::to_string(&envelope).unwrap();
+        assert_xml_eq(&ser, xml);
+    }
+
+    #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+    #[yaserde(prefix = "t", namespace = "t: http://example.com")]
+    pub struct Device {
+        #[yaserde(attribute, rename = "token")]
+        pub token: String,
+
+        #[yaserde(flatten)]
+        pub any_attribute: AnyAttributes<Device>,
+    }
+
+    impl DeclaredAttributes for Device {
+        const NAMES: &'static [(Option<&'static str>, &'static str)] = &[(None, "token")];
+    }
+
+    #[test]
+    fn any_attributes_round_trip() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+        <t:Device xmlns:t="http://example.com" xmlns:v="http://vendor.com" token="a" v:model="X1" extra="1" />"#;
+
+        let device: Device = yaserde::de::from_str(xml).unwrap();
+        assert_eq!(device.token, "a");
+        assert_eq!(device.any_attribute.len(), 2);
+        assert_eq!(device.any_attribute.get(Some("http://vendor.com"), "model"), Some("X1"));
+        assert_eq!(device.any_attribute.get(None, "extra"), Some("1"));
+
+        let ser = yaserde::ser::to_string(&device).unwrap();
+        assert_xml_eq(&ser, xml);
+
+        assert!(device
+            .any_attribute
+            .validate_namespace("##other", Some("http://example.com"))
+            .is_err());
+        assert!(device.any_attribute.validate_namespace("##local http://vendor.com", None).is_ok());
+    }
+
+    #[test]
+    fn any_attributes_with_declared_local_name() {
+        let xml = r#"<t:Device xmlns:t="http://example.com" xmlns:v="http://vendor.com" token="a" v:token="b" />"#;
+
+        let device: Device = yaserde::de::from_str(xml).unwrap();
+        assert_eq!(device.any_attribute.len(), 1);
+        assert_eq!(device.any_attribute.get(Some("http://vendor.com"), "token"), Some("b"));
+        assert_eq!(device.any_attribute.get(None, "token"), None);
+    }
+
+    #[test]
+    fn any_elements_validate_namespace() {
+        let xml = r#"<t:Message xmlns:t="http://example.com" xmlns:ext="http://ext.com">
+            <t:Name>foo</t:Name>
+            <ext:Extension/>
+        </t:Message>"#;
+        let message: Message = yaserde::de::from_str(xml).unwrap();
+        let tns = Some("http://example.com");
+
+        assert!(message.any.validate_namespace("##any", tns).is_ok());
+        assert!(message.any.validate_namespace("##other", tns).is_ok());
+        assert!(message.any.validate_namespace("##local ##targetNamespace", tns).is_err());
+        assert!(message.any.validate_namespace("##local http://ext.com", tns).is_ok());
+    }
+
+    #[test]
+    fn any_elements_validate_declared() {
+        let xml = r#"<t:Message xmlns:t="http://example.com" xmlns:ext="http://ext.com">
+            <t:Name>foo</t:Name>
+            <ext:Extension/>
+        </t:Message>"#;
+        let message: Message = yaserde::de::from_str(xml).unwrap();
+
+        assert!(message.any.validate_declared(&[(Some("http://ext.com"), "Extension")]).is_ok());
+        assert!(message.any.validate_declared(&[(None, "Extension")]).is_err());
+        assert!(message.any.validate_declared(&[]).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+use std::{
+    io::{Read, Write},
+    ops::{Deref, DerefMut},
+};
+
+use xml::{attribute::OwnedAttribute, namespace::Namespace};
+use yaserde::{de, ser, YaDeserialize, YaSerialize};
+
+// A value stored on the heap, generated for fields of recursive types.
+// yaserde has no implementations for Box, so this wrapper serializes the value as is.
+#[derive(Default, Clone, PartialEq, Debug)]
+pub struct Boxed<T>(pub Box<T>);
+
+impl<T> Boxed<T> {
+    pub fn new(value: T) -> Self {
+        Self(Box::new(value))
+    }
+
+    pub fn into_inner(self) -> T {
+        *self.0
+    }
+}
+
+impl<T> Deref for Boxed<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Boxed<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Boxed<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: YaSerialize> YaSerialize for Boxed<T> {
+    fn serialize<W: Write>(&self, writer: &mut ser::Serializer<W>) -> Result<(), String> {
+        self.0.serialize(writer)
+    }
+
+    fn serialize_attributes(
+        &self,
+        attributes: Vec<OwnedAttribute>,
+        namespace: Namespace,
+    ) -> Result<(Vec<OwnedAttribute>, Namespace), String> {
+        self.0.serialize_attributes(attributes, namespace)
+    }
+}
+
+impl<T: YaDeserialize> YaDeserialize for Boxed<T> {
+    fn deserialize<R: Read>(reader: &mut de::Deserializer<R>) -> Result<Self, String> {
+        T::deserialize(reader).map(Self::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    use super::*;
+    use crate::utils::xml_eq::assert_xml_eq;
+
+    // Mutually recursive, as derived deserializers take an element named like one of
+    // the fields of its own type for that field
+    #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+    #[yaserde(prefix = "t", namespace = "t: test")]
+    pub struct Folder {
+        #[yaserde(prefix = "t", rename = "Name")]
+        pub name: String,
+
+        #[yaserde(prefix = "t", rename = "Entry")]
+        pub entry: Option<Boxed<Entry>>,
+    }
+
+    #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+    #[yaserde(prefix = "t", namespace = "t: test")]
+    pub struct Entry {
+        #[yaserde(prefix = "t", rename = "Folder")]
+        pub folder: Option<Folder>,
+    }
+
+    #[test]
+    fn recursive_round_trip() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+            <t:Folder xmlns:t="test">
+                <t:Name>root</t:Name>
+                <t:Entry>
+                    <t:Folder>
+                        <t:Name>child</t:Name>
+                        <t:Entry>
+                            <t:Folder><t:Name>leaf</t:Name></t:Folder>
+                        </t:Entry>
+                    </t:Folder>
+                </t:Entry>
+            </t:Folder>"#;
+
+        let root: Folder = yaserde::de::from_str(xml).unwrap();
+        let child = root.entry.as_ref().unwrap().folder.as_ref().unwrap();
+        assert_eq!(child.name, "child");
+        let leaf = child.entry.as_ref().unwrap().folder.as_ref().unwrap();
+        assert_eq!(leaf.name, "leaf");
+        assert!(leaf.entry.is_none());
+
+        let ser = yaserde::ser::to_string(&root).unwrap();
+        assert_xml_eq(&ser, xml);
+    }
+
+    #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+    #[yaserde(prefix = "t", namespace = "t: test")]
+    pub struct Pair {
+        #[yaserde(prefix = "t", rename = "First")]
+        pub first: Option<Boxed<Folder>>,
+
+        #[yaserde(prefix = "t", rename = "Second")]
+        pub second: Option<Boxed<Folder>>,
+    }
+
+    #[test]
+    fn boxed_values_leave_their_siblings() {
+        let xml = r#"<t:Pair xmlns:t="test">
+                <t:First>
+                    <t:Name>first</t:Name>
+                    <t:Entry><t:Folder><t:Name>nested</t:Name></t:Folder></t:Entry>
+                </t:First>
+                <t:Second><t:Name>second</t:Name></t:Second>
+            </t:Pair>"#;
+
+        let pair: Pair = yaserde::de::from_str(xml).unwrap();
+        let first = pair.first.unwrap();
+        assert_eq!(first.name, "first");
+        assert_eq!(first.entry.as_ref().unwrap().folder.as_ref().unwrap().name, "nested");
+        assert_eq!(pair.second.unwrap().name, "second");
+    }
+
+    #[test]
+    fn deep_recursion() {
+        let xml = format!(
+            r#"<t:Folder xmlns:t="test">{}{}</t:Folder>"#,
+            "<t:Name>folder</t:Name><t:Entry><t:Folder>".repeat(50),
+            "</t:Folder></t:Entry>".repeat(50)
+        );
+
+        let mut folder = yaserde::de::from_str::<Folder>(&xml).unwrap();
+        let mut depth = 0;
+        while let Some(entry) = folder.entry {
+            assert_eq!(folder.name, "folder");
+            folder = entry.into_inner().folder.unwrap();
+            depth += 1;
+        }
+        assert_eq!(depth, 50);
+    }
+}
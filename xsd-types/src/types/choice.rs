@@ -0,0 +1,599 @@
+use std::{
+    fmt::Display,
+    io::{Read, Write},
+    ops::{Deref, DerefMut},
+    str::FromStr,
+};
+
+use xml::{attribute::OwnedAttribute, name::OwnedName, namespace::Namespace};
+use yaserde::{de, ser, YaDeserialize, YaSerialize};
+
+use crate::types::{any::read_children, namespace_allowed, AnyElement, AnyElements, AnyNode};
+
+// Content of xs:choice, implemented for generated choice enums by UtilsChoiceSerDe.
+// An alternative can be an element, a sequence of elements, a nested choice or an
+// element matched by xs:any, so it is read from the child elements of the parent
+// in document order.
+pub trait Choice: Sized {
+    // Elements of the parent which are read by its other fields. Any other element
+    // which starts no alternative is an error. None if they are not known, e.g. the
+    // parent has xs:any, then such elements are left to the other fields.
+    const SIBLINGS: Option<&'static [ElementName]> = None;
+
+    // Reads the alternative starting at the first element, returns it with the
+    // number of elements it consists of. None if no alternative starts there.
+    fn read(elements: &[AnyElement]) -> Result<Option<(Self, usize)>, String>;
+
+    fn write(&self, elements: &mut Vec<AnyElement>) -> Result<(), String>;
+
+    // Text of mixed content, for the choices of Mixed which have a text variant
+    fn from_text(_text: &str) -> Option<Self> {
+        None
+    }
+
+    fn text(&self) -> Option<&str> {
+        None
+    }
+
+    // Local name of the elements of an element alternative and how many of them it
+    // holds, None for the other alternatives. Validation errors of repeated choices
+    // are numbered by it, e.g. /Order/Pickup[2].
+    fn elements(&self) -> Option<(&'static str, usize)> {
+        None
+    }
+}
+
+// Name of an element of a choice. Elements are matched by their namespace and local
+// name, the prefix is the one written, declared by the parent element.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ElementName {
+    pub prefix: Option<&'static str>,
+    pub namespace: Option<&'static str>,
+    pub local_name: &'static str,
+}
+
+impl ElementName {
+    pub fn matches(&self, element: &AnyElement) -> bool {
+        let namespace = element.name.namespace.as_deref().filter(|ns| !ns.is_empty());
+        element.name.local_name == self.local_name && namespace == self.namespace
+    }
+
+    fn to_owned_name(self) -> OwnedName {
+        OwnedName {
+            local_name: self.local_name.to_string(),
+            namespace: None,
+            prefix: self.prefix.map(str::to_string),
+        }
+    }
+}
+
+// Step of a sequence alternative: one of the names, repeated from min to max times
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SequenceStep {
+    pub names: &'static [ElementName],
+    pub min: usize,
+    pub max: Option<usize>,
+}
+
+// Element of the sequence of a mixed type, by its local name, repeated from min to max
+// times, see Mixed::validate_sequence
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct MixedStep {
+    pub name: &'static str,
+    pub min: usize,
+    pub max: Option<usize>,
+}
+
+// Occurrences of a choice with maxOccurs > 1, in document order.
+// Generated structs read them as a flattened field.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Choices<T>(pub Vec<T>);
+
+impl<T> Default for Choices<T> {
+    fn default() -> Self {
+        Self(vec![])
+    }
+}
+
+impl<T> Deref for Choices<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Choices<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<Vec<T>> for Choices<T> {
+    fn from(choices: Vec<T>) -> Self {
+        Self(choices)
+    }
+}
+
+impl<T: Choice> Choice for Choices<T> {
+    fn read(elements: &[AnyElement]) -> Result<Option<(Self, usize)>, String> {
+        Ok(read_repeated(elements, T::read)?.map(|(choices, count)| (Self(choices), count)))
+    }
+
+    fn write(&self, elements: &mut Vec<AnyElement>) -> Result<(), String> {
+        self.iter().try_for_each(|choice| choice.write(elements))
+    }
+}
+
+impl<T: Choice> YaSerialize for Choices<T> {
+    fn serialize<W: Write>(&self, writer: &mut ser::Serializer<W>) -> Result<(), String> {
+        serialize_choice(self, writer)
+    }
+
+    fn serialize_attributes(
+        &self,
+        attributes: Vec<OwnedAttribute>,
+        namespace: Namespace,
+    ) -> Result<(Vec<OwnedAttribute>, Namespace), String> {
+        Ok((attributes, namespace))
+    }
+}
+
+impl<T: Choice> YaDeserialize for Choices<T> {
+    fn deserialize<R: Read>(reader: &mut de::Deserializer<R>) -> Result<Self, String> {
+        read_choices(&AnyElements::deserialize(reader)?.0).map(Self)
+    }
+}
+
+// Content of an element of a complex type with mixed="true": the text and the
+// alternatives of its child elements, in document order.
+// Generated structs read it as a flattened field.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Mixed<T>(pub Vec<T>);
+
+impl<T> Default for Mixed<T> {
+    fn default() -> Self {
+        Self(vec![])
+    }
+}
+
+impl<T> Deref for Mixed<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Mixed<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<Vec<T>> for Mixed<T> {
+    fn from(items: Vec<T>) -> Self {
+        Self(items)
+    }
+}
+
+impl<T: Choice> Mixed<T> {
+    // Concatenated text of the content, without the text of child elements
+    pub fn text(&self) -> String {
+        self.iter().filter_map(|item| item.text()).collect()
+    }
+}
+
+impl<T: Choice> YaSerialize for Mixed<T> {
+    fn serialize<W: Write>(&self, writer: &mut ser::Serializer<W>) -> Result<(), String> {
+        for item in self.iter() {
+            match item.text() {
+                Some(text) => writer
+                    .write(xml::writer::XmlEvent::characters(text))
+                    .map_err(|e| e.to_string())?,
+                None => serialize_choice(item, writer)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn serialize_attributes(
+        &self,
+        attributes: Vec<OwnedAttribute>,
+        namespace: Namespace,
+    ) -> Result<(Vec<OwnedAttribute>, Namespace), String> {
+        Ok((attributes, namespace))
+    }
+}
+
+impl<T: Choice> YaDeserialize for Mixed<T> {
+    fn deserialize<R: Read>(reader: &mut de::Deserializer<R>) -> Result<Self, String> {
+        let mut items = vec![];
+        let mut elements = vec![];
+        for node in read_children(reader)? {
+            match node {
+                AnyNode::Element(element) => elements.push(element),
+                AnyNode::Text(text) => {
+                    // The elements before the text are read together, an alternative
+                    // can consist of several of them
+                    items.extend(read_choices::<T>(&std::mem::take(&mut elements))?);
+                    items.extend(T::from_text(&text));
+                }
+            }
+        }
+        items.extend(read_choices::<T>(&elements)?);
+        Ok(Self(items))
+    }
+}
+
+// Reads the alternatives found among the children of the parent. Elements which start
+// no alternative must belong to the other fields of the parent, see Choice::SIBLINGS.
+fn read_choices<T: Choice>(elements: &[AnyElement]) -> Result<Vec<T>, String> {
+    let mut choices = vec![];
+    let mut position = 0;
+    while position < elements.len() {
+        match T::read(&elements[position..])? {
+            Some((choice, count)) => {
+                choices.push(choice);
+                position += count.max(1);
+            }
+            None => {
+                let element = &elements[position];
+                if T::SIBLINGS.is_some_and(|siblings| !siblings.iter().any(|s| s.matches(element)))
+                {
+                    return Err(format!(
+                        "Element {} matches no alternative of the choice",
+                        element.name
+                    ));
+                }
+                position += 1;
+            }
+        }
+    }
+    Ok(choices)
+}
+
+// Writes the elements of a choice, which is a flattened field of a struct
+pub fn serialize_choice<T: Choice, W: Write>(
+    choice: &T,
+    writer: &mut ser::Serializer<W>,
+) -> Result<(), String> {
+    let mut elements = vec![];
+    choice.write(&mut elements)?;
+    elements.iter().try_for_each(|element| element.serialize(writer))
+}
+
+// Reads the first alternative found among the children of the element at the
+// current position of the reader
+pub fn deserialize_choice<T: Choice + Default, R: Read>(
+    reader: &mut de::Deserializer<R>,
+) -> Result<T, String> {
+    let choices = read_choices(&AnyElements::deserialize(reader)?.0)?;
+    Ok(choices.into_iter().next().unwrap_or_default())
+}
+
+fn starts_with<'a>(elements: &'a [AnyElement], name: &ElementName) -> Option<&'a AnyElement> {
+    elements.first().filter(|element| name.matches(element))
+}
+
+pub fn read_element<T: YaDeserialize>(
+    elements: &[AnyElement],
+    name: &ElementName,
+) -> Result<Option<(T, usize)>, String> {
+    starts_with(elements, name).map(|element| Ok((element.to_value()?, 1))).transpose()
+}
+
+// Elements of built-in types which yaserde reads only as struct fields
+pub fn read_text_element<T: FromStr>(
+    elements: &[AnyElement],
+    name: &ElementName,
+) -> Result<Option<(T, usize)>, String>
+where
+    T::Err: Display,
+{
+    starts_with(elements, name)
+        .map(|element| Ok((element.text().parse().map_err(|e: T::Err| e.to_string())?, 1)))
+        .transpose()
+}
+
+pub fn read_empty_element(elements: &[AnyElement], name: &ElementName) -> Option<usize> {
+    starts_with(elements, name).map(|_| 1)
+}
+
+// Element matched by xs:any, kept as raw XML. The namespace constraint is the one of
+// the wildcard, e.g. "##other".
+pub fn read_any_element(
+    elements: &[AnyElement],
+    constraint: &str,
+    target_namespace: Option<&str>,
+) -> Option<(AnyElement, usize)> {
+    elements
+        .first()
+        .filter(|element| {
+            let namespace = element.name.namespace.as_deref().filter(|ns| !ns.is_empty());
+            namespace_allowed(constraint, namespace, target_namespace)
+        })
+        .map(|element| (element.clone(), 1))
+}
+
+// Reads the following occurrences of an element, sequence or choice
+pub fn read_repeated<T>(
+    elements: &[AnyElement],
+    read: impl Fn(&[AnyElement]) -> Result<Option<(T, usize)>, String>,
+) -> Result<Option<(Vec<T>, usize)>, String> {
+    let mut values = vec![];
+    let mut position = 0;
+    while position < elements.len() {
+        match read(&elements[position..])? {
+            Some((value, count)) if count > 0 => {
+                values.push(value);
+                position += count;
+            }
+            _ => break,
+        }
+    }
+    Ok(Some((values, position)).filter(|_| position > 0))
+}
+
+// Reads a sequence described by its steps, e.g. the fields of the struct
+pub fn read_sequence<T: YaDeserialize>(
+    elements: &[AnyElement],
+    steps: &[SequenceStep],
+) -> Result<Option<(T, usize)>, String> {
+    let count = sequence_length(elements, steps);
+    if count == 0 {
+        return Ok(None);
+    }
+
+    let mut sequence = AnyElement::new(OwnedName::local("sequence"));
+    sequence.children = elements[..count].iter().cloned().map(AnyNode::Element).collect();
+    Ok(Some((sequence.to_value()?, count)))
+}
+
+// Number of elements matched by the steps, 0 if a step occurs less than its min times
+fn sequence_length(elements: &[AnyElement], steps: &[SequenceStep]) -> usize {
+    let mut position = 0;
+    for step in steps {
+        let matches = |element: &AnyElement| step.names.iter().any(|name| name.matches(element));
+        let mut count = 0;
+        while step.max.is_none_or(|max| count < max) && elements.get(position).is_some_and(matches)
+        {
+            position += 1;
+            count += 1;
+        }
+        if count < step.min {
+            return 0;
+        }
+    }
+    position
+}
+
+pub fn write_element<T: YaSerialize>(
+    value: &T,
+    name: &ElementName,
+    elements: &mut Vec<AnyElement>,
+) -> Result<(), String> {
+    let mut element = AnyElement::from_value(value)?;
+    element.name = name.to_owned_name();
+    elements.push(element);
+    Ok(())
+}
+
+pub fn write_text_element<T: Display>(
+    value: &T,
+    name: &ElementName,
+    elements: &mut Vec<AnyElement>,
+) -> Result<(), String> {
+    let mut element = AnyElement::new(name.to_owned_name());
+    element.children.push(AnyNode::Text(value.to_string()));
+    elements.push(element);
+    Ok(())
+}
+
+pub fn write_empty_element(
+    name: &ElementName,
+    elements: &mut Vec<AnyElement>,
+) -> Result<(), String> {
+    elements.push(AnyElement::new(name.to_owned_name()));
+    Ok(())
+}
+
+pub fn write_any_element(value: &AnyElement, elements: &mut Vec<AnyElement>) -> Result<(), String> {
+    elements.push(value.clone());
+    Ok(())
+}
+
+// Writes the fields of a struct generated from a sequence without the struct element
+pub fn write_sequence<T: YaSerialize>(
+    value: &T,
+    elements: &mut Vec<AnyElement>,
+) -> Result<(), String> {
+    let sequence: AnyElements = yaserde::de::from_str(&yaserde::ser::to_string(value)?)?;
+    elements.extend(sequence.0);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use xsd_macro_utils::UtilsChoiceSerDe;
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    use super::*;
+    use crate::utils::xml_eq::assert_xml_eq;
+
+    #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+    #[yaserde(prefix = "t", namespace = "t: http://example.com")]
+    pub struct Range {
+        #[yaserde(prefix = "t", rename = "From")]
+        pub from: i32,
+        #[yaserde(prefix = "t", rename = "To")]
+        pub to: Option<i32>,
+    }
+
+    #[derive(PartialEq, Debug, Clone, UtilsChoiceSerDe)]
+    #[choice(namespace = "t: http://example.com", siblings = "t:Name")]
+    pub enum Item {
+        #[choice(element = "t:Value")]
+        Value(i32),
+        #[choice(element = "t:Empty")]
+        Empty,
+        #[choice(sequence = "t:From t:To?")]
+        Range(Range),
+        #[choice(any = "##other", target_namespace = "http://example.com")]
+        Other(AnyElement),
+        __Unknown__(String),
+    }
+
+    impl Default for Item {
+        fn default() -> Self {
+            Self::__Unknown__("No valid variants".into())
+        }
+    }
+
+    #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+    #[yaserde(prefix = "t", namespace = "t: http://example.com")]
+    pub struct List {
+        #[yaserde(prefix = "t", rename = "Name")]
+        pub name: String,
+        #[yaserde(flatten)]
+        pub items: Choices<Item>,
+    }
+
+    #[test]
+    fn choices_round_trip() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+        <t:List xmlns:t="http://example.com">
+            <t:Name>list</t:Name>
+            <t:From>1</t:From>
+            <t:To>2</t:To>
+            <t:Value>3</t:Value>
+            <t:Empty />
+            <t:From>4</t:From>
+        </t:List>"#;
+
+        let list: List = yaserde::de::from_str(xml).unwrap();
+        assert_eq!(list.name, "list");
+        assert_eq!(
+            list.items.0,
+            vec![
+                Item::Range(Range { from: 1, to: Some(2) }),
+                Item::Value(3),
+                Item::Empty,
+                Item::Range(Range { from: 4, to: None }),
+            ]
+        );
+
+        let ser = yaserde::ser::to_string(&list).unwrap();
+        assert_xml_eq(&ser, xml);
+    }
+
+    #[test]
+    fn choices_reject_unexpected_elements() {
+        let xml = r#"<t:List xmlns:t="http://example.com">
+            <t:Name>list</t:Name>
+            <t:Value>1</t:Value>
+            <t:Unknown />
+        </t:List>"#;
+        let err = yaserde::de::from_str::<List>(xml).unwrap_err();
+        assert!(err.contains("Unknown"), "{}", err);
+
+        // Elements are matched by namespace and local name, the prefix does not matter
+        let xml = r#"<t:List xmlns:t="http://example.com" xmlns:u="http://example.com">
+            <t:Name>list</t:Name>
+            <u:Value>1</u:Value>
+        </t:List>"#;
+        assert_eq!(yaserde::de::from_str::<List>(xml).unwrap().items.0, vec![Item::Value(1)]);
+    }
+
+    #[test]
+    fn choices_read_wildcard_alternative() {
+        let xml = r#"<t:List xmlns:t="http://example.com" xmlns:v="http://vendor.com">
+            <t:Name>list</t:Name>
+            <v:Value>1</v:Value>
+            <t:Value>2</t:Value>
+        </t:List>"#;
+        let list: List = yaserde::de::from_str(xml).unwrap();
+        assert!(matches!(&list.items.0[0], Item::Other(e) if e.name.local_name == "Value"));
+        assert_eq!(list.items.0[1], Item::Value(2));
+    }
+
+    #[derive(PartialEq, Debug, Clone, UtilsChoiceSerDe)]
+    #[choice(namespace = "t: http://example.com", siblings = "")]
+    pub enum Inline {
+        #[choice(text)]
+        Text(String),
+        #[choice(element = "t:B")]
+        Bold(String),
+        __Unknown__(String),
+    }
+
+    impl Default for Inline {
+        fn default() -> Self {
+            Self::__Unknown__("No valid variants".into())
+        }
+    }
+
+    #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+    #[yaserde(prefix = "t", namespace = "t: http://example.com")]
+    pub struct Paragraph {
+        #[yaserde(flatten)]
+        pub content: Mixed<Inline>,
+    }
+
+    #[test]
+    fn mixed_round_trip() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+        <t:Paragraph xmlns:t="http://example.com">a,<t:B>b</t:B>&amp;c</t:Paragraph>"#;
+
+        let paragraph: Paragraph = yaserde::de::from_str(xml).unwrap();
+        assert_eq!(
+            paragraph.content.0,
+            vec![
+                Inline::Text("a,".to_string()),
+                Inline::Bold("b".to_string()),
+                Inline::Text("&c".to_string()),
+            ]
+        );
+        assert_eq!(paragraph.content.text(), "a,&c");
+
+        let ser = yaserde::ser::to_string(&paragraph).unwrap();
+        assert_xml_eq(&ser, xml);
+
+        let xml = r#"<t:Paragraph xmlns:t="http://example.com">a <t:I>b</t:I></t:Paragraph>"#;
+        assert!(yaserde::de::from_str::<Paragraph>(xml).is_err());
+    }
+
+    const fn name(local_name: &'static str) -> ElementName {
+        ElementName { prefix: Some("t"), namespace: Some("http://example.com"), local_name }
+    }
+
+    const fn step(names: &'static [ElementName], min: usize, max: Option<usize>) -> SequenceStep {
+        SequenceStep { names, min, max }
+    }
+
+    #[test]
+    fn sequence_pattern() {
+        let xml = r#"<t:List xmlns:t="http://example.com">
+            <t:A/><t:B/><t:B/><t:D/><t:A/>
+        </t:List>"#;
+        let elements = yaserde::de::from_str::<AnyElements>(xml).unwrap().0;
+
+        const A: &[ElementName] = &[name("A")];
+        const B: &[ElementName] = &[name("B")];
+        const C: &[ElementName] = &[name("C")];
+        const D: &[ElementName] = &[name("D")];
+        const C_D: &[ElementName] = &[name("C"), name("D")];
+        const OTHER: &[ElementName] =
+            &[ElementName { prefix: None, namespace: None, local_name: "A" }];
+        assert_eq!(sequence_length(&elements, &[step(A, 1, Some(1)), step(B, 1, Some(1))]), 2);
+        let steps = [step(A, 1, Some(1)), step(B, 0, None), step(C_D, 1, Some(1))];
+        assert_eq!(sequence_length(&elements, &steps), 4);
+        // Required steps must occur, and at least min times
+        assert_eq!(sequence_length(&elements, &[step(B, 1, Some(1)), step(A, 1, Some(1))]), 0);
+        assert_eq!(sequence_length(&elements, &[step(A, 1, Some(1)), step(B, 3, None)]), 0);
+        assert_eq!(sequence_length(&elements, &[step(A, 1, Some(1)), step(C, 0, Some(1))]), 1);
+        assert_eq!(sequence_length(&elements, &[step(C, 1, Some(1)), step(D, 1, Some(1))]), 0);
+        // Elements of another namespace do not match
+        assert_eq!(sequence_length(&elements, &[step(OTHER, 1, Some(1))]), 0);
+    }
+}
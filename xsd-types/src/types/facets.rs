@@ -0,0 +1,113 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+};
+
+use crate::types::Pattern;
+
+// Checks of the facets constraining the lexical form of values, called by the
+// generated Validate impls.
+
+// Whether the value matches one of the patterns of a type, see Pattern. They are
+// compiled once and kept for the following checks.
+pub fn matches_pattern(value: &str, patterns: &[&str]) -> Result<bool, String> {
+    static COMPILED: OnceLock<Mutex<HashMap<String, Pattern>>> = OnceLock::new();
+    let mut compiled = COMPILED.get_or_init(Default::default).lock().unwrap();
+    for pattern in patterns {
+        if !compiled.contains_key(*pattern) {
+            let regex = Pattern::new(pattern).map_err(|e| e.to_string())?;
+            compiled.insert(pattern.to_string(), regex);
+        }
+        if compiled[*pattern].is_match(value) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+// Number of significant digits of a decimal number, None if the value isn't a number
+pub fn total_digits(value: &str) -> Option<usize> {
+    digits(value).map(|(total, _)| total)
+}
+
+// Number of significant digits after the decimal point, None if the value isn't a number
+pub fn fraction_digits(value: &str) -> Option<usize> {
+    digits(value).map(|(_, fraction)| fraction)
+}
+
+// Digits of the number without leading and trailing zeros, e.g. 0.0120 has 2 digits
+// of which 3 are in the fraction (the value is 12 * 10^-3). The exponent of the
+// scientific notation moves the decimal point.
+fn digits(value: &str) -> Option<(usize, usize)> {
+    let value = value.trim().trim_start_matches(['+', '-']);
+    let (mantissa, exponent) = match value.find(['e', 'E']) {
+        Some(index) => (&value[..index], value[index + 1..].parse::<i64>().ok()?),
+        None => (value, 0),
+    };
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = format!("{}{}", integer, fraction);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let first = match digits.find(|c| c != '0') {
+        Some(first) => first as i64,
+        None => return Some((1, 0)),
+    };
+    let last = digits.rfind(|c| c != '0').unwrap() as i64 + 1;
+    let point = integer.len() as i64 + exponent;
+    Some(((last.max(point) - first) as usize, (last - point).max(0) as usize))
+}
+
+// Value normalized as required by whiteSpace="replace", tabs and line breaks are
+// replaced with spaces
+pub fn replace_white_space(value: &str) -> String {
+    value.replace(['\t', '\n', '\r'], " ")
+}
+
+// Value normalized as required by whiteSpace="collapse", the spaces of the replaced
+// value are collapsed to single ones and the leading and trailing ones are removed
+pub fn collapse_white_space(value: &str) -> String {
+    value.split([' ', '\t', '\n', '\r']).filter(|s| !s.is_empty()).collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patterns() {
+        let address = r"(\d{1,3}\.){3}\d{1,3}";
+        assert_eq!(matches_pattern("192.168.0.1", &[address]), Ok(true));
+        // The whole value must match
+        assert_eq!(matches_pattern("address 192.168.0.1", &[address]), Ok(false));
+        assert_eq!(matches_pattern("ab", &["a|ab"]), Ok(true));
+        // Patterns of a type are alternatives
+        assert_eq!(matches_pattern("localhost", &[address, "[a-z]+"]), Ok(true));
+        assert!(matches_pattern("a", &["(a"]).is_err());
+        // XSD escapes
+        assert_eq!(matches_pattern("xs:id", &[r"\i\c*"]), Ok(true));
+    }
+
+    #[test]
+    fn digits() {
+        assert_eq!(total_digits("123.450"), Some(5));
+        assert_eq!(fraction_digits("123.450"), Some(2));
+        assert_eq!(total_digits("-0.0012"), Some(2));
+        assert_eq!(fraction_digits("-0.0012"), Some(4));
+        assert_eq!(total_digits("1200"), Some(4));
+        assert_eq!(fraction_digits("1200"), Some(0));
+        assert_eq!(total_digits("1.2E3"), Some(4));
+        assert_eq!(fraction_digits("12E-3"), Some(3));
+        assert_eq!(total_digits("0.00"), Some(1));
+        assert_eq!(total_digits("1.2.3"), None);
+        assert_eq!(total_digits("abc"), None);
+    }
+
+    #[test]
+    fn white_space() {
+        assert_eq!(replace_white_space("a\tb\r\n c"), "a b   c");
+        assert_eq!(collapse_white_space(" a\tb\r\n  c "), "a b c");
+        assert_eq!(collapse_white_space("  "), "");
+    }
+}
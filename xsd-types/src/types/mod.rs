@@ -1,3 +1,9 @@
+pub mod any;
+pub use any::*;
+pub mod boxed;
+pub use boxed::*;
+pub mod choice;
+pub use choice::*;
 pub mod date;
 pub use date::*;
 pub mod datetime;
@@ -8,6 +14,8 @@ pub mod decimal;
 pub use decimal::*;
 pub mod duration;
 pub use duration::*;
+pub mod facets;
+pub use facets::*;
 pub mod gday;
 pub use gday::*;
 pub mod gmonth;
@@ -22,13 +30,19 @@ pub mod integer;
 pub use integer::*;
 pub mod negative_integer;
 pub use negative_integer::*;
+pub mod nillable;
+pub use nillable::*;
 pub mod non_negative_integer;
 pub use non_negative_integer::*;
 pub mod non_positive_integer;
 pub use non_positive_integer::*;
+pub mod pattern;
+pub use pattern::*;
 pub mod positive_integer;
 pub use positive_integer::*;
 pub mod time;
 pub use time::*;
 pub mod utils;
 pub use utils::*;
+pub mod validation;
+pub use validation::*;
@@ -0,0 +1,206 @@
+use std::{
+    fmt,
+    io::{Read, Write},
+    str::FromStr,
+};
+
+use yaserde::{de, ser, YaDeserialize, YaSerialize};
+
+use crate::utils::yaserde::{deserialize, deserialize_nil, serialize, serialize_nil};
+
+// Content of an element declared with nillable="true".
+// `Nil` is sent as an empty element with xsi:nil="true", so it can be distinguished
+// from an empty value.
+#[derive(Default, Clone, PartialEq, Debug)]
+pub enum Nillable<T> {
+    #[default]
+    Nil,
+    Value(T),
+}
+
+impl<T> Nillable<T> {
+    pub fn is_nil(&self) -> bool {
+        matches!(self, Self::Nil)
+    }
+
+    pub fn as_option(&self) -> Option<&T> {
+        match self {
+            Self::Nil => None,
+            Self::Value(v) => Some(v),
+        }
+    }
+
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Self::Nil => None,
+            Self::Value(v) => Some(v),
+        }
+    }
+}
+
+impl<T> From<Option<T>> for Nillable<T> {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Nil, Self::Value)
+    }
+}
+
+impl<T: YaSerialize> YaSerialize for Nillable<T> {
+    fn serialize<W: Write>(&self, writer: &mut ser::Serializer<W>) -> Result<(), String> {
+        match self {
+            Self::Nil => serialize_nil("Nillable", writer),
+            Self::Value(v) => v.serialize(writer),
+        }
+    }
+
+    fn serialize_attributes(
+        &self,
+        attributes: Vec<xml::attribute::OwnedAttribute>,
+        namespace: xml::namespace::Namespace,
+    ) -> Result<(Vec<xml::attribute::OwnedAttribute>, xml::namespace::Namespace), String> {
+        match self {
+            Self::Nil => Ok((attributes, namespace)),
+            Self::Value(v) => v.serialize_attributes(attributes, namespace),
+        }
+    }
+}
+
+impl<T: YaDeserialize> YaDeserialize for Nillable<T> {
+    fn deserialize<R: Read>(reader: &mut de::Deserializer<R>) -> Result<Self, String> {
+        if deserialize_nil(reader)? {
+            return Ok(Self::Nil);
+        }
+        T::deserialize(reader).map(Self::Value)
+    }
+}
+
+// Same as `Nillable` for simple types which yaserde does not deserialize
+// on their own (integers, floats, bool and String).
+#[derive(Default, Clone, PartialEq, Debug)]
+pub enum NillableText<T> {
+    #[default]
+    Nil,
+    Value(T),
+}
+
+impl<T> NillableText<T> {
+    pub fn is_nil(&self) -> bool {
+        matches!(self, Self::Nil)
+    }
+
+    pub fn as_option(&self) -> Option<&T> {
+        match self {
+            Self::Nil => None,
+            Self::Value(v) => Some(v),
+        }
+    }
+
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Self::Nil => None,
+            Self::Value(v) => Some(v),
+        }
+    }
+}
+
+impl<T> From<Option<T>> for NillableText<T> {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Nil, Self::Value)
+    }
+}
+
+impl<T: fmt::Display> YaSerialize for NillableText<T> {
+    fn serialize<W: Write>(&self, writer: &mut ser::Serializer<W>) -> Result<(), String> {
+        match self {
+            Self::Nil => serialize_nil("NillableText", writer),
+            Self::Value(v) => serialize(v, "NillableText", writer, |v| v.to_string()),
+        }
+    }
+
+    fn serialize_attributes(
+        &self,
+        attributes: Vec<xml::attribute::OwnedAttribute>,
+        namespace: xml::namespace::Namespace,
+    ) -> Result<(Vec<xml::attribute::OwnedAttribute>, xml::namespace::Namespace), String> {
+        Ok((attributes, namespace))
+    }
+}
+
+impl<T: FromStr> YaDeserialize for NillableText<T>
+where
+    T::Err: fmt::Display,
+{
+    fn deserialize<R: Read>(reader: &mut de::Deserializer<R>) -> Result<Self, String> {
+        if deserialize_nil(reader)? {
+            return Ok(Self::Nil);
+        }
+        deserialize(reader, |s| T::from_str(s).map(Self::Value).map_err(|e| e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    use super::*;
+    use crate::{types::Date, utils::xml_eq::assert_xml_eq};
+
+    #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+    #[yaserde(prefix = "t", namespace = "t: test")]
+    pub struct Item {
+        #[yaserde(prefix = "t", rename = "Name")]
+        pub name: String,
+    }
+
+    #[derive(Default, Clone, PartialEq, Debug, YaSerialize, YaDeserialize)]
+    #[yaserde(prefix = "t", namespace = "t: test")]
+    pub struct Message {
+        #[yaserde(prefix = "t", rename = "Count")]
+        pub count: NillableText<i32>,
+
+        #[yaserde(prefix = "t", rename = "Date")]
+        pub date: Nillable<Date>,
+
+        #[yaserde(prefix = "t", rename = "Item")]
+        pub item: Nillable<Item>,
+    }
+
+    #[test]
+    fn nil_serialize_test() {
+        let expected = r#"<?xml version="1.0" encoding="utf-8"?>
+            <t:Message xmlns:t="test">
+                <t:Count xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:nil="true" />
+                <t:Date xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:nil="true" />
+                <t:Item xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:nil="true" />
+            </t:Message>
+            "#;
+        let actual = yaserde::ser::to_string(&Message::default()).unwrap();
+        assert_xml_eq(&actual, expected);
+    }
+
+    #[test]
+    fn nil_deserialize_test() {
+        let s = r#"<?xml version="1.0" encoding="utf-8"?>
+            <t:Message xmlns:t="test" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+                <t:Count xsi:nil="true"/>
+                <t:Date xsi:nil="true"/>
+                <t:Item xsi:nil="true"/>
+            </t:Message>
+            "#;
+        let m: Message = yaserde::de::from_str(s).unwrap();
+        assert_eq!(m, Message::default());
+    }
+
+    #[test]
+    fn value_round_trip_test() {
+        let m = Message {
+            count: NillableText::Value(0),
+            date: Nillable::Value(Date::from_str("2020-02-02").unwrap()),
+            item: Nillable::Value(Item { name: "".to_string() }),
+        };
+        let s = yaserde::ser::to_string(&m).unwrap();
+        assert!(!s.contains("nil"));
+
+        let de: Message = yaserde::de::from_str(&s).unwrap();
+        assert_eq!(de, m);
+    }
+}
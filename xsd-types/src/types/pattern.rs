@@ -0,0 +1,561 @@
+use std::{fmt, str::FromStr};
+
+use regex::Regex;
+
+// Regular expression of a pattern facet. XSD patterns (XML Schema Part 2, Appendix F)
+// match the whole value and have their own escapes, e.g. \i and \c for XML names,
+// \p{IsBasicLatin} for Unicode blocks and [a-z-[aeiou]] for character class
+// subtraction, so they are translated to the syntax of the regex crate.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    regex: Regex,
+}
+
+impl Pattern {
+    pub fn new(pattern: &str) -> Result<Self, PatternError> {
+        let regex = Regex::new(&translate(pattern)?).map_err(|e| PatternError {
+            pattern: pattern.to_string(),
+            position: 0,
+            reason: e.to_string(),
+        })?;
+        Ok(Self { regex })
+    }
+
+    pub fn is_match(&self, value: &str) -> bool {
+        self.regex.is_match(value)
+    }
+
+    // The translated regular expression
+    pub fn as_regex(&self) -> &Regex {
+        &self.regex
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = PatternError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternError {
+    pub pattern: String,
+    // Position of the invalid character, counted in characters
+    pub position: usize,
+    pub reason: String,
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid pattern {:?} at {}: {}", self.pattern, self.position, self.reason)
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+// Translates an XSD pattern to an anchored regular expression of the regex crate
+pub fn translate(pattern: &str) -> Result<String, PatternError> {
+    let mut translator = Translator { pattern, chars: pattern.chars().collect(), position: 0 };
+    let regex = translator.reg_exp()?;
+    match translator.peek() {
+        None => Ok(format!(r"\A(?:{})\z", regex)),
+        Some(')') => Err(translator.error("unmatched ')'")),
+        Some(_) => Err(translator.error("unexpected character")),
+    }
+}
+
+struct Translator<'a> {
+    pattern: &'a str,
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Translator<'_> {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.position + offset).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.position += 1;
+        c
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), PatternError> {
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            _ => {
+                self.position -= 1;
+                Err(self.error(&format!("expected '{}'", expected)))
+            }
+        }
+    }
+
+    fn error(&self, reason: &str) -> PatternError {
+        PatternError {
+            pattern: self.pattern.to_string(),
+            position: self.position.min(self.chars.len()),
+            reason: reason.to_string(),
+        }
+    }
+
+    // regExp ::= branch ( '|' branch )*
+    fn reg_exp(&mut self) -> Result<String, PatternError> {
+        let mut regex = self.branch()?;
+        while self.peek() == Some('|') {
+            self.next();
+            regex.push('|');
+            regex.push_str(&self.branch()?);
+        }
+        Ok(regex)
+    }
+
+    // branch ::= piece*
+    fn branch(&mut self) -> Result<String, PatternError> {
+        let mut regex = String::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            regex.push_str(&self.piece()?);
+        }
+        Ok(regex)
+    }
+
+    // piece ::= atom quantifier?
+    fn piece(&mut self) -> Result<String, PatternError> {
+        let atom = self.atom()?;
+        let quantifier = match self.peek() {
+            Some(c @ ('?' | '*' | '+')) => {
+                self.next();
+                c.to_string()
+            }
+            Some('{') => self.quantity()?,
+            _ => return Ok(atom),
+        };
+        // Quantifiers can't be repeated or made lazy
+        if matches!(self.peek(), Some('?' | '*' | '+' | '{')) {
+            return Err(self.error("quantifier without an atom"));
+        }
+        Ok(atom + &quantifier)
+    }
+
+    // quantity ::= '{' ( n | n ',' | n ',' m ) '}'
+    fn quantity(&mut self) -> Result<String, PatternError> {
+        self.expect('{')?;
+        let min = self.number().ok_or_else(|| self.error("expected a number"))?;
+        let max = if self.peek() == Some(',') {
+            self.next();
+            self.number()
+        } else {
+            Some(min)
+        };
+        self.expect('}')?;
+        match max {
+            Some(max) if max < min => Err(self.error("the maximum is less than the minimum")),
+            Some(max) if max == min => Ok(format!("{{{}}}", min)),
+            Some(max) => Ok(format!("{{{},{}}}", min, max)),
+            None => Ok(format!("{{{},}}", min)),
+        }
+    }
+
+    fn number(&mut self) -> Option<u32> {
+        let start = self.position;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.next();
+        }
+        self.chars[start..self.position].iter().collect::<String>().parse().ok()
+    }
+
+    // atom ::= Char | charClass | '(' regExp ')'
+    fn atom(&mut self) -> Result<String, PatternError> {
+        match self.peek() {
+            Some('(') => {
+                self.next();
+                let regex = self.reg_exp()?;
+                self.expect(')')?;
+                Ok(format!("(?:{})", regex))
+            }
+            Some('[') => self.char_class_expr(),
+            Some('.') => {
+                self.next();
+                Ok(r"[^\n\r]".into())
+            }
+            Some('\\') => match self.escape()? {
+                Escape::Char(c) => Ok(regex::escape(&c.to_string())),
+                Escape::Class(class) => Ok(class),
+            },
+            Some('?' | '*' | '+' | '{') => Err(self.error("quantifier without an atom")),
+            Some(c @ (']' | '}')) => Err(self.error(&format!("unescaped '{}'", c))),
+            Some(c) => {
+                self.next();
+                Ok(regex::escape(&c.to_string()))
+            }
+            None => Err(self.error("unexpected end")),
+        }
+    }
+
+    // charClassExpr ::= '[' charGroup ']'
+    // charGroup ::= ( posCharGroup | '^' posCharGroup ) ( '-' charClassExpr )?
+    fn char_class_expr(&mut self) -> Result<String, PatternError> {
+        self.expect('[')?;
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.next();
+        }
+
+        let mut items = String::new();
+        loop {
+            let first = items.is_empty();
+            match self.peek() {
+                None => return Err(self.error("unclosed character class")),
+                Some(']') if first => return Err(self.error("empty character class")),
+                Some(']') => break,
+                Some('-') if self.peek_at(1) == Some('[') && !first => break,
+                // A dash is a character at the start or the end of a group
+                Some('-') if first || self.peek_at(1) == Some(']') => {
+                    self.next();
+                    items.push_str(&class_char('-'));
+                }
+                Some('-') => return Err(self.error("unescaped '-'")),
+                Some('[') => return Err(self.error("unescaped '['")),
+                Some(_) => items.push_str(&self.char_range()?),
+            }
+        }
+        let group = format!("[{}{}]", if negated { "^" } else { "" }, items);
+
+        let class = if self.peek() == Some('-') {
+            self.next();
+            format!("[{}--{}]", group, self.char_class_expr()?)
+        } else {
+            group
+        };
+        self.expect(']')?;
+        Ok(class)
+    }
+
+    // charRange ::= charOrEsc '-' charOrEsc | charOrEsc | charClassEsc
+    fn char_range(&mut self) -> Result<String, PatternError> {
+        let start = match self.char_or_esc()? {
+            Escape::Char(c) => c,
+            Escape::Class(class) => return Ok(class),
+        };
+        if self.peek() != Some('-') || matches!(self.peek_at(1), Some('[' | ']')) {
+            return Ok(class_char(start));
+        }
+        self.next();
+        let end = match self.char_or_esc()? {
+            Escape::Char(c) => c,
+            Escape::Class(_) => return Err(self.error("a range can't end with a class")),
+        };
+        if end < start {
+            return Err(self.error("the range is out of order"));
+        }
+        Ok(format!("{}-{}", class_char(start), class_char(end)))
+    }
+
+    fn char_or_esc(&mut self) -> Result<Escape, PatternError> {
+        match self.peek() {
+            Some('\\') => self.escape(),
+            Some(c) => {
+                self.next();
+                Ok(Escape::Char(c))
+            }
+            None => Err(self.error("unexpected end")),
+        }
+    }
+
+    // SingleCharEsc, MultiCharEsc, catEsc and complEsc
+    fn escape(&mut self) -> Result<Escape, PatternError> {
+        self.expect('\\')?;
+        let c = self.next().ok_or_else(|| self.error("unexpected end"))?;
+        let class = match c {
+            'n' => return Ok(Escape::Char('\n')),
+            'r' => return Ok(Escape::Char('\r')),
+            't' => return Ok(Escape::Char('\t')),
+            '\\' | '|' | '.' | '?' | '*' | '+' | '(' | ')' | '{' | '}' | '-' | '[' | ']' | '^' => {
+                return Ok(Escape::Char(c))
+            }
+            's' => r"[\x20\t\n\r]".to_string(),
+            'S' => r"[^\x20\t\n\r]".to_string(),
+            'i' => format!("[{}]", NAME_START_CHARS),
+            'I' => format!("[^{}]", NAME_START_CHARS),
+            'c' => format!("[{}{}]", NAME_START_CHARS, NAME_CHARS),
+            'C' => format!("[^{}{}]", NAME_START_CHARS, NAME_CHARS),
+            'd' => r"\p{Nd}".to_string(),
+            'D' => r"\P{Nd}".to_string(),
+            'w' => r"[^\p{P}\p{Z}\p{C}]".to_string(),
+            'W' => r"[\p{P}\p{Z}\p{C}]".to_string(),
+            'p' | 'P' => {
+                let class = self.property()?;
+                if c == 'P' {
+                    format!("[^{}]", class)
+                } else {
+                    format!("[{}]", class)
+                }
+            }
+            _ => {
+                self.position -= 2;
+                return Err(self.error(&format!("unknown escape '\\{}'", c)));
+            }
+        };
+        Ok(Escape::Class(class))
+    }
+
+    // Items of a class for a general category or a block, e.g. \p{Lu} or \p{IsGreek}
+    fn property(&mut self) -> Result<String, PatternError> {
+        self.expect('{')?;
+        let start = self.position;
+        while self.peek().is_some_and(|c| c != '}') {
+            self.next();
+        }
+        let name = self.chars[start..self.position].iter().collect::<String>();
+        self.expect('}')?;
+
+        if let Some(block) = name.strip_prefix("Is") {
+            let normalize = |name: &str| name.replace([' ', '_'], "").to_lowercase();
+            let block = normalize(block);
+            let ranges = BLOCKS
+                .iter()
+                .filter(|(name, _, _)| normalize(name) == block)
+                .map(|(_, start, end)| format!(r"\x{{{:X}}}-\x{{{:X}}}", start, end))
+                .collect::<String>();
+            if !ranges.is_empty() {
+                return Ok(ranges);
+            }
+            // Surrogates can't occur in strings, so their blocks match nothing
+            if SURROGATE_BLOCKS.iter().any(|name| normalize(name) == block) {
+                return Ok(r"[^\x{0}-\x{10FFFF}]".to_string());
+            }
+            self.position = start;
+            return Err(self.error(&format!("unknown block '{}'", &name[2..])));
+        }
+        if !CATEGORIES.contains(&name.as_str()) {
+            self.position = start;
+            return Err(self.error(&format!("unknown category '{}'", name)));
+        }
+        Ok(format!(r"\p{{{}}}", name))
+    }
+}
+
+enum Escape {
+    Char(char),
+    // A class, or items which can be nested in a class
+    Class(String),
+}
+
+// A character in a class, escaped in case it has a meaning there
+fn class_char(c: char) -> String {
+    format!(r"\x{{{:X}}}", c as u32)
+}
+
+// Names of XML 1.0 (Fifth Edition), for \i and \c
+const NAME_START_CHARS: &str = r":A-Z_a-z\x{C0}-\x{D6}\x{D8}-\x{F6}\x{F8}-\x{2FF}\x{370}-\x{37D}\x{37F}-\x{1FFF}\x{200C}-\x{200D}\x{2070}-\x{218F}\x{2C00}-\x{2FEF}\x{3001}-\x{D7FF}\x{F900}-\x{FDCF}\x{FDF0}-\x{FFFD}\x{10000}-\x{EFFFF}";
+const NAME_CHARS: &str = r"\-.0-9\x{B7}\x{300}-\x{36F}\x{203F}-\x{2040}";
+
+const CATEGORIES: &[&str] = &[
+    "L", "Lu", "Ll", "Lt", "Lm", "Lo", "M", "Mn", "Mc", "Me", "N", "Nd", "Nl", "No", "P", "Pc",
+    "Pd", "Ps", "Pe", "Pi", "Pf", "Po", "Z", "Zs", "Zl", "Zp", "S", "Sm", "Sc", "Sk", "So", "C",
+    "Cc", "Cf", "Co", "Cn",
+];
+
+const SURROGATE_BLOCKS: &[&str] = &["HighSurrogates", "HighPrivateUseSurrogates", "LowSurrogates"];
+
+// Blocks of Unicode 3.1 named by XML Schema 1.0, some of them have several ranges.
+// Names are compared without spaces and case, e.g. IsBasicLatin or IsBasic_Latin.
+const BLOCKS: &[(&str, u32, u32)] = &[
+    ("BasicLatin", 0x0000, 0x007F),
+    ("Latin-1Supplement", 0x0080, 0x00FF),
+    ("LatinExtended-A", 0x0100, 0x017F),
+    ("LatinExtended-B", 0x0180, 0x024F),
+    ("IPAExtensions", 0x0250, 0x02AF),
+    ("SpacingModifierLetters", 0x02B0, 0x02FF),
+    ("CombiningDiacriticalMarks", 0x0300, 0x036F),
+    ("Greek", 0x0370, 0x03FF),
+    ("Cyrillic", 0x0400, 0x04FF),
+    ("Armenian", 0x0530, 0x058F),
+    ("Hebrew", 0x0590, 0x05FF),
+    ("Arabic", 0x0600, 0x06FF),
+    ("Syriac", 0x0700, 0x074F),
+    ("Thaana", 0x0780, 0x07BF),
+    ("Devanagari", 0x0900, 0x097F),
+    ("Bengali", 0x0980, 0x09FF),
+    ("Gurmukhi", 0x0A00, 0x0A7F),
+    ("Gujarati", 0x0A80, 0x0AFF),
+    ("Oriya", 0x0B00, 0x0B7F),
+    ("Tamil", 0x0B80, 0x0BFF),
+    ("Telugu", 0x0C00, 0x0C7F),
+    ("Kannada", 0x0C80, 0x0CFF),
+    ("Malayalam", 0x0D00, 0x0D7F),
+    ("Sinhala", 0x0D80, 0x0DFF),
+    ("Thai", 0x0E00, 0x0E7F),
+    ("Lao", 0x0E80, 0x0EFF),
+    ("Tibetan", 0x0F00, 0x0FFF),
+    ("Myanmar", 0x1000, 0x109F),
+    ("Georgian", 0x10A0, 0x10FF),
+    ("HangulJamo", 0x1100, 0x11FF),
+    ("Ethiopic", 0x1200, 0x137F),
+    ("Cherokee", 0x13A0, 0x13FF),
+    ("UnifiedCanadianAboriginalSyllabics", 0x1400, 0x167F),
+    ("Ogham", 0x1680, 0x169F),
+    ("Runic", 0x16A0, 0x16FF),
+    ("Khmer", 0x1780, 0x17FF),
+    ("Mongolian", 0x1800, 0x18AF),
+    ("LatinExtendedAdditional", 0x1E00, 0x1EFF),
+    ("GreekExtended", 0x1F00, 0x1FFF),
+    ("GeneralPunctuation", 0x2000, 0x206F),
+    ("SuperscriptsandSubscripts", 0x2070, 0x209F),
+    ("CurrencySymbols", 0x20A0, 0x20CF),
+    ("CombiningMarksforSymbols", 0x20D0, 0x20FF),
+    ("LetterlikeSymbols", 0x2100, 0x214F),
+    ("NumberForms", 0x2150, 0x218F),
+    ("Arrows", 0x2190, 0x21FF),
+    ("MathematicalOperators", 0x2200, 0x22FF),
+    ("MiscellaneousTechnical", 0x2300, 0x23FF),
+    ("ControlPictures", 0x2400, 0x243F),
+    ("OpticalCharacterRecognition", 0x2440, 0x245F),
+    ("EnclosedAlphanumerics", 0x2460, 0x24FF),
+    ("BoxDrawing", 0x2500, 0x257F),
+    ("BlockElements", 0x2580, 0x259F),
+    ("GeometricShapes", 0x25A0, 0x25FF),
+    ("MiscellaneousSymbols", 0x2600, 0x26FF),
+    ("Dingbats", 0x2700, 0x27BF),
+    ("BraillePatterns", 0x2800, 0x28FF),
+    ("CJKRadicalsSupplement", 0x2E80, 0x2EFF),
+    ("KangxiRadicals", 0x2F00, 0x2FDF),
+    ("IdeographicDescriptionCharacters", 0x2FF0, 0x2FFF),
+    ("CJKSymbolsandPunctuation", 0x3000, 0x303F),
+    ("Hiragana", 0x3040, 0x309F),
+    ("Katakana", 0x30A0, 0x30FF),
+    ("Bopomofo", 0x3100, 0x312F),
+    ("HangulCompatibilityJamo", 0x3130, 0x318F),
+    ("Kanbun", 0x3190, 0x319F),
+    ("BopomofoExtended", 0x31A0, 0x31BF),
+    ("EnclosedCJKLettersandMonths", 0x3200, 0x32FF),
+    ("CJKCompatibility", 0x3300, 0x33FF),
+    ("CJKUnifiedIdeographsExtensionA", 0x3400, 0x4DB5),
+    ("CJKUnifiedIdeographs", 0x4E00, 0x9FFF),
+    ("YiSyllables", 0xA000, 0xA48F),
+    ("YiRadicals", 0xA490, 0xA4CF),
+    ("HangulSyllables", 0xAC00, 0xD7A3),
+    ("PrivateUse", 0xE000, 0xF8FF),
+    ("CJKCompatibilityIdeographs", 0xF900, 0xFAFF),
+    ("AlphabeticPresentationForms", 0xFB00, 0xFB4F),
+    ("ArabicPresentationForms-A", 0xFB50, 0xFDFF),
+    ("CombiningHalfMarks", 0xFE20, 0xFE2F),
+    ("CJKCompatibilityForms", 0xFE30, 0xFE4F),
+    ("SmallFormVariants", 0xFE50, 0xFE6F),
+    ("ArabicPresentationForms-B", 0xFE70, 0xFEFE),
+    ("Specials", 0xFEFF, 0xFEFF),
+    ("HalfwidthandFullwidthForms", 0xFF00, 0xFFEF),
+    ("Specials", 0xFFF0, 0xFFFD),
+    ("OldItalic", 0x10300, 0x1032F),
+    ("Gothic", 0x10330, 0x1034F),
+    ("Deseret", 0x10400, 0x1044F),
+    ("ByzantineMusicalSymbols", 0x1D000, 0x1D0FF),
+    ("MusicalSymbols", 0x1D100, 0x1D1FF),
+    ("MathematicalAlphanumericSymbols", 0x1D400, 0x1D7FF),
+    ("CJKUnifiedIdeographsExtensionB", 0x20000, 0x2A6D6),
+    ("CJKCompatibilityIdeographsSupplement", 0x2F800, 0x2FA1F),
+    ("Tags", 0xE0000, 0xE007F),
+    ("PrivateUse", 0xF0000, 0xFFFFD),
+    ("PrivateUse", 0x100000, 0x10FFFD),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_match(pattern: &str, value: &str) -> bool {
+        Pattern::new(pattern).unwrap().is_match(value)
+    }
+
+    #[test]
+    fn anchoring() {
+        assert!(is_match("[0-9]+", "123"));
+        assert!(!is_match("[0-9]+", "a123"));
+        assert!(!is_match("[0-9]+", "123\n"));
+        assert!(is_match("a|ab", "ab"));
+        // ^ and $ aren't anchors
+        assert!(is_match("^a$", "^a$"));
+        assert!(!is_match("^a$", "a"));
+        assert!(!is_match(".", "\n"));
+    }
+
+    #[test]
+    fn escapes() {
+        assert!(is_match(r"\i\c*", "xs:element-1.2"));
+        assert!(!is_match(r"\i\c*", "1st"));
+        assert!(!is_match(r"\i\c*", "a b"));
+        assert!(is_match(r"\I\C", "1 "));
+        assert!(is_match(r"\d+\s\w+", "٣2 été"));
+        assert!(!is_match(r"\w", "!"));
+        assert!(is_match(r"\p{Lu}\P{Lu}", "Ab"));
+        assert!(is_match(r"[\-\[\]\^]+", "-[]^"));
+        assert!(is_match(r"\^\.$", "^.$"));
+    }
+
+    #[test]
+    fn blocks() {
+        assert!(is_match(r"\p{IsBasicLatin}+", "abc"));
+        assert!(!is_match(r"\p{IsBasicLatin}", "é"));
+        assert!(is_match(r"\p{IsLatin-1Supplement}", "é"));
+        assert!(is_match(r"\p{IsGreek}", "λ"));
+        assert!(is_match(r"\P{IsGreek}", "l"));
+        assert!(is_match(r"\p{IsSpecials}", "\u{FFFD}"));
+        assert!(is_match(r"\p{IsSpecials}", "\u{FEFF}"));
+        assert!(is_match(r"[\p{IsPrivateUse}]", "\u{F0000}"));
+        assert!(!is_match(r"\p{IsHighSurrogates}", "a"));
+        assert!(is_match(r"\P{IsHighSurrogates}", "a"));
+    }
+
+    #[test]
+    fn subtraction() {
+        let consonant = "[a-z-[aeiou]]";
+        assert!(is_match(consonant, "b"));
+        assert!(!is_match(consonant, "a"));
+        assert!(!is_match(consonant, "B"));
+        assert!(is_match("[^a-z-[XYZ]]", "B"));
+        assert!(!is_match("[^a-z-[XYZ]]", "Y"));
+        assert!(!is_match("[^a-z-[xyz]]", "b"));
+        assert!(is_match("[a-z-[b-y-[c]]]", "c"));
+        assert!(is_match("[-+]?[0-9]+", "-1"));
+        assert!(is_match("[a-]", "-"));
+    }
+
+    #[test]
+    fn quantifiers() {
+        assert!(is_match("a{2}", "aa"));
+        assert!(is_match("a{2,}", "aaa"));
+        assert!(!is_match("(ab){1,2}", "ababab"));
+        assert!(is_match("[ -~]{8,63}", "password"));
+        assert!(is_match("[0-9]+(.[0-9]+)*", "1.2.3"));
+    }
+
+    #[test]
+    fn errors() {
+        let error = |pattern| translate(pattern).unwrap_err();
+        assert_eq!(
+            error(r"a\q").to_string(),
+            r#"Invalid pattern "a\\q" at 1: unknown escape '\q'"#
+        );
+        assert_eq!(error("(a").reason, "expected ')'");
+        assert_eq!(error("a)").reason, "unmatched ')'");
+        assert_eq!(error("*a").reason, "quantifier without an atom");
+        assert_eq!(error("a+?").reason, "quantifier without an atom");
+        assert_eq!(error("a{2,1}").reason, "the maximum is less than the minimum");
+        assert_eq!(error("[z-a]").reason, "the range is out of order");
+        assert_eq!(error("[a-c-e]").reason, "unescaped '-'");
+        assert_eq!(error("[a[b]]").reason, "unescaped '['");
+        assert_eq!(error("[]").reason, "empty character class");
+        assert_eq!(error(r"\p{IsKlingon}").reason, "unknown block 'Klingon'");
+        assert_eq!(error(r"\p{Xx}").reason, "unknown category 'Xx'");
+        assert!(Pattern::new("[a").is_err());
+    }
+}
@@ -1,4 +1,6 @@
 use chrono::FixedOffset;
+use xml::escape::escape_str_pcdata;
+use yaserde::YaDeserialize;
 
 // Parses ISO 8601 timezone.
 pub fn parse_timezone(s: &str) -> Result<FixedOffset, String> {
@@ -29,9 +31,32 @@ pub fn parse_timezone(s: &str) -> Result<FixedOffset, String> {
     }
 }
 
+// Deserializes a value of a simple type from its lexical representation,
+// e.g. the default or fixed value of an element or attribute.
+pub fn from_text<T: YaDeserialize>(text: &str) -> Result<T, String> {
+    yaserde::de::from_str(&format!("<value>{}</value>", escape_str_pcdata(text)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::Integer;
+    use yaserde_derive::YaDeserialize;
+
+    #[derive(Default, PartialEq, Debug, YaDeserialize)]
+    enum Protocol {
+        #[default]
+        #[yaserde(rename = "HTTP")]
+        Http,
+        #[yaserde(rename = "RTSP")]
+        Rtsp,
+    }
+
+    #[test]
+    fn from_text_test() {
+        assert_eq!(from_text::<Integer>("-42"), Ok(Integer::from_bigint((-42).into())));
+        assert_eq!(from_text::<Protocol>("RTSP"), Ok(Protocol::Rtsp));
+    }
 
     #[test]
     fn timezone_parse_test() {
@@ -0,0 +1,488 @@
+use std::{collections::HashMap, fmt, ops::Deref};
+
+use crate::types::{Boxed, Choice, Choices, Mixed, MixedStep, Nillable};
+
+// Checks of the constraints of the schema. The generated impls check the facets,
+// fixed values and occurrences of the fields of a type, and walk the fields of
+// generated types, so a value is checked with everything nested in it.
+//
+// validate returned `Result<(), String>` before, impls of it no longer compile and
+// have to be migrated (see the Validation section of the README): either renamed to
+// validate_legacy, which validate_at calls unless it is implemented, or rewritten as
+// validate_at. Messages convert with `?` (ValidationError is From<String>).
+pub trait Validate {
+    // Checks the value, every error has the path of the invalid element
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        self.validate_at("", &mut errors);
+        errors.into_result()
+    }
+
+    // Adds the errors of the value, `path` is the path of its element
+    fn validate_at(&self, path: &str, errors: &mut ValidationErrors) {
+        #[allow(deprecated)]
+        errors.check(path, || Ok(self.validate_legacy()?));
+    }
+
+    // The former validate, its message is reported as an Other error at the path of the value
+    #[deprecated(note = "implement validate_at, which reports every error with its path")]
+    fn validate_legacy(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+// Wrappers of the generated fields are checked as their values
+impl<T: Validate> Validate for Option<T> {
+    fn validate_at(&self, path: &str, errors: &mut ValidationErrors) {
+        if let Some(value) = self {
+            value.validate_at(path, errors);
+        }
+    }
+}
+
+// Items are numbered from 1 like the elements of XPath, e.g. /Profile[2]
+impl<T: Validate> Validate for Vec<T> {
+    fn validate_at(&self, path: &str, errors: &mut ValidationErrors) {
+        for (i, value) in self.iter().enumerate() {
+            value.validate_at(&format!("{}[{}]", path, i + 1), errors);
+        }
+    }
+}
+
+impl<T: Validate> Validate for Boxed<T> {
+    fn validate_at(&self, path: &str, errors: &mut ValidationErrors) {
+        self.0.validate_at(path, errors);
+    }
+}
+
+impl<T: Validate> Validate for Nillable<T> {
+    fn validate_at(&self, path: &str, errors: &mut ValidationErrors) {
+        if let Some(value) = self.as_option() {
+            value.validate_at(path, errors);
+        }
+    }
+}
+
+// Alternatives are at the path of the parent and add their element names, see validate_items
+impl<T: Choice + Validate> Validate for Choices<T> {
+    fn validate_at(&self, path: &str, errors: &mut ValidationErrors) {
+        validate_items(&self.0, path, errors);
+    }
+}
+
+impl<T: Choice + Validate> Validate for Mixed<T> {
+    fn validate_at(&self, path: &str, errors: &mut ValidationErrors) {
+        validate_items(&self.0, path, errors);
+    }
+}
+
+impl<T: Choice> Mixed<T> {
+    // Checks that the child elements follow the sequence of a mixed type: the steps in
+    // order, each from min to max times. The text can be anywhere.
+    pub fn validate_sequence(&self, steps: &[MixedStep]) -> Result<(), ValidationError> {
+        let names = self
+            .iter()
+            .filter_map(|item| item.elements())
+            .flat_map(|(name, count)| std::iter::repeat_n(name, count))
+            .collect::<Vec<_>>();
+        let order = steps.iter().map(|step| step.name).collect::<Vec<_>>().join(", ");
+        let out_of_order = |name: &str, position: usize| {
+            ValidationError::new(
+                FacetKind::Other,
+                name,
+                format!("in the order {}", order),
+                format!("child element {}", position + 1),
+            )
+        };
+
+        let mut position = 0;
+        for step in steps {
+            let mut count = 0;
+            while step.max.is_none_or(|max| count < max) && names.get(position) == Some(&step.name)
+            {
+                position += 1;
+                count += 1;
+            }
+            if count < step.min {
+                return Err(match names[position..].iter().position(|name| *name == step.name) {
+                    Some(found) => out_of_order(step.name, position + found),
+                    None => ValidationError::new(
+                        FacetKind::MinOccurs,
+                        step.name,
+                        format!(">= {}", step.min),
+                        count,
+                    ),
+                });
+            }
+        }
+
+        let name = match names.get(position) {
+            Some(name) => *name,
+            None => return Ok(()),
+        };
+        let total = names.iter().filter(|n| **n == name).count();
+        match steps.iter().find(|step| step.name == name).and_then(|step| step.max) {
+            Some(max) if total > max => {
+                Err(ValidationError::new(FacetKind::MaxOccurs, name, format!("<= {}", max), total))
+            }
+            _ => Err(out_of_order(name, position)),
+        }
+    }
+}
+
+// Items of a repeated choice. An element alternative numbers its elements from the
+// first one of the item, so the errors are numbered among the elements of the same
+// name of the parent, e.g. the item holding the second Pickup reports /Order/Pickup[2].
+fn validate_items<T: Choice + Validate>(items: &[T], path: &str, errors: &mut ValidationErrors) {
+    let mut counts = HashMap::new();
+    for item in items {
+        let mut item_errors = ValidationErrors::default();
+        item.validate_at(path, &mut item_errors);
+        let (name, count) = match item.elements() {
+            Some(elements) => elements,
+            None => {
+                errors.0.extend(item_errors.0);
+                continue;
+            }
+        };
+        let element = format!("{}/{}", path, name);
+        let offset = counts.entry(name).or_insert(0);
+        for error in item_errors.0 {
+            let path = renumber(&error.path, &element, *offset);
+            errors.0.push(ValidationError { path, ..error });
+        }
+        *offset += count;
+    }
+}
+
+// Adds the offset to the number of the element in the path, an unnumbered element is the first one
+fn renumber(path: &str, element: &str, offset: usize) -> String {
+    let rest = match path.strip_prefix(element) {
+        Some(rest) => rest,
+        None => return path.to_string(),
+    };
+    if rest.is_empty() || rest.starts_with('/') {
+        return format!("{}[{}]{}", element, offset + 1, rest);
+    }
+    let numbered = rest.strip_prefix('[').and_then(|rest| rest.split_once(']'));
+    match numbered.and_then(|(number, rest)| Some((number.parse::<usize>().ok()?, rest))) {
+        Some((number, rest)) => format!("{}[{}]{}", element, offset + number, rest),
+        // Another element whose name starts with the name
+        None => path.to_string(),
+    }
+}
+
+// Constraint violated by a value: a facet, or a constraint of the declaration of its
+// element. Values not of their type (the text of a simple content) are Type errors,
+// and the errors converted from messages are Other ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FacetKind {
+    Length,
+    MinLength,
+    MaxLength,
+    Pattern,
+    Enumeration,
+    WhiteSpace,
+    MaxInclusive,
+    MaxExclusive,
+    MinInclusive,
+    MinExclusive,
+    TotalDigits,
+    FractionDigits,
+    Fixed,
+    MinOccurs,
+    MaxOccurs,
+    Namespace,
+    ProcessContents,
+    Type,
+    Other,
+}
+
+impl FacetKind {
+    // What the expected and actual values of the constraint measure, empty for the value itself
+    fn measure(self) -> &'static str {
+        match self {
+            FacetKind::Length | FacetKind::MinLength | FacetKind::MaxLength => " length",
+            FacetKind::MinOccurs | FacetKind::MaxOccurs => " occurs",
+            FacetKind::Namespace => " namespace",
+            FacetKind::ProcessContents => " declaration",
+            _ => "",
+        }
+    }
+}
+
+impl fmt::Display for FacetKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+// Constraint of the schema violated by a value, found by the generated Validate impls
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    // Path of the invalid element (or attribute) from the validated value,
+    // e.g. /Profile/VideoEncoderConfiguration/Resolution/Width, empty for the value itself
+    pub path: String,
+    pub kind: FacetKind,
+    // The constraint, e.g. "<= 1920" for a maxInclusive facet
+    pub expected: String,
+    // The value (or its length, number of occurrences, namespace) as found
+    pub actual: String,
+    pub message: String,
+}
+
+impl ValidationError {
+    pub fn new(
+        kind: FacetKind,
+        name: &str,
+        expected: impl Into<String>,
+        actual: impl ToString,
+    ) -> Self {
+        let expected = expected.into();
+        let actual = actual.to_string();
+        let message = format!(
+            "{kind} validation error: invalid value of {name}! \nExpected: {name}{measure} {expected}.\nActual: {name}{measure} == {actual}",
+            kind = kind,
+            name = name,
+            measure = kind.measure(),
+            expected = expected,
+            actual = actual
+        );
+        Self { path: String::new(), kind, expected, actual, message }
+    }
+}
+
+impl From<String> for ValidationError {
+    fn from(message: String) -> Self {
+        Self {
+            path: String::new(),
+            kind: FacetKind::Other,
+            expected: String::new(),
+            actual: String::new(),
+            message,
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+// Every error found in a value and the values nested in it, in document order
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ValidationErrors(pub Vec<ValidationError>);
+
+impl ValidationErrors {
+    // Runs the checks of the value at the path, they stop at the first error
+    pub fn check(&mut self, path: &str, checks: impl FnOnce() -> Result<(), ValidationError>) {
+        if let Err(error) = checks() {
+            self.0.push(ValidationError { path: path.to_string(), ..error });
+        }
+    }
+
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.0.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl Deref for ValidationErrors {
+    type Target = Vec<ValidationError>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<String> for ValidationErrors {
+    fn from(message: String) -> Self {
+        Self(vec![message.into()])
+    }
+}
+
+impl From<ValidationErrors> for String {
+    fn from(errors: ValidationErrors) -> Self {
+        errors.to_string()
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, error) in self.0.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors() {
+        let mut errors = ValidationErrors::default();
+        errors.check("/Resolution/Width", || Ok(()));
+        assert_eq!(errors.clone().into_result(), Ok(()));
+
+        errors.check("/Resolution/Width", || {
+            Err(ValidationError::new(FacetKind::MaxInclusive, "width", "<= 1920", 4096))
+        });
+        errors.check("", || Err("invalid".to_string().into()));
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].kind, FacetKind::MaxInclusive);
+        assert_eq!((errors[0].expected.as_str(), errors[0].actual.as_str()), ("<= 1920", "4096"));
+        assert_eq!(errors[1].kind, FacetKind::Other);
+        assert_eq!(
+            errors.to_string(),
+            "/Resolution/Width: MaxInclusive validation error: invalid value of width! \
+             \nExpected: width <= 1920.\nActual: width == 4096\ninvalid"
+        );
+        assert!(errors.into_result().is_err());
+    }
+
+    #[test]
+    fn measures() {
+        let error = ValidationError::new(FacetKind::MinOccurs, "items", ">= 2", 0);
+        assert!(error.message.contains("Expected: items occurs >= 2.\nActual: items occurs == 0"));
+    }
+
+    // Positive numbers, errors are reported at the path of the value
+    struct Positive(i32);
+    impl Validate for Positive {
+        fn validate_at(&self, path: &str, errors: &mut ValidationErrors) {
+            errors.check(path, || match self.0 {
+                0.. => Ok(()),
+                _ => Err(ValidationError::new(FacetKind::MinInclusive, "value", ">= 0", self.0)),
+            });
+        }
+    }
+
+    #[test]
+    fn positions() {
+        let errors = paths(&vec![Positive(-1), Positive(1), Positive(-2)], "/Item");
+        assert_eq!(errors, ["/Item[1]", "/Item[3]"]);
+    }
+
+    // Alternatives of a choice: an element (maybe repeated) or a sequence of elements
+    enum Alternative {
+        Pickup(Positive),
+        Codes(Vec<Positive>),
+        Carrier(Positive),
+    }
+
+    impl Choice for Alternative {
+        fn read(_: &[crate::types::AnyElement]) -> Result<Option<(Self, usize)>, String> {
+            unimplemented!()
+        }
+
+        fn write(&self, _: &mut Vec<crate::types::AnyElement>) -> Result<(), String> {
+            unimplemented!()
+        }
+
+        fn elements(&self) -> Option<(&'static str, usize)> {
+            match self {
+                Alternative::Pickup(_) => Some(("Pickup", 1)),
+                Alternative::Codes(codes) => Some(("Code", codes.len())),
+                Alternative::Carrier(_) => None,
+            }
+        }
+    }
+
+    impl Validate for Alternative {
+        fn validate_at(&self, path: &str, errors: &mut ValidationErrors) {
+            match self {
+                Alternative::Pickup(value) => {
+                    value.validate_at(&format!("{}/Pickup", path), errors)
+                }
+                Alternative::Codes(values) => values.validate_at(&format!("{}/Code", path), errors),
+                Alternative::Carrier(value) => {
+                    value.validate_at(&format!("{}/Sequence/Carrier", path), errors)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn choice_positions() {
+        use Alternative::*;
+
+        let choices = Choices(vec![
+            Pickup(Positive(1)),
+            Codes(vec![Positive(1), Positive(-1)]),
+            Carrier(Positive(-1)),
+            Pickup(Positive(-1)),
+            Codes(vec![Positive(-1)]),
+        ]);
+        assert_eq!(
+            paths(&choices, "/Order"),
+            ["/Order/Code[2]", "/Order/Sequence/Carrier", "/Order/Pickup[2]", "/Order/Code[3]"]
+        );
+    }
+
+    fn paths(value: &impl Validate, path: &str) -> Vec<String> {
+        let mut errors = ValidationErrors::default();
+        value.validate_at(path, &mut errors);
+        errors.0.into_iter().map(|error| error.path).collect()
+    }
+
+    // Impls returning messages convert them
+    #[test]
+    fn messages() {
+        fn check(value: i32) -> Result<(), String> {
+            if value < 0 {
+                return Err(format!("{} is negative", value));
+            }
+            Ok(())
+        }
+
+        struct Checked(i32);
+        impl Validate for Checked {
+            fn validate_at(&self, path: &str, errors: &mut ValidationErrors) {
+                errors.check(path, || Ok(check(self.0)?));
+            }
+        }
+
+        assert!(Checked(1).validate().is_ok());
+        assert_eq!(String::from(Checked(-1).validate().unwrap_err()), "-1 is negative");
+        assert_eq!(Some(Checked(-1)).validate().unwrap_err()[0].kind, FacetKind::Other);
+    }
+
+    // Impls of the former validate are reached through validate_at
+    #[test]
+    fn legacy() {
+        struct Legacy(i32);
+        impl Validate for Legacy {
+            fn validate_legacy(&self) -> Result<(), String> {
+                match self.0 {
+                    0.. => Ok(()),
+                    _ => Err(format!("{} is negative", self.0)),
+                }
+            }
+        }
+
+        assert!(Legacy(1).validate().is_ok());
+        assert_eq!(paths(&vec![Legacy(1), Legacy(-1)], "/Item"), ["/Item[2]"]);
+        let errors = Some(Legacy(-1)).validate().unwrap_err();
+        assert_eq!(
+            (errors[0].kind, errors[0].message.as_str()),
+            (FacetKind::Other, "-1 is negative")
+        );
+    }
+}
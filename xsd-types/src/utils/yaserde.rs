@@ -45,3 +45,45 @@ pub fn deserialize<S, R: Read>(
         de_fn("")
     }
 }
+
+pub const XSI_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+// Writes an empty element marked with xsi:nil="true"
+pub fn serialize_nil<W: Write>(
+    default_name: &str,
+    writer: &mut ser::Serializer<W>,
+) -> Result<(), String> {
+    if writer.skip_start_end() {
+        return Ok(());
+    }
+
+    let name = writer.get_start_event_name().unwrap_or_else(|| default_name.to_string());
+    writer
+        .write(
+            xml::writer::XmlEvent::start_element(name.as_str())
+                .attr("xsi:nil", "true")
+                .ns("xsi", XSI_NAMESPACE),
+        )
+        .map_err(|_e| "Start element write failed".to_string())?;
+
+    writer
+        .write(xml::writer::XmlEvent::end_element())
+        .map_err(|_e| "End element write failed".to_string())
+}
+
+// Consumes the start of the next element if it is marked with xsi:nil="true"
+pub fn deserialize_nil<R: Read>(reader: &mut de::Deserializer<R>) -> Result<bool, String> {
+    let is_nil = match reader.peek()? {
+        xml::reader::XmlEvent::StartElement { attributes, .. } => attributes.iter().any(|a| {
+            a.name.local_name == "nil"
+                && a.name.namespace.as_deref() == Some(XSI_NAMESPACE)
+                && (a.value == "true" || a.value == "1")
+        }),
+        _ => return Err("Start element not found".to_string()),
+    };
+
+    if is_nil {
+        reader.next_event()?;
+    }
+    Ok(is_nil)
+}